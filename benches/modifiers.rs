@@ -0,0 +1,35 @@
+#[macro_use]
+extern crate criterion;
+extern crate noise;
+
+use criterion::{black_box, Criterion};
+use noise::{Constant, NoiseFn, Perlin};
+
+criterion_group!(modifiers, bench_modifier_chain, bench_hand_written_chain);
+criterion_main!(modifiers);
+
+// The trivial modifiers and combiners are inlined, so a chain of them should
+// cost the same as the equivalent arithmetic written by hand.
+fn bench_modifier_chain(c: &mut Criterion) {
+    let perlin = Perlin::default();
+    let chain = NoiseFn::<f64, 2>::abs(perlin)
+        .negate()
+        .scale_bias()
+        .set_scale(0.5)
+        .set_bias(0.25)
+        .add(Constant::new(0.125))
+        .multiply(Constant::new(2.0));
+    c.bench_function("modifier chain 2d", |b| {
+        b.iter(|| chain.get(black_box([42.4_f64, 37.7])))
+    });
+}
+
+fn bench_hand_written_chain(c: &mut Criterion) {
+    let perlin = Perlin::default();
+    c.bench_function("hand-written chain 2d", |b| {
+        b.iter(|| {
+            let value = -perlin.get(black_box([42.4_f64, 37.7])).abs();
+            (value.mul_add(0.5, 0.25) + 0.125) * 2.0
+        })
+    });
+}
@@ -12,7 +12,12 @@ criterion_group!(
     bench_perlin3_64x64,
     bench_perlin4_64x64
 );
-criterion_main!(perlin, perlin_64x64);
+criterion_group!(
+    perlin_construction,
+    bench_perlin_new,
+    bench_perlin2_new_and_get
+);
+criterion_main!(perlin, perlin_64x64, perlin_construction);
 
 fn bench_perlin2(c: &mut Criterion) {
     let perlin = Perlin::default();
@@ -35,6 +40,19 @@ fn bench_perlin4(c: &mut Criterion) {
     });
 }
 
+// The permutation table is built once when the generator is constructed or
+// reseeded, so `get` only looks values up in it. Compare these against
+// `perlin 2d` to see how much of the cost is the table construction.
+fn bench_perlin_new(c: &mut Criterion) {
+    c.bench_function("perlin new", |b| b.iter(|| Perlin::new(black_box(42))));
+}
+
+fn bench_perlin2_new_and_get(c: &mut Criterion) {
+    c.bench_function("perlin 2d (new and get)", |b| {
+        b.iter(|| Perlin::new(black_box(42)).get(black_box([42.0_f64, 37.0])))
+    });
+}
+
 fn bench_perlin2_64x64(c: &mut Criterion) {
     let perlin = Perlin::default();
     c.bench_function("perlin 2d (64x64)", |b| {
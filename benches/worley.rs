@@ -2,7 +2,7 @@ extern crate criterion;
 extern crate noise;
 
 use criterion::*;
-use noise::{NoiseFn, ReturnType, Worley};
+use noise::{core::worley::distance_functions, NoiseFn, ReturnType, Worley};
 use rand::Rng;
 
 criterion_group!(worley_range, bench_worley2d, bench_worley3d, bench_worley4d,);
@@ -17,6 +17,9 @@ fn bench_worley2d(c: &mut Criterion) {
 
     let worley = Worley::default();
     let worley_range = Worley::default().set_return_type(ReturnType::Distance);
+    let worley_range_squared = worley_range
+        .clone()
+        .set_distance_function(distance_functions::euclidean_squared);
 
     let mut rng = rand_pcg::Pcg64Mcg::new(rand::random());
 
@@ -46,6 +49,14 @@ fn bench_worley2d(c: &mut Criterion) {
                 }
             })
         });
+
+        group.bench_function(BenchmarkId::new("worley - range squared", size), |b| {
+            b.iter(|| {
+                for point in &points {
+                    black_box(worley_range_squared.get(*point));
+                }
+            })
+        });
     }
 
     group.finish();
@@ -60,6 +71,9 @@ fn bench_worley3d(c: &mut Criterion) {
 
     let worley = Worley::default();
     let worley_range = Worley::default().set_return_type(ReturnType::Distance);
+    let worley_range_squared = worley_range
+        .clone()
+        .set_distance_function(distance_functions::euclidean_squared);
 
     let mut rng = rand_pcg::Pcg64Mcg::new(rand::random());
 
@@ -89,6 +103,14 @@ fn bench_worley3d(c: &mut Criterion) {
                 }
             })
         });
+
+        group.bench_function(BenchmarkId::new("worley - range squared", size), |b| {
+            b.iter(|| {
+                for point in &points {
+                    black_box(worley_range_squared.get(*point));
+                }
+            })
+        });
     }
 
     group.finish();
@@ -103,6 +125,9 @@ fn bench_worley4d(c: &mut Criterion) {
 
     let worley = Worley::default();
     let worley_range = Worley::default().set_return_type(ReturnType::Distance);
+    let worley_range_squared = worley_range
+        .clone()
+        .set_distance_function(distance_functions::euclidean_squared);
 
     let mut rng = rand_pcg::Pcg64Mcg::new(rand::random());
 
@@ -132,6 +157,14 @@ fn bench_worley4d(c: &mut Criterion) {
                 }
             })
         });
+
+        group.bench_function(BenchmarkId::new("worley - range squared", size), |b| {
+            b.iter(|| {
+                for point in &points {
+                    black_box(worley_range_squared.get(*point));
+                }
+            })
+        });
     }
 
     group.finish();
@@ -1,15 +1,32 @@
 use crate::{
     math::{
+        coordinate::{lattice_point, Coordinate},
         s_curve::quintic::Quintic,
         vectors::{Vector, Vector2, Vector3, Vector4},
     },
     permutationtable::NoiseHasher,
 };
+use alloc::vec::Vec;
 use core::f64;
 
 #[inline(always)]
-pub fn perlin_2d<NH>(point: [f64; 2], hasher: &NH) -> f64
+#[rustfmt::skip]
+fn gradient_dot_v_2d(perm: usize, point: Vector2<f64>) -> f64 {
+    let [x, y] = point.into_array();
+
+    match perm & 0b11 {
+        0 =>  x + y, // ( 1,  1)
+        1 => -x + y, // (-1,  1)
+        2 =>  x - y, // ( 1, -1)
+        3 => -x - y, // (-1, -1)
+        _ => unreachable!(),
+    }
+}
+
+#[inline(always)]
+pub fn perlin_2d<T, NH>(point: [T; 2], hasher: &NH) -> f64
 where
+    T: Coordinate,
     NH: NoiseHasher + ?Sized,
 {
     // Unscaled range of linearly interpolated perlin noise should be (-sqrt(N)/2, sqrt(N)/2).
@@ -19,31 +36,14 @@ where
     // 1/(sqrt(N)/2), N=2 -> sqrt(2)
     const SCALE_FACTOR: f64 = f64::consts::SQRT_2;
 
-    let point = Vector2::from(point);
-
-    #[inline(always)]
-    #[rustfmt::skip]
-    fn gradient_dot_v(perm: usize, point: Vector2<f64>) -> f64 {
-        let [x, y] = point.into_array();
-
-        match perm & 0b11 {
-            0 =>  x + y, // ( 1,  1)
-            1 => -x + y, // (-1,  1)
-            2 =>  x - y, // ( 1, -1)
-            3 => -x - y, // (-1, -1)
-            _ => unreachable!(),
-        }
-    }
-
-    let floored = point.floor();
-    let corner: Vector2<isize> = floored.numcast().unwrap();
-    let distance = point - floored;
+    let (corner, distance) = lattice_point(point);
+    let (corner, distance) = (Vector2::from(corner), Vector2::from(distance));
 
     macro_rules! call_gradient(
         ($x:expr, $y:expr) => {
             {
                 let offset = Vector2::new($x, $y);
-                gradient_dot_v(
+                gradient_dot_v_2d(
                     hasher.hash(&(corner + offset).into_array()),
                     distance - offset.numcast().unwrap()
                 )
@@ -79,8 +79,31 @@ fn bilinear_interpolation(u: f64, v: f64, g00: f64, g01: f64, g10: f64, g11: f64
 }
 
 #[inline(always)]
-pub fn perlin_3d<NH>(point: [f64; 3], hasher: &NH) -> f64
+#[rustfmt::skip]
+fn gradient_dot_v_3d(perm: usize, point: Vector3<f64>) -> f64 {
+    let [x, y, z] = point.into_array();
+
+    match perm & 0b1111 {
+        0  | 12 =>  x + y    , // ( 1,  1,  0)
+        1  | 13 => -x + y    , // (-1,  1,  0)
+        2       =>  x - y    , // ( 1, -1,  0)
+        3       => -x - y    , // (-1, -1,  0)
+        4       =>  x     + z, // ( 1,  0,  1)
+        5       => -x     + z, // (-1,  0,  1)
+        6       =>  x     - z, // ( 1,  0, -1)
+        7       => -x     - z, // (-1,  0, -1)
+        8       =>      y + z, // ( 0,  1,  1)
+        9  | 14 =>     -y + z, // ( 0, -1,  1)
+        10      =>      y - z, // ( 0,  1, -1)
+        11 | 15 =>     -y - z, // ( 0, -1, -1)
+        _ => unreachable!(),
+    }
+}
+
+#[inline(always)]
+pub fn perlin_3d<T, NH>(point: [T; 3], hasher: &NH) -> f64
 where
+    T: Coordinate,
     NH: NoiseHasher + ?Sized,
 {
     // Unscaled range of linearly interpolated perlin noise should be (-sqrt(N)/2, sqrt(N)/2).
@@ -93,39 +116,14 @@ where
     // 2/sqrt(3) = 1.1547005383792515290182975610039149112952035025402537520372046529
     const SCALE_FACTOR: f64 = 1.154_700_538_379_251_5;
 
-    let point = Vector3::from(point);
-
-    #[inline(always)]
-    #[rustfmt::skip]
-    fn gradient_dot_v(perm: usize, point: Vector3<f64>) -> f64 {
-        let [x, y, z] = point.into_array();
-
-        match perm & 0b1111 {
-            0  | 12 =>  x + y    , // ( 1,  1,  0)
-            1  | 13 => -x + y    , // (-1,  1,  0)
-            2       =>  x - y    , // ( 1, -1,  0)
-            3       => -x - y    , // (-1, -1,  0)
-            4       =>  x     + z, // ( 1,  0,  1)
-            5       => -x     + z, // (-1,  0,  1)
-            6       =>  x     - z, // ( 1,  0, -1)
-            7       => -x     - z, // (-1,  0, -1)
-            8       =>      y + z, // ( 0,  1,  1)
-            9  | 14 =>     -y + z, // ( 0, -1,  1)
-            10      =>      y - z, // ( 0,  1, -1)
-            11 | 15 =>     -y - z, // ( 0, -1, -1)
-            _ => unreachable!(),
-        }
-    }
-
-    let floored = point.floor();
-    let corner: Vector3<isize> = floored.numcast().unwrap();
-    let distance = point - floored;
+    let (corner, distance) = lattice_point(point);
+    let (corner, distance) = (Vector3::from(corner), Vector3::from(distance));
 
     macro_rules! call_gradient(
         ($x:expr, $y:expr, $z:expr) => {
             {
                 let offset = Vector3::new($x, $y, $z);
-                gradient_dot_v(
+                gradient_dot_v_3d(
                     hasher.hash(&(corner + offset).into_array()),
                     distance - offset.numcast().unwrap()
                 )
@@ -165,8 +163,47 @@ where
 }
 
 #[inline(always)]
-pub fn perlin_4d<NH>(point: [f64; 4], hasher: &NH) -> f64
+#[rustfmt::skip]
+fn gradient_dot_v_4d(perm: usize, point: Vector4<f64>) -> f64 {
+    let [x, y, z, w] = point.into_array();
+
+    match perm & 0b11111 {
+        0  | 28 =>  x + y + z    , // ( 1,  1,  1,  0)
+        1       => -x + y + z    , // (-1,  1,  1,  0)
+        2       =>  x - y + z    , // ( 1, -1,  1,  0)
+        3       =>  x + y - z    , // ( 1,  1, -1,  0)
+        4       => -x + y - z    , // (-1,  1, -1,  0)
+        5       =>  x - y - z    , // ( 1, -1, -1,  0)
+        6       =>  x - y - z    , // (-1, -1, -1,  0)
+        7  | 29 =>  x + y     + w, // ( 1,  1,  0,  1)
+        8       => -x + y     + w, // (-1,  1,  0,  1)
+        9       =>  x - y     + w, // ( 1, -1,  0,  1)
+        10      =>  x + y     - w, // ( 1,  1,  0, -1)
+        11      =>  x + y     - w, // (-1,  1,  0, -1)
+        12      =>  x + y     - w, // ( 1, -1,  0, -1)
+        13      => -x - y     - w, // (-1, -1,  0, -1)
+        14 | 30 =>  x     + z + w, // ( 1,  0,  1,  1)
+        15      => -x     + z + w, // (-1,  0,  1,  1)
+        16      =>  x     - z + w, // ( 1,  0, -1,  1)
+        17      =>  x     + z - w, // ( 1,  0,  1, -1)
+        18      =>  x     + z - w, // (-1,  0,  1, -1)
+        19      =>  x     + z - w, // ( 1,  0, -1, -1)
+        20      => -x     - z - w, // (-1,  0, -1, -1)
+        21 | 31 =>      y + z + w, // ( 0,  1,  1,  1)
+        22      =>     -y + z + w, // ( 0, -1,  1,  1)
+        23      =>      y - z + w, // ( 0,  1, -1,  1)
+        24      =>      y - z - w, // ( 0,  1,  1, -1)
+        25      =>     -y - z - w, // ( 0, -1,  1, -1)
+        26      =>  x + y + z - w, // ( 0,  1, -1, -1)
+        27      => -x + y + z - w, // ( 0, -1, -1, -1)
+        _ => unreachable!(),
+    }
+}
+
+#[inline(always)]
+pub fn perlin_4d<T, NH>(point: [T; 4], hasher: &NH) -> f64
 where
+    T: Coordinate,
     NH: NoiseHasher + ?Sized,
 {
     // Unscaled range of linearly interpolated perlin noise should be (-sqrt(N)/2, sqrt(N)/2).
@@ -174,55 +211,14 @@ where
     // range of (-1, 1).
     const SCALE_FACTOR: f64 = 1.0; // 1/(sqrt(N)/2), N=4 -> 2/sqrt(4) -> 2/2 -> 1
 
-    let point = Vector4::from(point);
-
-    #[inline(always)]
-    #[rustfmt::skip]
-    fn gradient_dot_v(perm: usize, point: Vector4<f64>) -> f64 {
-        let [x, y, z, w] = point.into_array();
-
-        match perm & 0b11111 {
-            0  | 28 =>  x + y + z    , // ( 1,  1,  1,  0)
-            1       => -x + y + z    , // (-1,  1,  1,  0)
-            2       =>  x - y + z    , // ( 1, -1,  1,  0)
-            3       =>  x + y - z    , // ( 1,  1, -1,  0)
-            4       => -x + y - z    , // (-1,  1, -1,  0)
-            5       =>  x - y - z    , // ( 1, -1, -1,  0)
-            6       =>  x - y - z    , // (-1, -1, -1,  0)
-            7  | 29 =>  x + y     + w, // ( 1,  1,  0,  1)
-            8       => -x + y     + w, // (-1,  1,  0,  1)
-            9       =>  x - y     + w, // ( 1, -1,  0,  1)
-            10      =>  x + y     - w, // ( 1,  1,  0, -1)
-            11      =>  x + y     - w, // (-1,  1,  0, -1)
-            12      =>  x + y     - w, // ( 1, -1,  0, -1)
-            13      => -x - y     - w, // (-1, -1,  0, -1)
-            14 | 30 =>  x     + z + w, // ( 1,  0,  1,  1)
-            15      => -x     + z + w, // (-1,  0,  1,  1)
-            16      =>  x     - z + w, // ( 1,  0, -1,  1)
-            17      =>  x     + z - w, // ( 1,  0,  1, -1)
-            18      =>  x     + z - w, // (-1,  0,  1, -1)
-            19      =>  x     + z - w, // ( 1,  0, -1, -1)
-            20      => -x     - z - w, // (-1,  0, -1, -1)
-            21 | 31 =>      y + z + w, // ( 0,  1,  1,  1)
-            22      =>     -y + z + w, // ( 0, -1,  1,  1)
-            23      =>      y - z + w, // ( 0,  1, -1,  1)
-            24      =>      y - z - w, // ( 0,  1,  1, -1)
-            25      =>     -y - z - w, // ( 0, -1,  1, -1)
-            26      =>  x + y + z - w, // ( 0,  1, -1, -1)
-            27      => -x + y + z - w, // ( 0, -1, -1, -1)
-            _ => unreachable!(),
-        }
-    }
-
-    let floored = point.floor();
-    let corner: Vector4<isize> = floored.numcast().unwrap();
-    let distance = point - floored;
+    let (corner, distance) = lattice_point(point);
+    let (corner, distance) = (Vector4::from(corner), Vector4::from(distance));
 
     macro_rules! call_gradient(
         ($x:expr, $y:expr, $z:expr, $w:expr) => {
             {
                 let offset = Vector4::new($x, $y, $z, $w);
-                gradient_dot_v(
+                gradient_dot_v_4d(
                     hasher.hash(&(corner + offset).into_array()),
                     distance - offset.numcast().unwrap()
                 )
@@ -290,3 +286,74 @@ where
     // outliers and return it.
     scaled_result.clamp(-1.0, 1.0)
 }
+
+/// The unfaded lattice values that a lattice generator interpolates between
+/// at a point, for checking the gradients of a generator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LatticeSample<const DIM: usize> {
+    /// The integer coordinates of the lattice cell containing the point. For
+    /// Simplex noise, this is the cell of the skewed lattice.
+    pub cell: [isize; DIM],
+
+    /// The position of the point within its cell, in `[0, 1)` along each
+    /// axis, before the fade curve is applied. For Simplex noise, this is the
+    /// offset of the point from the unskewed origin of its cell instead.
+    pub fraction: [f64; DIM],
+
+    /// The value at each corner of the cell. Bit `i` of the index is set for
+    /// the corners on the far side of the cell along axis `i`.
+    ///
+    /// For gradient noise, this is the dot product of the gradient at the
+    /// corner with the offset from that corner to the point, and for value
+    /// noise, it is the value at the corner. Simplex noise only has the
+    /// `DIM + 1` corners of the simplex containing the point, in the order
+    /// they are traversed from the origin of the cell, and their values are
+    /// not yet attenuated by the distance to the point.
+    pub corners: Vec<f64>,
+}
+
+/// Samples the lattice of Perlin noise at a point without interpolating it.
+pub fn perlin_lattice_sample<T, NH, const DIM: usize>(
+    point: [T; DIM],
+    hasher: &NH,
+) -> LatticeSample<DIM>
+where
+    T: Coordinate,
+    NH: NoiseHasher + ?Sized,
+{
+    assert!(
+        (2..=4).contains(&DIM),
+        "lattice samples are only supported in 2 to 4 dimensions"
+    );
+
+    let (cell, fraction) = lattice_point(point);
+
+    let corners = (0..1 << DIM)
+        .map(|index| {
+            let mut key = cell;
+            let mut offset = fraction;
+            for axis in 0..DIM {
+                if index & (1 << axis) != 0 {
+                    key[axis] += 1;
+                    offset[axis] -= 1.0;
+                }
+            }
+
+            let perm = hasher.hash(&key);
+            match DIM {
+                2 => gradient_dot_v_2d(perm, Vector2::new(offset[0], offset[1])),
+                3 => gradient_dot_v_3d(perm, Vector3::new(offset[0], offset[1], offset[2])),
+                _ => gradient_dot_v_4d(
+                    perm,
+                    Vector4::new(offset[0], offset[1], offset[2], offset[3]),
+                ),
+            }
+        })
+        .collect();
+
+    LatticeSample {
+        cell,
+        fraction,
+        corners,
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
+    core::perlin::LatticeSample,
     gradient,
-    math::vectors::{Vector, Vector2, Vector3, Vector4},
+    math::{
+        coordinate::{lattice_cell, Coordinate},
+        vectors::{Vector, Vector2, Vector3, Vector4},
+    },
     permutationtable::NoiseHasher,
 };
 use num_traits::{Float, NumCast};
@@ -43,11 +47,44 @@ where
     (F::one() - (F::one() / (n + F::one()).sqrt())) / n
 }
 
+/// Splits a point into the cell of the skewed simplex lattice containing it
+/// and the distances from the unskewed origin of that cell, given the skew and
+/// unskew factors. The cell is found in the coordinate type, so that it is
+/// exact for coordinates that are.
+#[inline(always)]
+fn simplex_cell<T, const DIM: usize>(
+    point: [T; DIM],
+    skew_factor: f64,
+    unskew_factor: f64,
+) -> ([isize; DIM], [f64; DIM])
+where
+    T: Coordinate,
+{
+    let sum = point[1..]
+        .iter()
+        .fold(point[0], |sum, &coordinate| sum + coordinate);
+    let skew = sum * T::from_f64(skew_factor);
+
+    let mut cell = [0; DIM];
+    for (index, &coordinate) in cell.iter_mut().zip(&point) {
+        *index = lattice_cell(coordinate + skew).0;
+    }
+
+    let unskew = T::from_f64(cell.iter().sum::<isize>() as f64 * unskew_factor);
+    let mut distance = [0.0; DIM];
+    for ((offset, &coordinate), &index) in distance.iter_mut().zip(&point).zip(&cell) {
+        *offset = (coordinate - (T::from_f64(index as f64) - unskew)).to_f64();
+    }
+
+    (cell, distance)
+}
+
 /// The simplex noise code was adapted from code by Stefan Gustavson,
 /// http://staffwww.itn.liu.se/~stegu/aqsis/aqsis-newnoise/sdnoise1234.c
 ///
 /// This is Stefan Gustavson's original copyright notice:
 ///
+/// ```text
 /// /* sdnoise1234, Simplex noise with true analytic
 ///  * derivative in 1D to 4D.
 ///  *
@@ -65,6 +102,7 @@ where
 ///  * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
 ///  * General Public License for more details.
 ///  */
+/// ```
 ///
 /// 1D Simplex Noise with Derivative
 #[inline(always)]
@@ -72,9 +110,7 @@ pub fn simplex_1d<NH>(x: f64, hasher: &NH) -> (f64, f64)
 where
     NH: NoiseHasher + ?Sized,
 {
-    let cell = x.floor() as isize;
-
-    let near_distance = x - cell as f64;
+    let (cell, near_distance) = lattice_cell(x);
     let far_distance = near_distance - 1.0;
 
     // Calculate gradient indexes for each corner
@@ -139,26 +175,22 @@ where
     (noise, dnoise_dx)
 }
 
+/// Returns the gradient index of each corner of the 2D simplex containing
+/// the point, in the order the corners are traversed from the origin of its
+/// cell, along with the offset from that corner to the point.
 #[inline(always)]
-pub fn simplex_2d<NH>(point: [f64; 2], hasher: &NH) -> (f64, [f64; 2])
+fn simplex_corners_2d<T, NH>(point: [T; 2], hasher: &NH) -> [(usize, Vector2<f64>); 3]
 where
+    T: Coordinate,
     NH: NoiseHasher + ?Sized,
 {
     let f2: f64 = skew_factor(2);
     let g2: f64 = unskew_factor(2);
 
-    let point = Vector2::from(point);
-
-    /* Skew the input space to determine which simplex cell we're in */
-    let skew = point.sum() * f2; /* Hairy factor for 2D */
-    let skewed = point + Vector2::broadcast(skew);
-    let cell: Vector2<isize> = skewed.floor().numcast().unwrap();
-
-    let unskew: f64 = cell.sum() as f64 * g2;
-    // Unskew the cell origin back to (x,y) space
-    let unskewed = cell.numcast().unwrap() - Vector2::broadcast(unskew);
-    // The x,y distances from the cell origin
-    let distance = point - unskewed;
+    // Skew the input space to determine which simplex cell we're in, and
+    // find the distances from the unskewed cell origin.
+    let (cell, distance) = simplex_cell(point, f2, g2);
+    let (cell, distance) = (Vector2::from(cell), Vector2::from(distance));
 
     // For the 2D case, the simplex shape is an equilateral triangle.
     // Determine which simplex we are in.
@@ -184,6 +216,17 @@ where
     let gi1 = hasher.hash(&(cell + distance1.numcast().unwrap()).into_array());
     let gi2 = hasher.hash(&(cell + Vector2::one()).into_array());
 
+    [(gi0, distance), (gi1, distance1), (gi2, distance2)]
+}
+
+#[inline(always)]
+pub fn simplex_2d<T, NH>(point: [T; 2], hasher: &NH) -> (f64, [f64; 2])
+where
+    T: Coordinate,
+    NH: NoiseHasher + ?Sized,
+{
+    let [(gi0, distance), (gi1, distance1), (gi2, distance2)] = simplex_corners_2d(point, hasher);
+
     struct SurfletComponents {
         value: f64,
         t: f64,
@@ -243,7 +286,7 @@ where
      *    dnoise_dy += -8.0 * t22 * t2 * y2 * ( gx2 * x2 + gy2 * y2 ) + t42 * gy2;
      */
     let temp0 = corner0.t2 * corner0.t * corner0.gradient.dot(distance);
-    let mut dnoise = distance + Vector2::broadcast(temp0);
+    let mut dnoise = distance * temp0;
 
     let temp1 = corner1.t2 * corner1.t * corner1.gradient.dot(distance1);
     dnoise += distance1 * temp1;
@@ -262,28 +305,22 @@ where
     (noise, dnoise.into())
 }
 
+/// Returns the gradient index of each corner of the 3D simplex containing
+/// the point, in the order the corners are traversed from the origin of its
+/// cell, along with the offset from that corner to the point.
 #[inline(always)]
-pub fn simplex_3d<NH>(point: [f64; 3], hasher: &NH) -> (f64, [f64; 3])
+fn simplex_corners_3d<T, NH>(point: [T; 3], hasher: &NH) -> [(usize, Vector3<f64>); 4]
 where
+    T: Coordinate,
     NH: NoiseHasher + ?Sized,
 {
     let f3: f64 = skew_factor(3);
     let g3: f64 = unskew_factor(3);
 
-    let point = Vector3::from(point);
-
-    /* Skew the input space to determine which simplex cell we're in */
-    // let skew = (x + y + z) * f3; /* Very nice and simple skew factor for 3D */
-    let skew = point.sum() * f3;
-    let skewed = point + Vector3::broadcast(skew);
-    let cell: Vector3<isize> = skewed.floor().numcast().unwrap();
-
-    // let unskew = (cell_x + cell_y + cell_z) as f64 * g3;
-    let unskew = cell.sum() as f64 * g3;
-    /* Unskew the cell origin back to (x,y,z) space */
-    let unskewed = cell.numcast().unwrap() - Vector3::broadcast(unskew);
-    /* The x,y,z distances from the cell origin */
-    let distance = point - unskewed;
+    // Skew the input space to determine which simplex cell we're in, and
+    // find the distances from the unskewed cell origin.
+    let (cell, distance) = simplex_cell(point, f3, g3);
+    let (cell, distance) = (Vector3::from(cell), Vector3::from(distance));
 
     /* For the 3D case, the simplex shape is a slightly irregular tetrahedron.
      * Determine which simplex we are in. */
@@ -328,6 +365,23 @@ where
     let gi2 = hasher.hash(&(cell + order2).into_array());
     let gi3 = hasher.hash(&(cell + Vector3::one()).into_array());
 
+    [
+        (gi0, distance),
+        (gi1, offset1),
+        (gi2, offset2),
+        (gi3, offset3),
+    ]
+}
+
+#[inline(always)]
+pub fn simplex_3d<T, NH>(point: [T; 3], hasher: &NH) -> (f64, [f64; 3])
+where
+    T: Coordinate,
+    NH: NoiseHasher + ?Sized,
+{
+    let [(gi0, distance), (gi1, offset1), (gi2, offset2), (gi3, offset3)] =
+        simplex_corners_3d(point, hasher);
+
     struct SurfletComponents {
         value: f64,
         t: f64,
@@ -418,32 +472,22 @@ where
     (noise, dnoise.into())
 }
 
+/// Returns the gradient index of each corner of the 4D simplex containing
+/// the point, in the order the corners are traversed from the origin of its
+/// cell, along with the offset from that corner to the point.
 #[inline(always)]
-pub fn simplex_4d<NH>(point: [f64; 4], hasher: &NH) -> (f64, [f64; 4])
+fn simplex_corners_4d<T, NH>(point: [T; 4], hasher: &NH) -> [(usize, Vector4<f64>); 5]
 where
+    T: Coordinate,
     NH: NoiseHasher + ?Sized,
 {
     let f4: f64 = skew_factor(4);
     let g4: f64 = unskew_factor(4);
 
-    let point = Vector4::from(point);
-
-    // Skew the (x,y,z,w) space to determine which cell of 24 simplices we're in
-    // Factor for 4D skewing
-    let skew = point.sum() * f4;
-    let skewed = point + Vector4::broadcast(skew);
-    let cell: Vector4<isize> = skewed.numcast().unwrap();
-
-    // Factor for 4D unskewing
-    let unskew = cell.sum() as f64 * g4;
-    // Unskew the cell origin back to (x,y,z,w) space
-    let unskewed = cell.numcast().unwrap() - Vector4::broadcast(unskew);
-
-    // let distance_x = x - unskewed_x; // The x,y,z,w distances from the cell origin
-    // let distance_y = y - unskewed_y;
-    // let distance_z = z - unskewed_z;
-    // let distance_w = w - unskewed_w;
-    let distance = point - unskewed;
+    // Skew the input space to determine which simplex cell we're in, and
+    // find the distances from the unskewed cell origin.
+    let (cell, distance) = simplex_cell(point, f4, g4);
+    let (cell, distance) = (Vector4::from(cell), Vector4::from(distance));
 
     // For the 4D case, the simplex is a 4D shape I won't even try to describe.
     // To find out which of the 24 possible simplices we're in, we need to
@@ -506,6 +550,24 @@ where
     let gi3 = hasher.hash(&(cell + order2).into_array());
     let gi4 = hasher.hash(&(cell + Vector4::one()).into_array());
 
+    [
+        (gi0, distance),
+        (gi1, offset1),
+        (gi2, offset2),
+        (gi3, offset3),
+        (gi4, offset4),
+    ]
+}
+
+#[inline(always)]
+pub fn simplex_4d<T, NH>(point: [T; 4], hasher: &NH) -> (f64, [f64; 4])
+where
+    T: Coordinate,
+    NH: NoiseHasher + ?Sized,
+{
+    let [(gi0, distance), (gi1, offset1), (gi2, offset2), (gi3, offset3), (gi4, offset4)] =
+        simplex_corners_4d(point, hasher);
+
     struct SurfletComponents {
         value: f64,
         t: f64,
@@ -604,11 +666,50 @@ where
         + corner4.gradient * corner4.t4;
 
     // Scale derivative to match the noise scaling
-    dnoise *= 28.0;
+    dnoise *= 27.0;
 
     (noise, dnoise.into())
 }
 
+/// Samples the simplex containing a point without attenuating or summing the
+/// contributions of its corners.
+pub fn simplex_lattice_sample<T, NH, const DIM: usize>(
+    point: [T; DIM],
+    hasher: &NH,
+) -> LatticeSample<DIM>
+where
+    T: Coordinate,
+    NH: NoiseHasher + ?Sized,
+{
+    assert!(
+        (2..=4).contains(&DIM),
+        "lattice samples are only supported in 2 to 4 dimensions"
+    );
+
+    let (cell, fraction) = simplex_cell(point, skew_factor(DIM), unskew_factor(DIM));
+
+    let corners = match DIM {
+        2 => simplex_corners_2d([point[0], point[1]], hasher)
+            .iter()
+            .map(|&(index, offset)| Vector2::from(gradient::grad2(index)).dot(offset))
+            .collect(),
+        3 => simplex_corners_3d([point[0], point[1], point[2]], hasher)
+            .iter()
+            .map(|&(index, offset)| Vector3::from(gradient::grad3(index)).dot(offset))
+            .collect(),
+        _ => simplex_corners_4d([point[0], point[1], point[2], point[3]], hasher)
+            .iter()
+            .map(|&(index, offset)| Vector4::from(gradient::grad4(index)).dot(offset))
+            .collect(),
+    };
+
+    LatticeSample {
+        cell,
+        fraction,
+        corners,
+    }
+}
+
 // A lookup table to traverse the simplex around a given point in 4D.
 // Details can be found where this table is used, in the 4D noise method.
 /* TODO: This should not be required, backport it from Bill's GLSL code! */
@@ -1,17 +1,22 @@
 use crate::{
-    math::{interpolate, s_curve::quintic::Quintic, vectors::*},
+    core::perlin::LatticeSample,
+    math::{
+        coordinate::{lattice_point, Coordinate},
+        interpolate,
+        s_curve::quintic::Quintic,
+        vectors::*,
+    },
     permutationtable::NoiseHasher,
 };
 
-pub fn value_2d<NH>(point: [f64; 2], hasher: &NH) -> f64
+pub fn value_2d<T, NH>(point: [T; 2], hasher: &NH) -> f64
 where
+    T: Coordinate,
     NH: NoiseHasher + ?Sized,
 {
-    let point = Vector2::from(point);
-
-    let floored = point.floor();
-    let corner = floored.numcast().unwrap();
-    let weight = point - floored.map_quintic();
+    let (corner, fraction) = lattice_point(point);
+    let corner = Vector2::from(corner);
+    let weight = Vector2::from(fraction).map_quintic();
 
     macro_rules! get(
         ($corner:expr, $offset:expr) => {
@@ -33,15 +38,14 @@ where
     d * 2.0 - 1.0
 }
 
-pub fn value_3d<NH>(point: [f64; 3], hasher: &NH) -> f64
+pub fn value_3d<T, NH>(point: [T; 3], hasher: &NH) -> f64
 where
+    T: Coordinate,
     NH: NoiseHasher + ?Sized,
 {
-    let point = Vector3::from(point);
-
-    let floored = point.floor();
-    let corner = floored.numcast().unwrap();
-    let weight = point - floored.map_quintic();
+    let (corner, fraction) = lattice_point(point);
+    let corner = Vector3::from(corner);
+    let weight = Vector3::from(fraction).map_quintic();
 
     macro_rules! get(
         ($corner:expr, $offset:expr) => {
@@ -71,15 +75,14 @@ where
     d * 2.0 - 1.0
 }
 
-pub fn value_4d<NH>(point: [f64; 4], hasher: &NH) -> f64
+pub fn value_4d<T, NH>(point: [T; 4], hasher: &NH) -> f64
 where
+    T: Coordinate,
     NH: NoiseHasher + ?Sized,
 {
-    let point = Vector4::from(point);
-
-    let floored = point.floor();
-    let corner = floored.numcast().unwrap();
-    let weight = point - floored.map_quintic();
+    let (corner, fraction) = lattice_point(point);
+    let corner = Vector4::from(corner);
+    let weight = Vector4::from(fraction).map_quintic();
 
     macro_rules! get(
         ($corner:expr, $offset:expr) => {
@@ -124,3 +127,39 @@ where
 
     d * 2.0 - 1.0
 }
+
+/// Samples the lattice of value noise at a point without interpolating it.
+pub fn value_lattice_sample<T, NH, const DIM: usize>(
+    point: [T; DIM],
+    hasher: &NH,
+) -> LatticeSample<DIM>
+where
+    T: Coordinate,
+    NH: NoiseHasher + ?Sized,
+{
+    assert!(
+        (2..=4).contains(&DIM),
+        "lattice samples are only supported in 2 to 4 dimensions"
+    );
+
+    let (cell, fraction) = lattice_point(point);
+
+    let corners = (0..1 << DIM)
+        .map(|index| {
+            let mut key = cell;
+            for (axis, key) in key.iter_mut().enumerate() {
+                if index & (1 << axis) != 0 {
+                    *key += 1;
+                }
+            }
+
+            hasher.hash(&key) as f64 / 255.0 * 2.0 - 1.0
+        })
+        .collect();
+
+    LatticeSample {
+        cell,
+        fraction,
+        corners,
+    }
+}
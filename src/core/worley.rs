@@ -2,15 +2,36 @@ use crate::{
     math::vectors::{Vector, Vector2, Vector3, Vector4, VectorMap},
     permutationtable::NoiseHasher,
 };
+use alloc::vec::Vec;
 use core::f64;
 
 #[derive(Clone, Copy, Debug)]
 pub enum ReturnType {
     Distance,
     Value,
+    /// Euclidean distance from the point to the nearest edge of its Voronoi
+    /// cell, i.e. the bisector between the nearest seed point and another.
+    /// This is zero along cell boundaries and largest near the seed points.
+    /// Only seed points of the 3<sup>n</sup> nearest cells are considered.
+    EdgeDistance,
+    /// Distance from the point to the second nearest feature point, scaled
+    /// like [`ReturnType::Distance`]. Every feature point of the
+    /// 3<sup>n</sup> nearest cells is tested.
+    SecondDistance,
+    /// Average of the distances from the point to the nearest and second
+    /// nearest feature points, `(F1 + F2) / 2`, scaled like
+    /// [`ReturnType::Distance`]. This softens the cell boundaries compared to
+    /// the nearest distance alone. Every feature point of the 3<sup>n</sup>
+    /// nearest cells is tested.
+    AverageDistance,
 }
 
+/// Distance functions for [`Worley`](crate::Worley) cells.
+///
+/// The Worley output for [`ReturnType::Distance`] is the distance scaled from
+/// 0.0 to 1.0 onto -1.0 to 1.0, so its range depends on the distance function.
 pub mod distance_functions {
+    /// Straight-line distance. This is the default.
     pub fn euclidean(p1: &[f64], p2: &[f64]) -> f64 {
         p1.iter()
             .zip(p2)
@@ -20,6 +41,11 @@ pub mod distance_functions {
             .sqrt()
     }
 
+    /// Square of the straight-line distance. This gives the same cells as
+    /// [`euclidean`] without a square root per candidate seed point, so it is
+    /// faster when only the cells or a threshold on the distance matter. Its
+    /// distances are the squares of the euclidean ones, so they are smaller and
+    /// fall off more sharply away from the seed points.
     pub fn euclidean_squared(p1: &[f64], p2: &[f64]) -> f64 {
         p1.iter()
             .zip(p2)
@@ -28,6 +54,7 @@ pub mod distance_functions {
             .fold(0.0, |acc, x| acc + x)
     }
 
+    /// Sum of the distances along each axis.
     pub fn manhattan(p1: &[f64], p2: &[f64]) -> f64 {
         p1.iter()
             .zip(p2)
@@ -36,6 +63,7 @@ pub mod distance_functions {
             .fold(0.0, |acc, x| acc + x)
     }
 
+    /// Largest of the distances along each axis.
     pub fn chebyshev(p1: &[f64], p2: &[f64]) -> f64 {
         p1.iter()
             .zip(p2)
@@ -44,6 +72,37 @@ pub mod distance_functions {
             .fold(f64::MIN, |a, b| a.max(b))
     }
 
+    /// Returns the Minkowski distance of order `p`, `(sum(|d|^p))^(1/p)` over
+    /// the distances `d` along each axis.
+    ///
+    /// This generalizes the other distances: a `p` of 1.0 gives [`manhattan`],
+    /// 2.0 gives [`euclidean`], and the distance approaches [`chebyshev`] as
+    /// `p` goes to infinity, which it equals for an infinite `p`. Orders
+    /// between 0.0 and 1.0 give star-shaped cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not greater than 0.0, which includes NaN.
+    pub fn minkowski(p: f64) -> impl Fn(&[f64], &[f64]) -> f64 + Clone + Send + Sync {
+        assert!(
+            p > 0.0,
+            "the order of the Minkowski distance must be positive"
+        );
+
+        move |p1: &[f64], p2: &[f64]| {
+            if p.is_infinite() {
+                return chebyshev(p1, p2);
+            }
+
+            p1.iter()
+                .zip(p2)
+                .map(|(a, b)| (*a - *b).abs().powf(p))
+                .fold(0.0, |acc, x| acc + x)
+                .powf(p.recip())
+        }
+    }
+
+    /// Square of the sum of the offsets along each axis.
     pub fn quadratic(p1: &[f64], p2: &[f64]) -> f64 {
         #[cfg(not(feature = "std"))]
         use alloc::vec::Vec;
@@ -72,6 +131,11 @@ where
     F: Fn(&[f64], &[f64]) -> f64,
     NH: NoiseHasher + ?Sized,
 {
+    // The second nearest feature point needs every nearby point tested.
+    if let ReturnType::SecondDistance | ReturnType::AverageDistance = return_type {
+        return worley_points_2d(hasher, distance_function, return_type, 1, 0.0, point);
+    }
+
     let point = Vector2::from(point);
 
     fn get_point(index: usize, whole: Vector2<isize>) -> Vector2<f64> {
@@ -124,11 +188,450 @@ where
     let value = match return_type {
         ReturnType::Distance => distance,
         ReturnType::Value => hasher.hash(&seed_cell.into_array()) as f64 / 255.0,
+        ReturnType::SecondDistance | ReturnType::AverageDistance => unreachable!(),
+        ReturnType::EdgeDistance => edge_distance(point.into_array(), near.into_array(), |cell| {
+            get_point(hasher.hash(&cell), Vector2::from(cell)).into_array()
+        }),
     };
 
     value * 2.0 - 1.0
 }
 
+/// Returns the seed points of the 3<sup>n</sup> cells surrounding the lattice
+/// point `near`, along with their count.
+fn neighborhood_seeds<F, const DIM: usize>(
+    near: [isize; DIM],
+    seed_point: F,
+) -> ([[f64; DIM]; 81], usize)
+where
+    F: Fn([isize; DIM]) -> [f64; DIM],
+{
+    let count = 3_usize.pow(DIM as u32);
+    let mut seeds = [[0.0; DIM]; 81];
+    for (i, seed) in seeds[..count].iter_mut().enumerate() {
+        let mut cell = near;
+        let mut digits = i;
+        for coordinate in cell.iter_mut() {
+            *coordinate += (digits % 3) as isize - 1;
+            digits /= 3;
+        }
+        *seed = seed_point(cell);
+    }
+
+    (seeds, count)
+}
+
+/// Returns the euclidean distance from the point to the nearest bisector
+/// between the seed point nearest to it and the other seed points of the
+/// 3<sup>n</sup> cells surrounding the lattice point `near`.
+fn edge_distance<F, const DIM: usize>(point: [f64; DIM], near: [isize; DIM], seed_point: F) -> f64
+where
+    F: Fn([isize; DIM]) -> [f64; DIM],
+{
+    let (seeds, count) = neighborhood_seeds(near, seed_point);
+    let seeds = &seeds[..count];
+
+    let nearest = seeds
+        .iter()
+        .min_by(|a, b| {
+            let a = distance_functions::euclidean_squared(&point, *a);
+            let b = distance_functions::euclidean_squared(&point, *b);
+            a.partial_cmp(&b).unwrap()
+        })
+        .unwrap();
+
+    bisector_distance(point, nearest, seeds.iter())
+}
+
+/// Returns the euclidean distance from the point to the nearest bisector
+/// between the seed point `nearest` and the other seed points.
+fn bisector_distance<'a, I, const DIM: usize>(
+    point: [f64; DIM],
+    nearest: &[f64; DIM],
+    seeds: I,
+) -> f64
+where
+    I: Iterator<Item = &'a [f64; DIM]>,
+{
+    seeds
+        .filter(|seed| *seed != nearest)
+        .map(|seed| {
+            // Project the point onto the direction between the seed points,
+            // relative to their midpoint.
+            let mut dot = 0.0;
+            let mut length_squared = 0.0;
+            for axis in 0..DIM {
+                let direction = seed[axis] - nearest[axis];
+                let midpoint = (seed[axis] + nearest[axis]) * 0.5;
+                dot += (midpoint - point[axis]) * direction;
+                length_squared += direction * direction;
+            }
+
+            dot / length_squared.sqrt()
+        })
+        .fold(f64::MAX, f64::min)
+}
+
+/// Returns the feature points of the 3<sup>n</sup> cells surrounding the
+/// lattice point nearest to the point, `points_per_cell` per cell, along with
+/// their hashes. The first feature point of each cell is its usual seed point.
+fn feature_points<NH, O, const DIM: usize>(
+    hasher: &NH,
+    point: [f64; DIM],
+    points_per_cell: usize,
+    offset: O,
+) -> Vec<([f64; DIM], usize)>
+where
+    NH: NoiseHasher + ?Sized,
+    O: Fn(usize) -> [f64; DIM],
+{
+    let mut near = [0; DIM];
+    for (near, coordinate) in near.iter_mut().zip(point.iter()) {
+        *near = coordinate.round() as isize;
+    }
+
+    let count = 3_usize.pow(DIM as u32);
+    let mut points = Vec::with_capacity(count * points_per_cell);
+    for i in 0..count {
+        // The cell coordinates, followed by the index of the feature point.
+        let mut key = [0; 5];
+        let mut digits = i;
+        for (key, near) in key.iter_mut().zip(near.iter()) {
+            *key = near + (digits % 3) as isize - 1;
+            digits /= 3;
+        }
+
+        for index in 0..points_per_cell {
+            let hash = if index == 0 {
+                hasher.hash(&key[..DIM])
+            } else {
+                key[DIM] = index as isize;
+                hasher.hash(&key[..=DIM])
+            };
+
+            let mut feature_point = offset(hash);
+            for (coordinate, cell) in feature_point.iter_mut().zip(key.iter()) {
+                *coordinate += *cell as f64;
+            }
+            points.push((feature_point, hash));
+        }
+    }
+
+    points
+}
+
+/// Returns the index of the smallest of `distances`, along with the smallest
+/// and second smallest of them.
+fn nearest_two(distances: &[f64]) -> (usize, f64, f64) {
+    distances.iter().copied().enumerate().fold(
+        (0, f64::MAX, f64::MAX),
+        |(nearest, first, second), (index, distance)| {
+            if distance < first {
+                (index, distance, first)
+            } else if distance < second {
+                (nearest, first, distance)
+            } else {
+                (nearest, first, second)
+            }
+        },
+    )
+}
+
+/// Returns the distances from the point to the nearest and second nearest
+/// feature points of the 3<sup>n</sup> cells surrounding the lattice point
+/// nearest to the point, found in a single search and both scaled like
+/// [`ReturnType::Distance`].
+fn worley_f1_f2<F, NH, O, const DIM: usize>(
+    hasher: &NH,
+    distance_function: F,
+    points_per_cell: usize,
+    point: [f64; DIM],
+    offset: O,
+) -> (f64, f64)
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+    O: Fn(usize) -> [f64; DIM],
+{
+    let points = feature_points(hasher, point, points_per_cell.max(1), offset);
+    let distances = points
+        .iter()
+        .map(|(feature_point, _)| distance_function(&point, feature_point))
+        .collect::<Vec<_>>();
+    let (_, first, second) = nearest_two(&distances);
+
+    (first * 2.0 - 1.0, second * 2.0 - 1.0)
+}
+
+/// Evaluates Worley noise over every feature point of the 3<sup>n</sup> cells
+/// surrounding the lattice point nearest to the point.
+///
+/// For [`ReturnType::Distance`], a `smoothness` above zero takes the
+/// exponential smooth minimum `-k ln(sum(exp(-distance / k)))` of the
+/// distances, offset by the hard minimum so that it stays finite for small
+/// `k`. It is never larger than the hard minimum and converges to it as
+/// `smoothness` goes to zero.
+fn worley_points<F, NH, O, const DIM: usize>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    points_per_cell: usize,
+    smoothness: f64,
+    point: [f64; DIM],
+    offset: O,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+    O: Fn(usize) -> [f64; DIM],
+{
+    let points = feature_points(hasher, point, points_per_cell.max(1), offset);
+    let distances = points
+        .iter()
+        .map(|(feature_point, _)| distance_function(&point, feature_point))
+        .collect::<Vec<_>>();
+
+    let (nearest, distance, second) = nearest_two(&distances);
+
+    let value = match return_type {
+        ReturnType::Distance if smoothness > 0.0 => {
+            let sum = distances
+                .iter()
+                .map(|other| (-(other - distance) / smoothness).exp())
+                .sum::<f64>();
+
+            distance - smoothness * sum.ln()
+        }
+        ReturnType::Distance => distance,
+        ReturnType::SecondDistance => second,
+        ReturnType::AverageDistance => (distance + second) / 2.0,
+        ReturnType::Value => points[nearest].1 as f64 / 255.0,
+        ReturnType::EdgeDistance => bisector_distance(
+            point,
+            &points[nearest].0,
+            points.iter().map(|(feature_point, _)| feature_point),
+        ),
+    };
+
+    value * 2.0 - 1.0
+}
+
+/// Outputs Worley noise like [`worley_2d`], but with `points_per_cell`
+/// feature points in each cell, taking the nearest of them. `smoothness`
+/// feathers the cell boundaries of [`ReturnType::Distance`], see
+/// [`smooth_worley_2d`].
+///
+/// Every feature point of the surrounding cells is tested, so this is slower
+/// than [`worley_2d`].
+pub fn worley_points_2d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    points_per_cell: usize,
+    smoothness: f64,
+    point: [f64; 2],
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_points(
+        hasher,
+        distance_function,
+        return_type,
+        points_per_cell,
+        smoothness,
+        point,
+        |hash| get_vec2(hash).into_array(),
+    )
+}
+
+/// Outputs the distance to the nearest seed point like [`worley_2d`] with
+/// [`ReturnType::Distance`], but takes a smooth minimum of the distances to
+/// the nearby seed points, which feathers the cell boundaries.
+pub fn smooth_worley_2d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    smoothness: f64,
+    point: [f64; 2],
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_points_2d(
+        hasher,
+        distance_function,
+        ReturnType::Distance,
+        1,
+        smoothness,
+        point,
+    )
+}
+
+/// Outputs the distances to the nearest and second nearest feature points,
+/// like [`worley_points_2d`] with [`ReturnType::Distance`] and
+/// [`ReturnType::SecondDistance`], from a single search of the surrounding
+/// cells.
+pub fn worley_f1_f2_2d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    points_per_cell: usize,
+    point: [f64; 2],
+) -> (f64, f64)
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_f1_f2(hasher, distance_function, points_per_cell, point, |hash| {
+        get_vec2(hash).into_array()
+    })
+}
+
+/// Outputs Worley noise like [`worley_3d`], but with `points_per_cell`
+/// feature points in each cell, taking the nearest of them. `smoothness`
+/// feathers the cell boundaries of [`ReturnType::Distance`], see
+/// [`smooth_worley_3d`].
+///
+/// Every feature point of the surrounding cells is tested, so this is slower
+/// than [`worley_3d`].
+pub fn worley_points_3d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    points_per_cell: usize,
+    smoothness: f64,
+    point: [f64; 3],
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_points(
+        hasher,
+        distance_function,
+        return_type,
+        points_per_cell,
+        smoothness,
+        point,
+        |hash| get_vec3(hash).into_array(),
+    )
+}
+
+/// Outputs the distance to the nearest seed point like [`worley_3d`] with
+/// [`ReturnType::Distance`], but takes a smooth minimum of the distances to
+/// the nearby seed points, which feathers the cell boundaries.
+pub fn smooth_worley_3d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    smoothness: f64,
+    point: [f64; 3],
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_points_3d(
+        hasher,
+        distance_function,
+        ReturnType::Distance,
+        1,
+        smoothness,
+        point,
+    )
+}
+
+/// Outputs the distances to the nearest and second nearest feature points,
+/// like [`worley_points_3d`] with [`ReturnType::Distance`] and
+/// [`ReturnType::SecondDistance`], from a single search of the surrounding
+/// cells.
+pub fn worley_f1_f2_3d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    points_per_cell: usize,
+    point: [f64; 3],
+) -> (f64, f64)
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_f1_f2(hasher, distance_function, points_per_cell, point, |hash| {
+        get_vec3(hash).into_array()
+    })
+}
+
+/// Outputs Worley noise like [`worley_4d`], but with `points_per_cell`
+/// feature points in each cell, taking the nearest of them. `smoothness`
+/// feathers the cell boundaries of [`ReturnType::Distance`], see
+/// [`smooth_worley_4d`].
+///
+/// Every feature point of the surrounding cells is tested, so this is slower
+/// than [`worley_4d`].
+pub fn worley_points_4d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    points_per_cell: usize,
+    smoothness: f64,
+    point: [f64; 4],
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_points(
+        hasher,
+        distance_function,
+        return_type,
+        points_per_cell,
+        smoothness,
+        point,
+        |hash| get_vec4(hash).into_array(),
+    )
+}
+
+/// Outputs the distance to the nearest seed point like [`worley_4d`] with
+/// [`ReturnType::Distance`], but takes a smooth minimum of the distances to
+/// the nearby seed points, which feathers the cell boundaries.
+pub fn smooth_worley_4d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    smoothness: f64,
+    point: [f64; 4],
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_points_4d(
+        hasher,
+        distance_function,
+        ReturnType::Distance,
+        1,
+        smoothness,
+        point,
+    )
+}
+
+/// Outputs the distances to the nearest and second nearest feature points,
+/// like [`worley_points_4d`] with [`ReturnType::Distance`] and
+/// [`ReturnType::SecondDistance`], from a single search of the surrounding
+/// cells.
+pub fn worley_f1_f2_4d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    points_per_cell: usize,
+    point: [f64; 4],
+) -> (f64, f64)
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_f1_f2(hasher, distance_function, points_per_cell, point, |hash| {
+        get_vec4(hash).into_array()
+    })
+}
+
 #[rustfmt::skip]
 fn get_vec2(index: usize) -> Vector2<f64> {
     let length = ((index & 0xF8) >> 3) as f64 * 0.5 / 31.0;
@@ -158,6 +661,11 @@ where
     F: Fn(&[f64], &[f64]) -> f64,
     NH: NoiseHasher + ?Sized,
 {
+    // The second nearest feature point needs every nearby point tested.
+    if let ReturnType::SecondDistance | ReturnType::AverageDistance = return_type {
+        return worley_points_3d(hasher, distance_function, return_type, 1, 0.0, point);
+    }
+
     let point = Vector3::from(point);
 
     fn get_point(index: usize, whole: Vector3<isize>) -> Vector3<f64> {
@@ -222,6 +730,10 @@ where
     let value = match return_type {
         ReturnType::Distance => distance,
         ReturnType::Value => hasher.hash(&seed_cell.into_array()) as f64 / 255.0,
+        ReturnType::SecondDistance | ReturnType::AverageDistance => unreachable!(),
+        ReturnType::EdgeDistance => edge_distance(point.into_array(), near.into_array(), |cell| {
+            get_point(hasher.hash(&cell), Vector3::from(cell)).into_array()
+        }),
     };
 
     value * 2.0 - 1.0
@@ -257,7 +769,6 @@ fn get_vec3(index: usize) -> Vector3<f64> {
 }
 
 #[inline(always)]
-#[allow(clippy::cognitive_complexity)]
 pub fn worley_4d<F, NH>(
     hasher: &NH,
     distance_function: F,
@@ -268,6 +779,11 @@ where
     F: Fn(&[f64], &[f64]) -> f64,
     NH: NoiseHasher + ?Sized,
 {
+    // The second nearest feature point needs every nearby point tested.
+    if let ReturnType::SecondDistance | ReturnType::AverageDistance = return_type {
+        return worley_points_4d(hasher, distance_function, return_type, 1, 0.0, point);
+    }
+
     let point = Vector4::from(point);
 
     fn get_point(index: usize, whole: Vector4<isize>) -> Vector4<f64> {
@@ -281,82 +797,67 @@ where
     let half = frac.map(|x| x > 0.5);
 
     let near = whole + half.map(|x| x as isize);
-    let far = whole + half.map(|x| !x as isize);
-
-    let mut seed_cell = near;
-    let seed_index = hasher.hash(&near.into_array());
-    let seed_point = get_point(seed_index, near);
-    let mut distance = distance_function(&point.into_array(), &seed_point.into_array());
-
-    let range = frac.map(|x| (0.5 - x).powf(2.0));
+    let step = half.map(|x| if x { -1 } else { 1 }).into_array();
 
-    macro_rules! test_point(
-        [$x:expr, $y:expr, $z:expr, $w:expr] => {
+    // Seed points are offset at most half a unit from their lattice point, so
+    // the nearest seed point lies in the 3x3x3x3 neighborhood surrounding the
+    // nearest lattice point. Cells are visited nearest side first along each
+    // axis, so that a cell can be skipped once the gap to it along any single
+    // axis is already no closer than the nearest seed point found so far. This
+    // assumes the distance function grows with the separation along each axis,
+    // as the euclidean, manhattan and chebyshev distances do.
+    let offsets = |axis: usize| [0, step[axis], -step[axis]];
+    let near_gap = (point - near.numcast().unwrap()).map(f64::abs).into_array();
+    let bounds = {
+        let mut bounds = [[0.0; 3]; 4];
+        for (axis, bound) in bounds.iter_mut().enumerate() {
+            for (i, &gap) in [0.5 - near_gap[axis], 0.5 + near_gap[axis]]
+                .iter()
+                .enumerate()
             {
-                let test_point = Vector4::from([$x, $y, $z, $w]);
-                let index = hasher.hash(&test_point.into_array());
-                let offset = get_point(index, test_point);
-                let cur_distance = distance_function(&point.into_array(), &offset.into_array());
-                if cur_distance < distance {
-                    distance = cur_distance;
-                    seed_cell = test_point;
-                }
+                let mut offset = [0.0; 4];
+                offset[axis] = gap;
+                bound[i + 1] = distance_function(&[0.0; 4], &offset);
             }
         }
-    );
-
-    if range.x < distance {
-        test_point![far.x, near.y, near.z, near.w];
-    }
-    if range.y < distance {
-        test_point![near.x, far.y, near.z, near.w];
-    }
-    if range.z < distance {
-        test_point![near.x, near.y, far.z, near.w];
-    }
-    if range.w < distance {
-        test_point![near.x, near.y, near.z, far.w];
-    }
+        bounds
+    };
 
-    if range.x < distance && range.y < distance {
-        test_point![far.x, far.y, near.z, near.w];
-    }
-    if range.x < distance && range.z < distance {
-        test_point![far.x, near.y, far.z, near.w];
-    }
-    if range.x < distance && range.w < distance {
-        test_point![far.x, near.y, near.z, far.w];
-    }
-    if range.y < distance && range.z < distance {
-        test_point![near.x, far.y, far.z, near.w];
-    }
-    if range.y < distance && range.w < distance {
-        test_point![near.x, far.y, near.z, far.w];
-    }
-    if range.z < distance && range.w < distance {
-        test_point![near.x, near.y, far.z, far.w];
-    }
+    let mut seed_cell = near;
+    let mut distance = f64::MAX;
 
-    if range.x < distance && range.y < distance && range.z < distance {
-        test_point![far.x, far.y, far.z, near.w];
-    }
-    if range.x < distance && range.y < distance && range.w < distance {
-        test_point![far.x, far.y, near.z, far.w];
-    }
-    if range.x < distance && range.z < distance && range.w < distance {
-        test_point![far.x, near.y, far.z, far.w];
-    }
-    if range.y < distance && range.z < distance && range.w < distance {
-        test_point![near.x, far.y, far.z, far.w];
-    }
+    for (w, &w_bound) in offsets(3).iter().zip(&bounds[3]) {
+        for (z, &z_bound) in offsets(2).iter().zip(&bounds[2]) {
+            for (y, &y_bound) in offsets(1).iter().zip(&bounds[1]) {
+                for (x, &x_bound) in offsets(0).iter().zip(&bounds[0]) {
+                    if x_bound >= distance
+                        || y_bound >= distance
+                        || z_bound >= distance
+                        || w_bound >= distance
+                    {
+                        continue;
+                    }
 
-    if range.x < distance && range.y < distance && range.z < distance && range.w < distance {
-        test_point![far.x, far.y, far.z, far.w];
+                    let test_point = near + Vector4::new(*x, *y, *z, *w);
+                    let index = hasher.hash(&test_point.into_array());
+                    let offset = get_point(index, test_point);
+                    let cur_distance = distance_function(&point.into_array(), &offset.into_array());
+                    if cur_distance < distance {
+                        distance = cur_distance;
+                        seed_cell = test_point;
+                    }
+                }
+            }
+        }
     }
 
     let value = match return_type {
         ReturnType::Distance => distance,
         ReturnType::Value => hasher.hash(&seed_cell.into_array()) as f64 / 255.0,
+        ReturnType::SecondDistance | ReturnType::AverageDistance => unreachable!(),
+        ReturnType::EdgeDistance => edge_distance(point.into_array(), near.into_array(), |cell| {
+            get_point(hasher.hash(&cell), Vector4::from(cell)).into_array()
+        }),
     };
 
     value * 2.0 - 1.0
@@ -404,3 +905,307 @@ fn get_vec4(index: usize) -> Vector4<f64> {
         _ => unreachable!("Attempt to access 4D gradient {} of 32", index % 32),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutationtable::PermutationTable;
+
+    fn sample_points() -> impl Iterator<Item = [f64; 4]> {
+        (0..2000).map(|i| {
+            let t = i as f64;
+            [
+                (t * 0.731).sin() * 10.0,
+                (t * 0.377).cos() * 10.0,
+                (t * 0.191).sin() * 7.0,
+                (t * 0.513).cos() * 5.0,
+            ]
+        })
+    }
+
+    fn brute_force_distance_4d(hasher: &PermutationTable, point: [f64; 4]) -> f64 {
+        let whole: Vector4<isize> = Vector4::from(point).floor().numcast().unwrap();
+        let mut distance = f64::MAX;
+
+        for w in -2..=2 {
+            for z in -2..=2 {
+                for y in -2..=2 {
+                    for x in -2..=2 {
+                        let cell = whole + Vector4::new(x, y, z, w);
+                        let seed_point =
+                            get_vec4(hasher.hash(&cell.into_array())) + cell.numcast().unwrap();
+                        distance = distance.min(distance_functions::euclidean(
+                            &point,
+                            &seed_point.into_array(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        distance
+    }
+
+    #[test]
+    fn test_worley_4d_finds_nearest_seed_point() {
+        let hasher = PermutationTable::new(0);
+
+        for point in sample_points() {
+            let value = worley_4d(
+                &hasher,
+                distance_functions::euclidean,
+                ReturnType::Distance,
+                point,
+            );
+            let distance = (value + 1.0) / 2.0;
+
+            assert!(distance >= 0.0);
+            assert!((distance - brute_force_distance_4d(&hasher, point)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_worley_4d_fixed_u_slice() {
+        // Freezing _u_ doesn't reproduce `worley_3d`: the 4D seed points are
+        // hashed from all four cell coordinates and offset along _u_ as well.
+        // The slice is instead the 3D cellular field of the seed points of the
+        // nearby 4D cells, each one's distance including its offset from the
+        // slice along _u_.
+        let hasher = PermutationTable::new(7);
+        let u = 0.25;
+        let mut seed_points = Vec::new();
+        for w in -2..=2 {
+            for z in -4..=4 {
+                for y in -4..=4 {
+                    for x in -4..=4 {
+                        let cell = Vector4::new(x, y, z, w);
+                        let hash = hasher.hash(&cell.into_array());
+                        let seed_point = get_vec4(hash) + cell.numcast().unwrap();
+                        seed_points.push((seed_point.into_array(), hash));
+                    }
+                }
+            }
+        }
+
+        let mut differs_from_3d = false;
+        for i in 0..200 {
+            let t = i as f64;
+            let point = [
+                (t * 0.731).sin() * 2.0,
+                (t * 0.377).cos() * 2.0,
+                (t * 0.191).sin() * 2.0,
+            ];
+            let [x, y, z] = point;
+
+            let (nearest, hash) = seed_points
+                .iter()
+                .map(|&([sx, sy, sz, su], hash)| {
+                    let slice_distance = distance_functions::euclidean(&point, &[sx, sy, sz]);
+                    let offset = u - su;
+                    let distance = (slice_distance * slice_distance + offset * offset).sqrt();
+                    (distance, hash)
+                })
+                .fold((f64::MAX, 0), |a, b| if b.0 < a.0 { b } else { a });
+
+            let sample = |return_type| {
+                worley_4d(
+                    &hasher,
+                    distance_functions::euclidean,
+                    return_type,
+                    [x, y, z, u],
+                )
+            };
+            let distance = (sample(ReturnType::Distance) + 1.0) / 2.0;
+            let value = (sample(ReturnType::Value) + 1.0) / 2.0;
+
+            assert!((distance - nearest).abs() < 1e-12);
+            assert!((value - hash as f64 / 255.0).abs() < 1e-12);
+
+            let distance_3d = worley_3d(
+                &hasher,
+                distance_functions::euclidean,
+                ReturnType::Distance,
+                point,
+            );
+            differs_from_3d |= (distance_3d - sample(ReturnType::Distance)).abs() > 1e-6;
+        }
+        assert!(differs_from_3d);
+    }
+
+    #[test]
+    fn test_edge_distance_2d() {
+        let hasher = PermutationTable::new(3);
+        let sample = |return_type, point| {
+            worley_2d(&hasher, distance_functions::euclidean, return_type, point)
+        };
+        let edge_distance = |point| (sample(ReturnType::EdgeDistance, point) + 1.0) / 2.0;
+        let seed_point = |cell: Vector2<isize>| {
+            get_vec2(hasher.hash(&cell.into_array())) + cell.numcast().unwrap()
+        };
+        let nearest_cell = |point: [f64; 2]| {
+            let whole: Vector2<isize> = Vector2::from(point).floor().numcast().unwrap();
+            let mut nearest = (f64::MAX, whole);
+            for y in -2..=2 {
+                for x in -2..=2 {
+                    let cell = whole + Vector2::new(x, y);
+                    let distance =
+                        distance_functions::euclidean(&point, &seed_point(cell).into_array());
+                    if distance < nearest.0 {
+                        nearest = (distance, cell);
+                    }
+                }
+            }
+            nearest.1
+        };
+
+        let step = 0.001;
+        let mut boundaries = 0;
+        for row in 0..5 {
+            let y = row as f64 * 0.73 + 0.1;
+            for i in 0..5000 {
+                let a = [i as f64 * step - 2.0, y];
+                let b = [a[0] + step, y];
+
+                // The distance to the nearest edge is never negative, and is
+                // continuous.
+                assert!(edge_distance(a) >= -1e-12);
+                assert!((edge_distance(a) - edge_distance(b)).abs() <= step + 1e-9);
+
+                // Where the nearest seed point changes between two samples, the
+                // samples are next to an edge.
+                if nearest_cell(a) != nearest_cell(b) {
+                    assert!(edge_distance(a) <= step);
+                    assert!(edge_distance(b) <= step);
+                    boundaries += 1;
+                }
+            }
+        }
+        assert!(boundaries > 10);
+
+        // At a seed point, the nearest edge is halfway to the next seed point.
+        for x in -3..3 {
+            for y in -3..3 {
+                let cell = Vector2::new(x, y);
+                let seed_point =
+                    get_vec2(hasher.hash(&cell.into_array())) + cell.numcast().unwrap();
+                assert!(edge_distance(seed_point.into_array()) > 0.05);
+            }
+        }
+    }
+
+    fn brute_force_distance_2d(hasher: &PermutationTable, point: [f64; 2]) -> (f64, [isize; 2]) {
+        let whole: Vector2<isize> = Vector2::from(point).floor().numcast().unwrap();
+        let mut nearest = (f64::MAX, [0; 2]);
+
+        for y in -2..=2 {
+            for x in -2..=2 {
+                let cell = whole + Vector2::new(x, y);
+                let seed_point =
+                    get_vec2(hasher.hash(&cell.into_array())) + cell.numcast().unwrap();
+                let distance = distance_functions::euclidean(&point, &seed_point.into_array());
+                if distance < nearest.0 {
+                    nearest = (distance, cell.into_array());
+                }
+            }
+        }
+
+        nearest
+    }
+
+    #[test]
+    fn test_smooth_distance_near_boundary() {
+        let hasher = PermutationTable::new(0);
+        let smooth = |k, point| {
+            (smooth_worley_2d(&hasher, distance_functions::euclidean, k, point) + 1.0) / 2.0
+        };
+
+        // Walk along a line until the nearest seed point changes, which
+        // brackets a cell boundary.
+        let mut previous = [0.1, 0.3];
+        let mut boundary = None;
+        for i in 1..1000 {
+            let point = [0.1 + i as f64 * 0.001, 0.3];
+            if brute_force_distance_2d(&hasher, point).1
+                != brute_force_distance_2d(&hasher, previous).1
+            {
+                boundary = Some(point);
+                break;
+            }
+            previous = point;
+        }
+        let boundary = boundary.expect("no cell boundary found");
+
+        for &point in [previous, boundary, [0.1, 0.3], [0.62, 0.3]].iter() {
+            let hard = brute_force_distance_2d(&hasher, point).0;
+
+            // A vanishing smoothness matches the hard distance, and smoothing
+            // never increases it.
+            assert!((smooth(1e-9, point) - hard).abs() < 1e-12);
+            assert!(smooth(0.05, point) <= hard);
+        }
+
+        // At the boundary two seed points are equally near, so the smooth
+        // minimum is lower by about k ln 2.
+        let hard = brute_force_distance_2d(&hasher, boundary).0;
+        assert!(hard - smooth(0.05, boundary) > 0.05 * 2.0_f64.ln() * 0.9);
+    }
+
+    #[test]
+    fn test_single_point_per_cell_matches_search() {
+        let hasher = PermutationTable::new(0);
+
+        for point in sample_points().take(500) {
+            for &return_type in [ReturnType::Distance, ReturnType::Value].iter() {
+                assert_eq!(
+                    worley_4d(&hasher, distance_functions::euclidean, return_type, point),
+                    worley_points_4d(
+                        &hasher,
+                        distance_functions::euclidean,
+                        return_type,
+                        1,
+                        0.0,
+                        point
+                    )
+                );
+            }
+
+            let point = [point[0], point[1]];
+            let distance = worley_points_2d(
+                &hasher,
+                distance_functions::euclidean,
+                ReturnType::Distance,
+                1,
+                0.0,
+                point,
+            );
+            assert!(
+                ((distance + 1.0) / 2.0 - brute_force_distance_2d(&hasher, point).0).abs() < 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_more_points_per_cell_are_nearer() {
+        let hasher = PermutationTable::new(0);
+        let mean_distance = |points_per_cell| {
+            sample_points()
+                .map(|point| {
+                    let value = worley_points_3d(
+                        &hasher,
+                        distance_functions::euclidean,
+                        ReturnType::Distance,
+                        points_per_cell,
+                        0.0,
+                        [point[0], point[1], point[2]],
+                    );
+                    (value + 1.0) / 2.0
+                })
+                .sum::<f64>()
+                / 2000.0
+        };
+
+        let one = mean_distance(1);
+        let three = mean_distance(3);
+        assert!(three < one * 0.9, "{} is not below {}", three, one);
+    }
+}
@@ -0,0 +1,725 @@
+//! Compact textual representation of noise function pipelines.
+//!
+//! A pipeline is written as nested function calls, where positional arguments
+//! are source functions and named arguments are scalar parameters:
+//!
+//! ```text
+//! clamp(scale_bias(perlin(seed=1), scale=0.5, bias=0.25), lower=-0.5, upper=0.5)
+//! ```
+//!
+//! Supported generators are `constant(value)`, `perlin`, `perlin_surflet`,
+//! `value`, `simplex`, `open_simplex` and `super_simplex` (all with `seed` and
+//! the per-axis frequencies `frequency_x` to `frequency_u`, and `perlin` and
+//! `value` with the per-axis periods `period_x` to `period_u`) and
+//! `worley(seed, frequency, smoothness, normalized, points_per_cell)` with the
+//! per-axis periods. Supported modifiers are `abs`, `negate`,
+//! `clamp(lower, upper, nan_replacement)`, `scale_bias(scale, bias)` and
+//! `exponent(exponent, negative_exponent, positive_exponent, input_min,
+//! input_max)`. Supported combiners are `add`, `multiply`, `min`, `max` and
+//! `power`. Omitted parameters take the default value of the corresponding
+//! noise function, and flags such as `normalized` are written as 0 or 1.
+//!
+//! The distance function and return type of Worley noise have no textual
+//! representation: parsed Worley noise uses the defaults, and printing Worley
+//! noise with others set loses them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use noise::{dsl::parse_dsl, NoiseFn};
+//!
+//! let pipeline = parse_dsl("abs(add(perlin(seed=1), constant(value=0.5)))").unwrap();
+//! let val = pipeline.get([1.5, 2.5]);
+//! assert_eq!("abs(add(perlin(seed=1), constant(value=0.5)))", pipeline.to_dsl());
+//! ```
+
+use crate::{
+    Abs, Add, Clamp, Constant, Exponent, Max, Min, Multiply, Negate, NoiseFn, OpenSimplex, Perlin,
+    PerlinSurflet, Power, ScaleBias, Seedable, Simplex, SuperSimplex, Value, Worley,
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+/// Suffixes of the per-axis parameters, in order.
+const AXES: [&str; 4] = ["x", "y", "z", "u"];
+
+/// Noise function pipeline parsed from, or printable as, its textual
+/// representation.
+#[derive(Clone, Debug)]
+pub enum Expr {
+    Constant(Constant),
+    Perlin(Perlin),
+    PerlinSurflet(PerlinSurflet),
+    Value(Value),
+    Simplex(Simplex),
+    OpenSimplex(OpenSimplex),
+    SuperSimplex(SuperSimplex),
+    Worley(Worley),
+    Abs(Box<Abs<f64, Expr, 2>>),
+    Negate(Box<Negate<f64, Expr, 2>>),
+    Clamp(Box<Clamp<f64, Expr, 2>>),
+    ScaleBias(Box<ScaleBias<f64, Expr, 2>>),
+    Exponent(Box<Exponent<f64, Expr, 2>>),
+    Add(Box<Add<f64, Expr, Expr, 2>>),
+    Multiply(Box<Multiply<f64, Expr, Expr, 2>>),
+    Min(Box<Min<f64, Expr, Expr, 2>>),
+    Max(Box<Max<f64, Expr, Expr, 2>>),
+    Power(Box<Power<f64, Expr, Expr, 2>>),
+}
+
+impl Expr {
+    /// Returns the textual representation of the pipeline, which can be read
+    /// back with [`parse_dsl`].
+    pub fn to_dsl(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl NoiseFn<f64, 2> for Expr {
+    fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
+        let point = point.into();
+
+        match self {
+            Expr::Constant(noise) => noise.get(point),
+            Expr::Perlin(noise) => noise.get(point),
+            Expr::PerlinSurflet(noise) => noise.get(point),
+            Expr::Value(noise) => noise.get(point),
+            Expr::Simplex(noise) => noise.get(point),
+            Expr::OpenSimplex(noise) => noise.get(point),
+            Expr::SuperSimplex(noise) => noise.get(point),
+            Expr::Worley(noise) => noise.get(point),
+            Expr::Abs(noise) => noise.get(point),
+            Expr::Negate(noise) => noise.get(point),
+            Expr::Clamp(noise) => noise.get(point),
+            Expr::ScaleBias(noise) => noise.get(point),
+            Expr::Exponent(noise) => noise.get(point),
+            Expr::Add(noise) => noise.get(point),
+            Expr::Multiply(noise) => noise.get(point),
+            Expr::Min(noise) => noise.get(point),
+            Expr::Max(noise) => noise.get(point),
+            Expr::Power(noise) => noise.get(point),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Constant(noise) => write!(f, "constant(value={})", noise.value),
+            Expr::Perlin(noise) => {
+                write!(f, "perlin(seed={}", noise.seed())?;
+                write_frequencies(f, noise.frequencies())?;
+                write_period(f, noise.period())?;
+                f.write_str(")")
+            }
+            Expr::PerlinSurflet(noise) => {
+                write!(f, "perlin_surflet(seed={}", noise.seed())?;
+                write_frequencies(f, noise.frequencies())?;
+                f.write_str(")")
+            }
+            Expr::Value(noise) => {
+                write!(f, "value(seed={}", noise.seed())?;
+                write_frequencies(f, noise.frequencies())?;
+                write_period(f, noise.period())?;
+                f.write_str(")")
+            }
+            Expr::Simplex(noise) => {
+                write!(f, "simplex(seed={}", noise.seed())?;
+                write_frequencies(f, noise.frequencies())?;
+                f.write_str(")")
+            }
+            Expr::OpenSimplex(noise) => {
+                write!(f, "open_simplex(seed={}", noise.seed())?;
+                write_frequencies(f, noise.frequencies())?;
+                f.write_str(")")
+            }
+            Expr::SuperSimplex(noise) => {
+                write!(f, "super_simplex(seed={}", noise.seed())?;
+                write_frequencies(f, noise.frequencies())?;
+                f.write_str(")")
+            }
+            Expr::Worley(noise) => {
+                write!(
+                    f,
+                    "worley(seed={}, frequency={}",
+                    noise.seed(),
+                    noise.frequency
+                )?;
+                if noise.smoothness() != 0.0 {
+                    write!(f, ", smoothness={}", noise.smoothness())?;
+                }
+                if noise.is_normalized() {
+                    f.write_str(", normalized=1")?;
+                }
+                if noise.points_per_cell() != 1 {
+                    write!(f, ", points_per_cell={}", noise.points_per_cell())?;
+                }
+                write_period(f, noise.period())?;
+                f.write_str(")")
+            }
+            Expr::Abs(noise) => write!(f, "abs({})", noise.source),
+            Expr::Negate(noise) => write!(f, "negate({})", noise.source),
+            Expr::Clamp(noise) => {
+                write!(
+                    f,
+                    "clamp({}, lower={}, upper={}",
+                    noise.source, noise.bounds.0, noise.bounds.1
+                )?;
+                if let Some(replacement) = noise.nan_replacement {
+                    write!(f, ", nan_replacement={}", replacement)?;
+                }
+                f.write_str(")")
+            }
+            Expr::ScaleBias(noise) => write!(
+                f,
+                "scale_bias({}, scale={}, bias={})",
+                noise.source, noise.scale, noise.bias
+            ),
+            Expr::Exponent(noise) => {
+                write!(f, "exponent({}, exponent={}", noise.source, noise.exponent)?;
+                if let Some((negative, positive)) = noise.signed_exponents {
+                    write!(
+                        f,
+                        ", negative_exponent={}, positive_exponent={}",
+                        negative, positive
+                    )?;
+                }
+                if let Some((min, max)) = noise.input_range {
+                    write!(f, ", input_min={}, input_max={}", min, max)?;
+                }
+                f.write_str(")")
+            }
+            Expr::Add(noise) => write!(f, "add({}, {})", noise.source1, noise.source2),
+            Expr::Multiply(noise) => write!(f, "multiply({}, {})", noise.source1, noise.source2),
+            Expr::Min(noise) => write!(f, "min({}, {})", noise.source1, noise.source2),
+            Expr::Max(noise) => write!(f, "max({}, {})", noise.source1, noise.source2),
+            Expr::Power(noise) => write!(f, "power({}, {})", noise.source1, noise.source2),
+        }
+    }
+}
+
+/// Writes the frequency of each axis that differs from the default.
+fn write_frequencies(f: &mut fmt::Formatter<'_>, frequencies: [f64; 4]) -> fmt::Result {
+    for (axis, &frequency) in AXES.iter().zip(frequencies.iter()) {
+        if frequency != 1.0 {
+            write!(f, ", frequency_{}={}", axis, frequency)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the period of each axis that tiles.
+fn write_period(f: &mut fmt::Formatter<'_>, period: [u32; 4]) -> fmt::Result {
+    for (axis, &period) in AXES.iter().zip(period.iter()) {
+        if period != 0 {
+            write!(f, ", period_{}={}", axis, period)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Error returned when a pipeline string cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset in the input at which the error was detected.
+    pub position: usize,
+
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Parses the textual representation of a pipeline.
+pub fn parse_dsl(input: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser { input, position: 0 };
+
+    let expr = parser.expr()?;
+    parser.skip_whitespace();
+    if parser.position < input.len() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+
+    Ok(expr)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+/// Arguments of a single call, split into sources and named parameters.
+struct Args {
+    position: usize,
+    sources: Vec<Expr>,
+    params: Vec<(String, f64, usize)>,
+}
+
+impl Args {
+    fn expect_sources(&self, name: &str, count: usize) -> Result<(), ParseError> {
+        if self.sources.len() == count {
+            Ok(())
+        } else {
+            Err(ParseError {
+                position: self.position,
+                message: format!(
+                    "`{}` takes {} source(s), found {}",
+                    name,
+                    count,
+                    self.sources.len()
+                ),
+            })
+        }
+    }
+
+    fn source(&mut self, name: &str) -> Result<Expr, ParseError> {
+        self.expect_sources(name, 1)?;
+        Ok(self.sources.remove(0))
+    }
+
+    fn source_pair(&mut self, name: &str) -> Result<(Expr, Expr), ParseError> {
+        self.expect_sources(name, 2)?;
+        let source2 = self.sources.pop().unwrap();
+        let source1 = self.sources.pop().unwrap();
+        Ok((source1, source2))
+    }
+
+    fn optional_param(&mut self, name: &str) -> Option<f64> {
+        self.params
+            .iter()
+            .position(|(param, _, _)| param == name)
+            .map(|index| self.params.remove(index).1)
+    }
+
+    fn param(&mut self, name: &str, default: f64) -> f64 {
+        self.optional_param(name).unwrap_or(default)
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        ParseError {
+            position: self.position,
+            message,
+        }
+    }
+
+    fn unsigned(&mut self, name: &str, default: u32) -> Result<u32, ParseError> {
+        let value = self.param(name, f64::from(default));
+
+        if value.fract() != 0.0 || value < 0.0 || value > f64::from(u32::MAX) {
+            return Err(self.error(format!("invalid {} `{}`", name, value)));
+        }
+
+        Ok(value as u32)
+    }
+
+    fn flag(&mut self, name: &str) -> Result<bool, ParseError> {
+        let value = self.param(name, 0.0);
+
+        if value != 0.0 && value != 1.0 {
+            return Err(self.error(format!("invalid {} `{}`, expected 0 or 1", name, value)));
+        }
+
+        Ok(value == 1.0)
+    }
+
+    fn seed(&mut self) -> Result<u32, ParseError> {
+        self.unsigned("seed", 0)
+    }
+
+    fn frequencies(&mut self) -> [f64; 4] {
+        let mut frequencies = [1.0; 4];
+        for (axis, frequency) in AXES.iter().zip(frequencies.iter_mut()) {
+            *frequency = self.param(&format!("frequency_{}", axis), 1.0);
+        }
+
+        frequencies
+    }
+
+    fn period(&mut self) -> Result<[u32; 4], ParseError> {
+        let mut period = [0; 4];
+        for (axis, period) in AXES.iter().zip(period.iter_mut()) {
+            *period = self.unsigned(&format!("period_{}", axis), 0)?;
+        }
+
+        Ok(period)
+    }
+
+    /// Fails if any parameter was not consumed by the noise function.
+    fn finish(self, name: &str) -> Result<(), ParseError> {
+        match self.params.first() {
+            Some((param, _, position)) => Err(ParseError {
+                position: *position,
+                message: format!("unknown parameter `{}` for `{}`", param, name),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> ParseError {
+        ParseError {
+            position: self.position,
+            message: message.to_string(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.as_bytes().get(self.position).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), ParseError> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", byte as char)))
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a str {
+        self.skip_whitespace();
+        let start = self.position;
+        while let Some(&byte) = self.input.as_bytes().get(self.position) {
+            if !predicate(byte) {
+                break;
+            }
+            self.position += 1;
+        }
+
+        &self.input[start..self.position]
+    }
+
+    fn ident(&mut self) -> Result<&'a str, ParseError> {
+        let ident = self.take_while(|byte| byte.is_ascii_alphanumeric() || byte == b'_');
+        if ident.is_empty() {
+            Err(self.error("expected a name"))
+        } else {
+            Ok(ident)
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, ParseError> {
+        let start = self.position;
+        let number = self.take_while(|byte| {
+            byte.is_ascii_digit() || matches!(byte, b'+' | b'-' | b'.' | b'e' | b'E')
+        });
+
+        number.parse().map_err(|_| ParseError {
+            position: start,
+            message: format!("invalid number `{}`", number),
+        })
+    }
+
+    fn args(&mut self) -> Result<Args, ParseError> {
+        let mut args = Args {
+            position: self.position,
+            sources: Vec::new(),
+            params: Vec::new(),
+        };
+
+        if !self.eat(b'(') || self.eat(b')') {
+            return Ok(args);
+        }
+
+        loop {
+            self.skip_whitespace();
+            let start = self.position;
+            let ident = self.ident()?;
+
+            if self.eat(b'=') {
+                if args.params.iter().any(|(param, _, _)| param == ident) {
+                    return Err(ParseError {
+                        position: start,
+                        message: format!("duplicate parameter `{}`", ident),
+                    });
+                }
+                args.params.push((ident.to_string(), self.number()?, start));
+            } else {
+                args.sources.push(self.call(ident, start)?);
+            }
+
+            if !self.eat(b',') {
+                break;
+            }
+        }
+        self.expect(b')')?;
+
+        Ok(args)
+    }
+
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        self.skip_whitespace();
+        let start = self.position;
+        let name = self.ident()?;
+
+        self.call(name, start)
+    }
+
+    fn call(&mut self, name: &str, start: usize) -> Result<Expr, ParseError> {
+        let mut args = self.args()?;
+
+        let expr = match name {
+            "constant" => {
+                args.expect_sources(name, 0)?;
+                Expr::Constant(Constant::new(args.param("value", 0.0)))
+            }
+            "perlin" => {
+                args.expect_sources(name, 0)?;
+                let perlin = Perlin::default()
+                    .set_seed(args.seed()?)
+                    .set_frequencies(args.frequencies())
+                    .set_period(args.period()?);
+                Expr::Perlin(perlin)
+            }
+            "perlin_surflet" => {
+                args.expect_sources(name, 0)?;
+                let perlin_surflet = PerlinSurflet::default()
+                    .set_seed(args.seed()?)
+                    .set_frequencies(args.frequencies());
+                Expr::PerlinSurflet(perlin_surflet)
+            }
+            "value" => {
+                args.expect_sources(name, 0)?;
+                let value = Value::default()
+                    .set_seed(args.seed()?)
+                    .set_frequencies(args.frequencies())
+                    .set_period(args.period()?);
+                Expr::Value(value)
+            }
+            "simplex" => {
+                args.expect_sources(name, 0)?;
+                let simplex = Simplex::default()
+                    .set_seed(args.seed()?)
+                    .set_frequencies(args.frequencies());
+                Expr::Simplex(simplex)
+            }
+            "open_simplex" => {
+                args.expect_sources(name, 0)?;
+                let open_simplex = OpenSimplex::default()
+                    .set_seed(args.seed()?)
+                    .set_frequencies(args.frequencies());
+                Expr::OpenSimplex(open_simplex)
+            }
+            "super_simplex" => {
+                args.expect_sources(name, 0)?;
+                let super_simplex = SuperSimplex::default()
+                    .set_seed(args.seed()?)
+                    .set_frequencies(args.frequencies());
+                Expr::SuperSimplex(super_simplex)
+            }
+            "worley" => {
+                args.expect_sources(name, 0)?;
+                let worley = Worley::default()
+                    .set_seed(args.seed()?)
+                    .set_frequency(args.param("frequency", Worley::DEFAULT_FREQUENCY))
+                    .smooth(args.param("smoothness", 0.0))
+                    .normalized(args.flag("normalized")?)
+                    .set_points_per_cell(args.unsigned("points_per_cell", 1)? as usize)
+                    .set_period(args.period()?);
+                Expr::Worley(worley)
+            }
+            "abs" => {
+                let source = args.source(name)?;
+                Expr::Abs(Box::new(Abs::new(source)))
+            }
+            "negate" => {
+                let source = args.source(name)?;
+                Expr::Negate(Box::new(Negate::new(source)))
+            }
+            "clamp" => {
+                let source = args.source(name)?;
+                let clamp = Clamp::new(source);
+                let lower = args.param("lower", clamp.bounds.0);
+                let upper = args.param("upper", clamp.bounds.1);
+                let clamp = clamp.set_bounds(lower, upper);
+                let clamp = match args.optional_param("nan_replacement") {
+                    Some(replacement) => clamp.sanitize_nan(replacement),
+                    None => clamp,
+                };
+                Expr::Clamp(Box::new(clamp))
+            }
+            "scale_bias" => {
+                let source = args.source(name)?;
+                let scale_bias = ScaleBias::new(source);
+                let scale = args.param("scale", scale_bias.scale);
+                let bias = args.param("bias", scale_bias.bias);
+                Expr::ScaleBias(Box::new(scale_bias.set_scale(scale).set_bias(bias)))
+            }
+            "exponent" => {
+                let source = args.source(name)?;
+                let exponent = Exponent::new(source);
+                let value = args.param("exponent", exponent.exponent);
+                let mut exponent = exponent.set_exponent(value);
+
+                let negative = args.optional_param("negative_exponent");
+                let positive = args.optional_param("positive_exponent");
+                if negative.is_some() || positive.is_some() {
+                    exponent = exponent
+                        .set_exponents(negative.unwrap_or(value), positive.unwrap_or(value));
+                }
+
+                match (
+                    args.optional_param("input_min"),
+                    args.optional_param("input_max"),
+                ) {
+                    (None, None) => {}
+                    (Some(min), Some(max)) if min < max => {
+                        exponent = exponent.set_input_range(min, max);
+                    }
+                    _ => {
+                        return Err(args.error(format!(
+                            "`{}` takes a non-empty range from `input_min` to `input_max`",
+                            name
+                        )))
+                    }
+                }
+
+                Expr::Exponent(Box::new(exponent))
+            }
+            "add" => {
+                let (source1, source2) = args.source_pair(name)?;
+                Expr::Add(Box::new(Add::new(source1, source2)))
+            }
+            "multiply" => {
+                let (source1, source2) = args.source_pair(name)?;
+                Expr::Multiply(Box::new(Multiply::new(source1, source2)))
+            }
+            "min" => {
+                let (source1, source2) = args.source_pair(name)?;
+                Expr::Min(Box::new(Min::new(source1, source2)))
+            }
+            "max" => {
+                let (source1, source2) = args.source_pair(name)?;
+                Expr::Max(Box::new(Max::new(source1, source2)))
+            }
+            "power" => {
+                let (source1, source2) = args.source_pair(name)?;
+                Expr::Power(Box::new(Power::new(source1, source2)))
+            }
+            _ => {
+                return Err(ParseError {
+                    position: start,
+                    message: format!("unknown noise function `{}`", name),
+                })
+            }
+        };
+        args.finish(name)?;
+
+        Ok(expr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let dsl = "clamp(add(scale_bias(perlin(seed=1), scale=0.5, bias=0.25), \
+                   abs(worley(seed=3, frequency=2.5))), lower=-0.5, upper=0.75)";
+
+        let expr = parse_dsl(dsl).unwrap();
+        let reparsed = parse_dsl(&expr.to_dsl()).unwrap();
+
+        assert_eq!(dsl, expr.to_dsl());
+        assert_eq!(expr.to_dsl(), reparsed.to_dsl());
+
+        let expected = Clamp::new(Add::new(
+            ScaleBias::new(Perlin::new(1)).set_scale(0.5).set_bias(0.25),
+            Abs::new(Worley::new(3).set_frequency(2.5)),
+        ))
+        .set_bounds(-0.5, 0.75);
+        for &point in [[0.3, 0.7], [-4.2, 1.9], [10.5, -3.3]].iter() {
+            assert_eq!(expected.get(point), expr.get(point));
+            assert_eq!(expr.get(point), reparsed.get(point));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_options() {
+        let dsl = "max(exponent(perlin(seed=2, frequency_x=2, frequency_y=0.5, period_x=4), \
+                   exponent=1, negative_exponent=2, positive_exponent=0.5, input_min=-1, \
+                   input_max=1), clamp(worley(seed=3, frequency=1.5, smoothness=0.1, \
+                   normalized=1, points_per_cell=3, period_y=8), lower=-1, upper=1, \
+                   nan_replacement=0))";
+
+        let expr = parse_dsl(dsl).unwrap();
+        assert_eq!(dsl, expr.to_dsl());
+
+        let expected = Max::new(
+            Exponent::new(Perlin::new(2).set_frequencies([2.0, 0.5]).set_period([4]))
+                .set_exponents(2.0, 0.5)
+                .set_input_range(-1.0, 1.0),
+            Clamp::new(
+                Worley::new(3)
+                    .set_frequency(1.5)
+                    .smooth(0.1)
+                    .normalized(true)
+                    .set_points_per_cell(3)
+                    .set_period([0, 8]),
+            )
+            .sanitize_nan(0.0),
+        );
+        for &point in [[0.3, 0.7], [-4.2, 1.9], [10.5, -3.3]].iter() {
+            assert_eq!(expected.get(point), expr.get(point));
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let expr = parse_dsl(" clamp ( perlin ) ").unwrap();
+
+        assert_eq!("clamp(perlin(seed=0), lower=-1, upper=1)", expr.to_dsl());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |input| parse_dsl(input).unwrap_err();
+
+        assert_eq!(0, error("fbm(seed=1)").position);
+        assert_eq!(7, error("perlin(seeds=1)").position);
+        assert_eq!(15, error("perlin(seed=1, seed=2)").position);
+        assert!(error("abs(perlin, value)").message.contains("1 source"));
+        assert!(error("add(perlin)").message.contains("2 source"));
+        assert!(error("perlin(seed=-1)").message.contains("seed"));
+        assert!(error("perlin(seed=x)").message.contains("number"));
+        assert!(error("abs(perlin").message.contains("`)`"));
+        assert!(error("perlin perlin").message.contains("trailing"));
+        assert!(error("simplex(period_x=2)").message.contains("unknown"));
+        assert!(error("worley(normalized=2)").message.contains("normalized"));
+        assert!(error("exponent(perlin, input_min=1)")
+            .message
+            .contains("input_max"));
+        assert!(error("exponent(perlin, input_min=1, input_max=1)")
+            .message
+            .contains("input_max"));
+    }
+}
@@ -15,11 +15,17 @@
 #[macro_use]
 extern crate alloc;
 
-pub use crate::noise_fns::*;
+pub use crate::{
+    math::{coordinate::Coordinate, point::IntoPoint},
+    noise_fns::*,
+    permutationtable::{DefaultHasher, NoiseHasher, PermutationTable, SeedableHasher},
+};
 
 pub mod core;
+pub mod dsl;
 mod gradient;
 mod math;
 mod noise_fns;
 mod permutationtable;
+pub mod prelude;
 pub mod utils;
@@ -1,7 +1,11 @@
 //! An ultra-light private math library to make our short lives easier as we
 //! implement super-complex noise stuff.
 
+use self::coordinate::Coordinate;
+
+pub(crate) mod coordinate;
 pub(crate) mod interpolate;
+pub(crate) mod point;
 pub(crate) mod s_curve;
 pub(crate) mod vectors;
 
@@ -16,3 +20,30 @@ pub(crate) fn scale_shift(value: f64, n: f64) -> f64 {
 pub(crate) fn scale_shift(value: f64, n: f64) -> f64 {
     (value.abs() * n) + -1.0_f64
 }
+
+/// Expands per-axis values to four axes, filling the missing axes with
+/// `default`.
+pub(crate) fn expand_axes<const DIM: usize>(values: [f64; DIM], default: f64) -> [f64; 4] {
+    assert!(DIM <= 4, "at most four axes are supported");
+
+    let mut result = [default; 4];
+    result[..DIM].copy_from_slice(&values);
+    result
+}
+
+/// Expands per-axis frequencies to four axes, leaving the missing axes at 1.0.
+pub(crate) fn axis_frequencies<const DIM: usize>(frequencies: [f64; DIM]) -> [f64; 4] {
+    expand_axes(frequencies, 1.0)
+}
+
+/// Multiplies each coordinate of the point by the frequency of its axis.
+#[inline]
+pub(crate) fn scale_axes<T: Coordinate, const DIM: usize>(
+    mut point: [T; DIM],
+    frequencies: &[f64; 4],
+) -> [T; DIM] {
+    for (coordinate, &frequency) in point.iter_mut().zip(frequencies) {
+        *coordinate = *coordinate * T::from_f64(frequency);
+    }
+    point
+}
@@ -0,0 +1,165 @@
+use core::ops::{Add, Mul, Sub};
+
+/// Numeric type of the coordinates of an input value.
+///
+/// The point transformers and the lattice math only need a few operations on
+/// coordinates, so they are generic over this trait instead of being fixed to
+/// `f64`. It is implemented for `f64` and `f32`. A fixed-point type can
+/// implement it to keep coordinates exact and reproducible across platforms,
+/// which matters when several machines must generate the same world; the
+/// coordinates are only converted to `f64` where the noise itself is
+/// evaluated.
+pub trait Coordinate:
+    Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Converts an `f64` parameter, such as a scale or an angle's sine, to a
+    /// coordinate.
+    fn from_f64(value: f64) -> Self;
+
+    /// Converts the coordinate to an `f64`.
+    fn to_f64(self) -> f64;
+
+    /// Returns the largest whole coordinate less than or equal to this one.
+    fn floor(self) -> Self;
+}
+
+impl Coordinate for f64 {
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    #[inline]
+    fn floor(self) -> Self {
+        f64::floor(self)
+    }
+}
+
+impl Coordinate for f32 {
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    #[inline]
+    fn floor(self) -> Self {
+        f32::floor(self)
+    }
+}
+
+/// Splits a coordinate into the index of the lattice cell containing it and
+/// its position within that cell.
+#[inline]
+pub(crate) fn lattice_cell<T: Coordinate>(coordinate: T) -> (isize, T) {
+    let floored = coordinate.floor();
+    (floored.to_f64() as isize, coordinate - floored)
+}
+
+/// Splits every coordinate of a point with [`lattice_cell`], returning the
+/// lattice cell containing the point and its position within that cell.
+#[inline]
+pub(crate) fn lattice_point<T: Coordinate, const DIM: usize>(
+    point: [T; DIM],
+) -> ([isize; DIM], [f64; DIM]) {
+    let mut cells = [0; DIM];
+    let mut fractions = [0.0; DIM];
+    for ((cell, fraction), &coordinate) in cells.iter_mut().zip(&mut fractions).zip(&point) {
+        let (whole, part) = lattice_cell(coordinate);
+        *cell = whole;
+        *fraction = part.to_f64();
+    }
+
+    (cells, fractions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{
+            perlin::{perlin_2d, perlin_3d, perlin_4d, perlin_lattice_sample},
+            simplex::{simplex_2d, simplex_3d},
+            value::{value_2d, value_3d, value_4d},
+        },
+        PermutationTable,
+    };
+
+    #[test]
+    fn test_lattice_cell() {
+        assert_eq!((2, 0.75), lattice_cell(2.75_f64));
+        assert_eq!((-3, 0.75), lattice_cell(-2.25_f64));
+        assert_eq!((-3, 0.75), lattice_cell(-2.25_f32));
+        assert_eq!((0, 0.0), lattice_cell(0.0_f32));
+    }
+
+    #[test]
+    fn test_f32_lattice_matches_f64() {
+        let hasher = PermutationTable::new(5);
+        for i in 0..32 {
+            let point = [i as f32 * 0.375 - 4.0, i as f32 * -0.625 + 2.5];
+            let expected = perlin_lattice_sample(point.map(f64::from), &hasher);
+            let actual = perlin_lattice_sample(point, &hasher);
+
+            assert_eq!(expected.cell, actual.cell);
+            assert_eq!(expected.fraction, actual.fraction);
+            assert_eq!(expected.corners, actual.corners);
+        }
+    }
+
+    #[test]
+    fn test_f32_generators_match_f64() {
+        let hasher = PermutationTable::new(5);
+        for i in 0..32 {
+            // Dyadic coordinates, so that they are exact in both types.
+            let point = [
+                i as f32 * 0.375 - 4.0,
+                i as f32 * -0.625 + 2.5,
+                i as f32 * 0.125 - 1.0,
+                i as f32 * -0.25 + 0.5,
+            ];
+            let [x, y, z, _] = point;
+            let [x64, y64, z64, u64] = point.map(f64::from);
+
+            assert_eq!(perlin_2d([x64, y64], &hasher), perlin_2d([x, y], &hasher));
+            assert_eq!(
+                perlin_3d([x64, y64, z64], &hasher),
+                perlin_3d([x, y, z], &hasher)
+            );
+            assert_eq!(
+                perlin_4d([x64, y64, z64, u64], &hasher),
+                perlin_4d(point, &hasher)
+            );
+            assert_eq!(value_2d([x64, y64], &hasher), value_2d([x, y], &hasher));
+            assert_eq!(
+                value_3d([x64, y64, z64], &hasher),
+                value_3d([x, y, z], &hasher)
+            );
+            assert_eq!(
+                value_4d([x64, y64, z64, u64], &hasher),
+                value_4d(point, &hasher)
+            );
+
+            // The skew factors are rounded to f32, so simplex noise only
+            // matches closely.
+            let close = |a: f64, b: f64| assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+            close(
+                simplex_2d([x64, y64], &hasher).0,
+                simplex_2d([x, y], &hasher).0,
+            );
+            close(
+                simplex_3d([x64, y64, z64], &hasher).0,
+                simplex_3d([x, y, z], &hasher).0,
+            );
+        }
+    }
+}
@@ -44,3 +44,15 @@ where
     let s = n1;
     p * alpha * alpha * alpha + q * alpha * alpha + r * alpha + s
 }
+
+/// Returns the derivative of [`cubic`] with respect to `alpha`.
+#[inline]
+pub(crate) fn cubic_derivative<T>(n0: T, n1: T, n2: T, n3: T, alpha: T) -> T
+where
+    T: Add<Output = T> + Mul<Output = T> + Sub<Output = T> + Copy,
+{
+    let p = (n3 - n2) - (n0 - n1);
+    let q = (n0 - n1) - p;
+    let r = n2 - n0;
+    (p + p + p) * alpha * alpha + (q + q) * alpha + r
+}
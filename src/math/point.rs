@@ -0,0 +1,136 @@
+/// Conversion into the input value of a noise function.
+///
+/// [`NoiseFn::get`](crate::NoiseFn::get) takes `impl Into<[f64; DIM]>`, which
+/// foreign types such as `[f32; DIM]` or engine vectors cannot implement.
+/// This trait is local, so it is implemented for them instead, and
+/// [`NoiseFn::get_point`](crate::NoiseFn::get_point) accepts any of them.
+///
+/// It is implemented for `[f64; DIM]` and `[f32; DIM]`, for the `glam` vector
+/// types with the `glam` feature, and for the two- to four-dimensional
+/// `nalgebra` vectors and points with the `nalgebra` feature.
+pub trait IntoPoint<const DIM: usize> {
+    /// Converts the value into an input value.
+    fn into_point(self) -> [f64; DIM];
+}
+
+impl<const DIM: usize> IntoPoint<DIM> for [f64; DIM] {
+    #[inline]
+    fn into_point(self) -> [f64; DIM] {
+        self
+    }
+}
+
+impl<const DIM: usize> IntoPoint<DIM> for [f32; DIM] {
+    #[inline]
+    fn into_point(self) -> [f64; DIM] {
+        let mut point = [0.0; DIM];
+        for (coordinate, &value) in point.iter_mut().zip(&self) {
+            *coordinate = f64::from(value);
+        }
+        point
+    }
+}
+
+#[cfg(feature = "glam")]
+macro_rules! impl_glam {
+    ($($vector:ident => $dim:expr),*) => {
+        $(
+            impl IntoPoint<$dim> for glam::$vector {
+                #[inline]
+                fn into_point(self) -> [f64; $dim] {
+                    self.to_array().into_point()
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "glam")]
+impl_glam!(Vec2 => 2, Vec3 => 3, Vec3A => 3, Vec4 => 4, DVec2 => 2, DVec3 => 3, DVec4 => 4);
+
+#[cfg(feature = "nalgebra")]
+macro_rules! impl_nalgebra {
+    ($($dim:expr => [$($axis:expr),*]),*) => {
+        $(
+            impl IntoPoint<$dim> for nalgebra::SVector<f32, $dim> {
+                #[inline]
+                fn into_point(self) -> [f64; $dim] {
+                    [$(f64::from(self[$axis])),*]
+                }
+            }
+
+            impl IntoPoint<$dim> for nalgebra::SVector<f64, $dim> {
+                #[inline]
+                fn into_point(self) -> [f64; $dim] {
+                    [$(self[$axis]),*]
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "nalgebra")]
+impl_nalgebra!(2 => [0, 1], 3 => [0, 1, 2], 4 => [0, 1, 2, 3]);
+
+#[cfg(feature = "nalgebra")]
+impl<T, const DIM: usize> IntoPoint<DIM> for nalgebra::Point<T, DIM>
+where
+    T: nalgebra::Scalar,
+    nalgebra::SVector<T, DIM>: IntoPoint<DIM>,
+{
+    #[inline]
+    fn into_point(self) -> [f64; DIM] {
+        self.coords.into_point()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NoiseFn, Perlin};
+
+    #[test]
+    fn test_f32_array_samples_like_f64() {
+        let perlin = Perlin::new(3);
+        for i in 0..32 {
+            let point = [i as f32 * 0.375 - 4.0, i as f32 * -0.625 + 2.5];
+
+            assert_eq!(perlin.get(point.map(f64::from)), perlin.get_point(point));
+        }
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam_vectors_sample_like_arrays() {
+        let perlin = Perlin::new(3);
+        for i in 0..32 {
+            let (x, y, z) = (i as f32 * 0.375 - 4.0, i as f32 * -0.625 + 2.5, 0.5);
+
+            assert_eq!(
+                perlin.get([f64::from(x), f64::from(y)]),
+                perlin.get_point(glam::Vec2::new(x, y))
+            );
+            assert_eq!(
+                perlin.get([f64::from(x), f64::from(y), f64::from(z)]),
+                perlin.get_point(glam::Vec3A::new(x, y, z))
+            );
+        }
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra_vectors_sample_like_arrays() {
+        let perlin = Perlin::new(3);
+        for i in 0..32 {
+            let (x, y) = (i as f64 * 0.375 - 4.0, i as f64 * -0.625 + 2.5);
+
+            assert_eq!(
+                perlin.get([x, y]),
+                perlin.get_point(nalgebra::Vector2::new(x, y))
+            );
+            assert_eq!(
+                perlin.get([x, y]),
+                perlin.get_point(nalgebra::Point2::new(x as f32, y as f32))
+            );
+        }
+    }
+}
@@ -8,6 +8,7 @@ mod vector2;
 mod vector3;
 mod vector4;
 
+#[allow(dead_code)]
 pub trait Vector<T, const DIM: usize> {
     // Create a vector with a single value broadcasted to all elements.
     fn broadcast(value: T) -> Self
@@ -39,16 +39,7 @@ impl<T> Vector2<T> {
         T: NumCast,
         D: NumCast,
     {
-        Some(Vector2::new(
-            match D::from(self.x) {
-                Some(x) => x,
-                None => return None,
-            },
-            match D::from(self.y) {
-                Some(y) => y,
-                None => return None,
-            },
-        ))
+        Some(Vector2::new(D::from(self.x)?, D::from(self.y)?))
     }
 }
 
@@ -54,18 +54,9 @@ impl<T> Vector3<T> {
         D: NumCast,
     {
         Some(Vector3::new(
-            match D::from(self.x) {
-                Some(x) => x,
-                None => return None,
-            },
-            match D::from(self.y) {
-                Some(y) => y,
-                None => return None,
-            },
-            match D::from(self.z) {
-                Some(z) => z,
-                None => return None,
-            },
+            D::from(self.x)?,
+            D::from(self.y)?,
+            D::from(self.z)?,
         ))
     }
 }
@@ -70,22 +70,10 @@ impl<T> Vector4<T> {
         D: NumCast,
     {
         Some(Vector4::new(
-            match D::from(self.x) {
-                Some(x) => x,
-                None => return None,
-            },
-            match D::from(self.y) {
-                Some(y) => y,
-                None => return None,
-            },
-            match D::from(self.z) {
-                Some(z) => z,
-                None => return None,
-            },
-            match D::from(self.w) {
-                Some(w) => w,
-                None => return None,
-            },
+            D::from(self.x)?,
+            D::from(self.y)?,
+            D::from(self.z)?,
+            D::from(self.w)?,
         ))
     }
 }
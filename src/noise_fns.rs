@@ -1,6 +1,7 @@
 pub use self::{
     cache::*, combiners::*, generators::*, modifiers::*, selectors::*, transformers::*,
 };
+use crate::IntoPoint;
 use alloc::boxed::Box;
 
 mod cache;
@@ -19,13 +20,45 @@ mod transformers;
 /// value. Some of these methods include:
 ///
 /// * Calculating a value using a coherent-noise function or some other
-///     mathematical function.
+///   mathematical function.
 /// * Mathematically changing the output value from another noise function
-///     in various ways.
+///   in various ways.
 /// * Combining the output values from two noise functions in various ways.
 pub trait NoiseFn<T, const DIM: usize> {
     fn get(&self, point: impl Into<[T; DIM]>) -> f64;
 
+    /// Returns the output value at a borrowed point, such as one stored in a
+    /// collection. The point is copied into the input of [`get`](Self::get),
+    /// so this is a convenience for callers that hold a reference, not a way
+    /// to avoid the copy.
+    #[inline]
+    fn get_ref(&self, point: &[T; DIM]) -> f64
+    where
+        T: Copy,
+    {
+        self.get(*point)
+    }
+
+    /// Returns the output value at a point of any type that converts into an
+    /// input value, such as an `[f32; DIM]` array or, with the `glam` or
+    /// `nalgebra` feature, an engine vector. See [`IntoPoint`].
+    #[inline]
+    fn get_point(&self, point: impl IntoPoint<DIM>) -> f64
+    where
+        Self: NoiseFn<f64, DIM>,
+    {
+        NoiseFn::<f64, DIM>::get(self, point.into_point())
+    }
+
+    /// Returns the lower and upper bounds of the values output by the noise
+    /// function.
+    ///
+    /// The bounds are a hint: noise functions that don't know their range
+    /// report `(f64::NEG_INFINITY, f64::INFINITY)`, which is the default.
+    fn value_range(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
     fn abs(self) -> Abs<T, Self, DIM>
     where
         Self: Sized,
@@ -58,6 +91,18 @@ pub trait NoiseFn<T, const DIM: usize> {
         Blend::new(self, other, control)
     }
 
+    /// Blends this function with `other` using a constant weight `t`, where
+    /// -1.0 outputs this function, 1.0 outputs `other` and 0.0 outputs the
+    /// midpoint between them.
+    fn blend_constant<Other>(self, other: Other, t: f64) -> Blend<T, Self, Other, Constant, DIM>
+    where
+        T: Copy,
+        Self: Sized,
+        Other: NoiseFn<T, DIM>,
+    {
+        Blend::new(self, other, Constant::new((t + 1.0) * 0.5))
+    }
+
     fn cache(self) -> Cache<Self>
     where
         Self: Sized,
@@ -79,6 +124,21 @@ pub trait NoiseFn<T, const DIM: usize> {
         Clamp::new(self).set_bounds(lower_bound, upper_bound)
     }
 
+    /// Changes the contrast of the output around 0.0, keeping its sign and the
+    /// values -1.0, 0.0 and 1.0 in place. An `amount` of 0.0 is the identity,
+    /// positive amounts push values towards -1.0 and 1.0, and negative amounts
+    /// pull them towards 0.0.
+    ///
+    /// The output is `sign(x) * |x|^(2^-amount)`, built from an
+    /// [`Exponent`] with equal per-sign exponents.
+    fn contrast(self, amount: f64) -> Exponent<T, Self, DIM>
+    where
+        Self: Sized,
+    {
+        let exponent = (-amount).exp2();
+        Exponent::new(self).set_exponents(exponent, exponent)
+    }
+
     fn curve(self) -> Curve<T, Self, DIM>
     where
         Self: Sized,
@@ -131,6 +191,29 @@ pub trait NoiseFn<T, const DIM: usize> {
         Displace::new(self, x_displace, y_displace, z_displace, u_displace)
     }
 
+    /// Displaces all four coordinates by the output value of a single
+    /// function, scaled by the weight of each axis. With equal weights, this
+    /// is equivalent to passing copies of `displace` to
+    /// [`displace_xyzu`](Self::displace_xyzu), but samples it only once.
+    fn displace_uniform<Offset>(
+        self,
+        displace: Offset,
+        weights: [f64; 4],
+    ) -> TranslateByNoise<Self, Offset>
+    where
+        Self: NoiseFn<f64, 4> + Sized,
+        Offset: NoiseFn<f64, 4>,
+    {
+        TranslateByNoise::new(self, displace).set_weights(weights)
+    }
+
+    fn dither(self) -> Dither<T, Self, DIM>
+    where
+        Self: Sized,
+    {
+        Dither::new(self)
+    }
+
     fn exponent(self) -> Exponent<T, Self, DIM>
     where
         Self: Sized,
@@ -145,6 +228,55 @@ pub trait NoiseFn<T, const DIM: usize> {
         Exponent::new(self).set_exponent(exponent)
     }
 
+    /// Calls `function` with each input point and the output value, which is
+    /// passed on unchanged, for debugging the values flowing through a
+    /// function.
+    fn inspect<F>(self, function: F) -> Inspect<T, Self, F, DIM>
+    where
+        Self: Sized,
+        F: Fn([T; DIM], f64),
+    {
+        Inspect::new(self, function)
+    }
+
+    /// Lifts this function into a higher dimension, where it ignores the
+    /// coordinates beyond its own.
+    fn lift(self) -> Lift<Self, DIM>
+    where
+        Self: Sized,
+    {
+        Lift::new(self)
+    }
+
+    /// Animates this function along a time axis that loops every `period`,
+    /// by moving the time coordinate around a circle in the _z_ and _u_ axes.
+    fn looping_time(self, period: f64) -> LoopingTime<Self>
+    where
+        Self: Sized,
+    {
+        LoopingTime::new(self, period)
+    }
+
+    /// Applies `function` to the output value, for transformations that none
+    /// of the other modifiers provide.
+    fn map<F>(self, function: F) -> Map<T, Self, F, DIM>
+    where
+        Self: Sized,
+        F: Fn(f64) -> f64,
+    {
+        Map::new(self, function)
+    }
+
+    /// Applies `function` to the input value before sampling, for coordinate
+    /// transforms that none of the other transformers provide.
+    fn map_point<F>(self, function: F) -> MapPoint<Self, F>
+    where
+        Self: Sized,
+        F: Fn([T; DIM]) -> [T; DIM],
+    {
+        MapPoint::new(self, function)
+    }
+
     fn max<Other>(self, other: Other) -> Max<T, Self, Other, DIM>
     where
         Self: Sized,
@@ -216,6 +348,22 @@ pub trait NoiseFn<T, const DIM: usize> {
         Power::new(self, Constant::new(value))
     }
 
+    /// Wraps the input point into the range `[0, period)` along each axis,
+    /// so the output repeats. See [`Repeat`] for the seams this can cause.
+    fn repeat(self, period: [f64; DIM]) -> Repeat<Self, DIM>
+    where
+        Self: Sized,
+    {
+        Repeat::new(self, period)
+    }
+
+    fn ridge(self) -> Ridge<T, Self, DIM>
+    where
+        Self: Sized,
+    {
+        Ridge::new(self)
+    }
+
     fn rotate_point(self) -> RotatePoint<Self>
     where
         Self: Sized,
@@ -253,7 +401,7 @@ pub trait NoiseFn<T, const DIM: usize> {
         ScalePoint::new(self).set_all_scales(x, y, z, u)
     }
 
-    fn select<Other, Control>(self, other: Other, control: Control) -> Select<T, Self, Other, Control, DIM>
+    fn select<Other, Control>(self, other: Other, control: Control) -> Select<T, Self, Other, Control, (), DIM>
     where
         Self: Sized,
         Other: NoiseFn<T, DIM>,
@@ -262,6 +410,24 @@ pub trait NoiseFn<T, const DIM: usize> {
         Select::new(self, other, control)
     }
 
+    /// Samples a 2-dimensional slice of this 3-dimensional function at a
+    /// fixed _z_ coordinate.
+    fn slice_z(self, z: f64) -> Slice<Self>
+    where
+        Self: NoiseFn<f64, 3> + Sized,
+    {
+        Slice::new(self, z)
+    }
+
+    /// Smoothly limits the output to the range -`scale` to `scale` with
+    /// `scale * tanh(x / scale)`.
+    fn soft_clamp(self, scale: f64) -> SoftClamp<T, Self, DIM>
+    where
+        Self: Sized,
+    {
+        SoftClamp::new(self).set_scale(scale)
+    }
+
     fn terrace(self) -> Terrace<T, Self, DIM>
     where
         Self: Sized,
@@ -269,6 +435,18 @@ pub trait NoiseFn<T, const DIM: usize> {
         Terrace::new(self)
     }
 
+    fn translate_by_noise<Offset>(
+        self,
+        offset: Offset,
+        weights: impl TransformerArgs,
+    ) -> TranslateByNoise<Self, Offset>
+    where
+        Self: Sized,
+        Offset: NoiseFn<T, DIM>,
+    {
+        TranslateByNoise::new(self, offset).set_weights(weights)
+    }
+
     fn translate_point(self) -> TranslatePoint<Self>
     where
         Self: Sized,
@@ -293,7 +471,7 @@ pub trait NoiseFn<T, const DIM: usize> {
     }
 }
 
-impl<'a, T, M, const DIM: usize> NoiseFn<T, DIM> for &'a M
+impl<T, M, const DIM: usize> NoiseFn<T, DIM> for &M
 where
     M: NoiseFn<T, DIM> + ?Sized,
 {
@@ -301,6 +479,11 @@ where
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
         M::get(*self, point)
     }
+
+    #[inline]
+    fn value_range(&self) -> (f64, f64) {
+        M::value_range(*self)
+    }
 }
 
 impl<T, M, const DIM: usize> NoiseFn<T, DIM> for Box<M>
@@ -311,6 +494,90 @@ where
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
         M::get(self, point)
     }
+
+    #[inline]
+    fn value_range(&self) -> (f64, f64) {
+        M::value_range(self)
+    }
+}
+
+/// Object-safe counterpart of [`NoiseFn`] for pipelines that are built at
+/// runtime and need to be duplicated, such as the node graph of an editor.
+///
+/// It is implemented for every `'static` noise function that is [`Clone`], so
+/// any pipeline can be stored as a `Box<dyn CloneableNoiseFn<T, DIM>>`, which
+/// is itself a [`NoiseFn`] and can be cloned.
+#[cfg(feature = "dyn-clone")]
+pub trait CloneableNoiseFn<T, const DIM: usize>: dyn_clone::DynClone {
+    /// Returns the output value at the point. See [`NoiseFn::get`].
+    fn get_dyn(&self, point: [T; DIM]) -> f64;
+
+    /// Returns the bounds of the output value. See [`NoiseFn::value_range`].
+    fn dyn_value_range(&self) -> (f64, f64);
+
+    /// Returns a boxed copy of this noise function.
+    fn boxed_clone(&self) -> Box<dyn CloneableNoiseFn<T, DIM>>;
+}
+
+#[cfg(feature = "dyn-clone")]
+impl<T, N, const DIM: usize> CloneableNoiseFn<T, DIM> for N
+where
+    N: NoiseFn<T, DIM> + Clone + 'static,
+{
+    #[inline]
+    fn get_dyn(&self, point: [T; DIM]) -> f64 {
+        self.get(point)
+    }
+
+    #[inline]
+    fn dyn_value_range(&self) -> (f64, f64) {
+        self.value_range()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn CloneableNoiseFn<T, DIM>> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "dyn-clone")]
+impl<T, const DIM: usize> NoiseFn<T, DIM> for dyn CloneableNoiseFn<T, DIM> + '_ {
+    #[inline]
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        self.get_dyn(point.into())
+    }
+
+    #[inline]
+    fn value_range(&self) -> (f64, f64) {
+        self.dyn_value_range()
+    }
+}
+
+#[cfg(feature = "dyn-clone")]
+impl<T, const DIM: usize> Clone for Box<dyn CloneableNoiseFn<T, DIM> + '_> {
+    fn clone(&self) -> Self {
+        dyn_clone::clone_box(&**self)
+    }
+}
+
+/// Trait for noise functions that can output the gradient of their output
+/// value, with respect to the input point, along with the value itself.
+///
+/// The gradient is computed analytically, which is more accurate and usually
+/// cheaper than estimating it by finite differences.
+pub trait NoiseFnGradient<const DIM: usize>: NoiseFn<f64, DIM> {
+    /// Returns the output value at the point and its partial derivatives
+    /// along each axis.
+    fn get_with_gradient(&self, point: [f64; DIM]) -> (f64, [f64; DIM]);
+}
+
+impl<M, const DIM: usize> NoiseFnGradient<DIM> for &M
+where
+    M: NoiseFnGradient<DIM> + ?Sized,
+{
+    #[inline]
+    fn get_with_gradient(&self, point: [f64; DIM]) -> (f64, [f64; DIM]) {
+        M::get_with_gradient(*self, point)
+    }
 }
 
 /// Trait for functions that require a seed before generating their values
@@ -321,3 +588,131 @@ pub trait Seedable {
     /// Getter to retrieve the seed from the function
     fn seed(&self) -> u32;
 }
+
+/// Trait for functions that scale their input by a frequency
+pub trait Frequency {
+    /// Set the frequency for the function implementing the `Frequency` trait
+    fn set_frequency(self, frequency: f64) -> Self
+    where
+        Self: Sized;
+
+    /// Getter to retrieve the frequency from the function
+    fn frequency(&self) -> f64;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::boxed::Box;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_pipelines_are_send_sync() {
+        assert_send_sync::<Perlin>();
+        assert_send_sync::<Worley>();
+        assert_send_sync::<Fbm<Perlin>>();
+        assert_send_sync::<RidgedMulti<OpenSimplex>>();
+        assert_send_sync::<Turbulence<Worley, Perlin>>();
+        assert_send_sync::<
+            Select<
+                f64,
+                ScaleBias<f64, Fbm<Perlin>, 2>,
+                Add<f64, Worley, Constant, 2>,
+                Curve<f64, Billow<Simplex>, 2>,
+                Perlin,
+                2,
+            >,
+        >();
+        assert_send_sync::<Blend<f64, Perlin, Checkerboard, Cylinders, 3>>();
+        assert_send_sync::<Box<Perlin>>();
+    }
+
+    #[test]
+    fn test_cloned_pipeline_matches() {
+        let terrain = Fbm::<Perlin>::new(3)
+            .set_octaves(4)
+            .scale_bias()
+            .set_scale(0.5);
+        let cells = Worley::new(5).set_return_type(ReturnType::Distance);
+        let pipeline = MaxWithIndex::new(vec![
+            Lod::new(vec![Perlin::new(1), Perlin::new(2)]).set_active_levels(1),
+            Lod::new(vec![Perlin::new(7)]),
+        ]);
+        let pipeline = Turbulence::<_, Perlin>::new(
+            Select::new(terrain, cells, pipeline)
+                .set_falloff(0.1)
+                .set_transition_source(Perlin::new(11)),
+        )
+        .set_seed(9);
+
+        let cloned = pipeline.clone();
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 4.0, i as f64 * 0.53 + 1.0];
+            assert_eq!(pipeline.get(point), cloned.get(point));
+        }
+    }
+
+    #[test]
+    fn test_modifier_chain_matches_hand_written() {
+        let perlin = Perlin::new(4);
+        let chain = NoiseFn::<f64, 2>::abs(perlin)
+            .negate()
+            .scale_bias()
+            .set_scale(0.5)
+            .set_bias(0.25)
+            .add(Constant::new(0.125))
+            .multiply(Constant::new(2.0));
+
+        for i in 0..32 {
+            let point = [i as f64 * 0.37 - 2.1, i as f64 * 0.53 + 0.6];
+            let value = -perlin.get(point).abs();
+            let expected = (value.mul_add(0.5, 0.25) + 0.125) * 2.0;
+
+            assert_eq!(expected, chain.get(point));
+        }
+    }
+
+    #[cfg(feature = "dyn-clone")]
+    #[test]
+    fn test_boxed_pipeline_clone_matches() {
+        let pipeline: Box<dyn CloneableNoiseFn<f64, 2>> = Box::new(
+            Fbm::<Perlin>::new(3)
+                .add(Worley::new(5))
+                .scale_bias()
+                .set_scale(0.5),
+        );
+        let boxed_clone = pipeline.boxed_clone();
+        let cloned = pipeline.clone();
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 4.0, i as f64 * 0.53 + 1.0];
+            let value = pipeline.get(point);
+            assert_eq!(value, boxed_clone.get(point));
+            assert_eq!(value, cloned.get(point));
+        }
+    }
+
+    fn double_frequency<F: Frequency>(noise: F) -> F {
+        let frequency = noise.frequency();
+        noise.set_frequency(frequency * 2.0)
+    }
+
+    #[test]
+    fn test_frequency_set_generically() {
+        let perlin = double_frequency(Perlin::new(1));
+        let fbm = double_frequency(Fbm::<Perlin>::new(1).set_frequency(0.75));
+        let worley = double_frequency(Worley::new(1));
+        let cylinders = double_frequency(Cylinders::new().set_frequency(3.0));
+        let noises: [&dyn Frequency; 4] = [&perlin, &fbm, &worley, &cylinders];
+        assert_eq!([2.0, 1.5, 2.0, 6.0], noises.map(|noise| noise.frequency()));
+
+        // Setting a uniform frequency matches setting it on every axis.
+        let uniform = Simplex::new(2).set_frequency(2.5);
+        let per_axis = Simplex::new(2).set_frequencies([2.5, 2.5]);
+        for i in 0..32 {
+            let point = [i as f64 * 0.37 - 2.1, i as f64 * 0.53 + 0.6];
+            assert_eq!(per_axis.get(point), uniform.get(point));
+        }
+    }
+}
@@ -6,15 +6,18 @@ use core::cell::{Cell, RefCell};
 /// function.
 ///
 /// If the input coordinates passed to `Cache::get` are equal to the previous
-/// call, the function returns the cached result of the previous call to
-/// `Source::get`. Otherwise, `Source::get` is called with the new coordinates,
-/// overwriting the cache with the result, and returning the result to the
-/// caller.
+/// call, or within the tolerance set by `Cache::with_tolerance`, the function
+/// returns the cached result of the previous call to `Source::get`. Otherwise,
+/// `Source::get` is called with the new coordinates, overwriting the cache with
+/// the result, and returning the result to the caller.
 ///
 /// Caching a noise function is useful if it is used as a source function for
 /// multiple noise functions. If a source function is not cached, the source
 /// function will redundantly calculate the same output value once for each
 /// noise function in which it is included.
+///
+/// The cache is not synchronized, so `Cache` is not `Sync`. When sampling from
+/// multiple threads, give each thread its own clone.
 #[derive(Clone, Debug)]
 pub struct Cache<Source> {
     /// Outputs the value to be cached.
@@ -23,6 +26,8 @@ pub struct Cache<Source> {
     value: Cell<Option<f64>>,
 
     point: RefCell<Vec<f64>>,
+
+    tolerance: f64,
 }
 
 impl<Source> Cache<Source> {
@@ -31,8 +36,38 @@ impl<Source> Cache<Source> {
             source,
             value: Cell::new(None),
             point: RefCell::new(Vec::new()),
+            tolerance: 0.0,
         }
     }
+
+    /// Sets how far, per axis, the input coordinates may be from the cached
+    /// coordinates for the cached value to be returned. The default of 0.0
+    /// only matches equal coordinates.
+    ///
+    /// This lets jittered samples share a cached value, at the cost of
+    /// accuracy: a hit returns the value of the source function at the cached
+    /// coordinates rather than at the input coordinates, so the output can be
+    /// off by as much as the source function changes over `tolerance`. The
+    /// cached coordinates are only replaced on a miss, so the error doesn't
+    /// accumulate over a run of nearby samples.
+    pub fn with_tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    /// Returns the tolerance of the cached coordinates.
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Clears the cached value, so that the next call to `get` evaluates the
+    /// source function even if the input coordinates are unchanged.
+    ///
+    /// This is needed when the output of the source function can change
+    /// without the cache being rebuilt, such as when it reads interior-mutable
+    /// state.
+    pub fn reset(&self) {
+        self.value.set(None);
+    }
 }
 
 impl<Source, const DIM: usize> NoiseFn<f64, DIM> for Cache<Source>
@@ -42,7 +77,7 @@ where
     fn get(&self, point: impl Into<[f64; DIM]>) -> f64 {
         let point = point.into();
         match self.value.get() {
-            Some(value) if quick_eq(&*self.point.borrow(), &point) => value,
+            Some(value) if quick_eq(&self.point.borrow(), &point, self.tolerance) => value,
             Some(_) | None => {
                 let value = self.source.get(point);
                 self.value.set(Some(value));
@@ -57,8 +92,77 @@ where
     }
 }
 
-fn quick_eq(a: &[f64], b: &[f64]) -> bool {
+fn quick_eq(a: &[f64], b: &[f64], tolerance: f64) -> bool {
     assert_eq!(a.len(), b.len());
 
-    a.iter().eq(b)
+    a.iter()
+        .zip(b)
+        .all(|(a, b)| a == b || (a - b).abs() <= tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Source that outputs a configurable value and counts its evaluations.
+    struct Configurable<'a> {
+        value: &'a Cell<f64>,
+        count: &'a Cell<usize>,
+    }
+
+    impl NoiseFn<f64, 2> for Configurable<'_> {
+        fn get(&self, _point: impl Into<[f64; 2]>) -> f64 {
+            self.count.set(self.count.get() + 1);
+            self.value.get()
+        }
+    }
+
+    #[test]
+    fn test_reset_recomputes() {
+        let (value, count) = (Cell::new(0.25), Cell::new(0));
+        let cache = Cache::new(Configurable {
+            value: &value,
+            count: &count,
+        });
+        let point = [1.5, -0.5];
+
+        assert_eq!(0.25, cache.get(point));
+        assert_eq!(0.25, cache.get(point));
+        assert_eq!(1, count.get());
+
+        // The cached value is stale after the source changes.
+        value.set(0.75);
+        assert_eq!(0.25, cache.get(point));
+
+        cache.reset();
+        assert_eq!(0.75, cache.get(point));
+        assert_eq!(0.75, cache.get(point));
+        assert_eq!(2, count.get());
+    }
+
+    #[test]
+    fn test_tolerance_matches_nearby_points() {
+        let (value, count) = (Cell::new(0.25), Cell::new(0));
+        let cache = Cache::new(Configurable {
+            value: &value,
+            count: &count,
+        })
+        .with_tolerance(0.01);
+        assert_eq!(0.01, cache.tolerance());
+
+        assert_eq!(0.25, cache.get([1.5, -0.5]));
+        assert_eq!(1, count.get());
+
+        // Points within the tolerance on every axis hit the cache.
+        value.set(0.75);
+        assert_eq!(0.25, cache.get([1.505, -0.495]));
+        assert_eq!(0.25, cache.get([1.495, -0.509]));
+        assert_eq!(1, count.get());
+
+        // A point beyond the tolerance on any axis recomputes.
+        assert_eq!(0.75, cache.get([1.5, -0.52]));
+        assert_eq!(2, count.get());
+        assert_eq!(0.75, cache.get([1.52, -0.52]));
+        assert_eq!(3, count.get());
+    }
 }
@@ -1,7 +1,9 @@
-pub use self::{add::*, max::*, min::*, multiply::*, power::*};
+pub use self::{add::*, lod::*, max::*, min::*, multiply::*, power::*, product_tuple::*};
 
 mod add;
+mod lod;
 mod max;
 mod min;
 mod multiply;
 mod power;
+mod product_tuple;
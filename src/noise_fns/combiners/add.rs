@@ -38,6 +38,7 @@ where
     Source1: NoiseFn<T, DIM>,
     Source2: NoiseFn<T, DIM>,
 {
+    #[inline]
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
         let point = point.into();
         self.source1.get(point) + self.source2.get(point)
@@ -0,0 +1,122 @@
+use crate::noise_fns::NoiseFn;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Noise function that outputs the sum of the output values from a number of
+/// layered source functions, with a runtime-controllable level of detail.
+///
+/// The layers are summed in order, and only the first
+/// [`active_levels`](Self::active_levels) layers contribute. This is similar to
+/// fBm with the octaves stored separately, so that detail can be dropped for
+/// distant samples without rebuilding the noise function.
+#[derive(Clone, Debug)]
+pub struct Lod<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs the layers to sum, from coarsest to finest.
+    pub layers: Vec<Source>,
+
+    active_levels: usize,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Lod<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Creates a new `Lod` with every layer active.
+    pub fn new(layers: Vec<Source>) -> Self {
+        Self {
+            active_levels: layers.len(),
+            layers,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets the number of layers that contribute to the output. Values larger
+    /// than the number of layers activate every layer.
+    pub fn set_active_levels(self, active_levels: usize) -> Self {
+        Self {
+            active_levels,
+            ..self
+        }
+    }
+
+    pub fn active_levels(&self) -> usize {
+        self.active_levels
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Lod<T, Source, DIM>
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        let point = point.into();
+
+        self.layers
+            .iter()
+            .take(self.active_levels)
+            .map(|layer| layer.get(point))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin, ScaleBias, ScalePoint};
+
+    #[test]
+    fn test_active_levels() {
+        let lod = Lod::new(vec![
+            Constant::new(0.5),
+            Constant::new(0.25),
+            Constant::new(0.125),
+        ]);
+
+        assert_eq!(0.875, NoiseFn::<f64, 2>::get(&lod, [0.3, 0.7]));
+
+        let mut previous = f64::NEG_INFINITY;
+        for levels in 0..=4 {
+            let value = NoiseFn::<f64, 2>::get(&lod.clone().set_active_levels(levels), [0.3, 0.7]);
+            assert!(value >= previous);
+            previous = value;
+        }
+
+        let lod = lod.set_active_levels(0);
+        assert_eq!(0.0, NoiseFn::<f64, 2>::get(&lod, [0.3, 0.7]));
+    }
+
+    #[test]
+    fn test_active_levels_add_detail() {
+        let layers = (0..4)
+            .map(|octave| {
+                let scale = 2.0_f64.powi(octave);
+                ScaleBias::new(ScalePoint::new(Perlin::new(octave as u32)).set_scale(scale))
+                    .set_scale(1.0 / scale)
+            })
+            .collect();
+        let lod = Lod::new(layers);
+
+        // Each extra level adds small-scale variation, so the total variation
+        // between neighboring samples grows with the number of active levels.
+        let variation = |levels| {
+            let lod = lod.clone().set_active_levels(levels);
+            (0..256)
+                .map(|i| {
+                    let x = i as f64 * 0.05;
+                    (lod.get([x + 0.05, 0.3]) - lod.get([x, 0.3])).abs()
+                })
+                .sum::<f64>()
+        };
+
+        assert_eq!(0.0, variation(0));
+        for levels in 1..4 {
+            assert!(variation(levels + 1) > variation(levels));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::noise_fns::NoiseFn;
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 
 /// Noise function that outputs the smaller of the two output values from two source
@@ -43,3 +44,96 @@ where
         (self.source1.get(point)).min(self.source2.get(point))
     }
 }
+
+/// Noise function that outputs the smallest of the output values from a number
+/// of source functions, and can also report which source produced it.
+///
+/// This is useful for debugging compositions: rendering the index returned by
+/// [`get_with_index`](Self::get_with_index) as a color shows the region each
+/// source wins. Ties go to the source with the lowest index.
+#[derive(Clone, Debug)]
+pub struct MinWithIndex<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs the values to compare.
+    pub sources: Vec<Source>,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> MinWithIndex<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Creates a new `MinWithIndex`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sources` is empty.
+    pub fn new(sources: Vec<Source>) -> Self {
+        assert!(
+            !sources.is_empty(),
+            "MinWithIndex requires at least one source"
+        );
+
+        Self {
+            sources,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Source, const DIM: usize> MinWithIndex<T, Source, DIM>
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM>,
+{
+    /// Returns the smallest source value at `point` along with the index of the
+    /// source that produced it.
+    pub fn get_with_index(&self, point: impl Into<[T; DIM]>) -> (f64, usize) {
+        let point = point.into();
+
+        let mut best = (self.sources[0].get(point), 0);
+        for (index, source) in self.sources.iter().enumerate().skip(1) {
+            let value = source.get(point);
+            if value < best.0 {
+                best = (value, index);
+            }
+        }
+
+        best
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for MinWithIndex<T, Source, DIM>
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        self.get_with_index(point).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Perlin, Seedable};
+
+    #[test]
+    fn test_min_with_index() {
+        let sources = vec![Perlin::new(0), Perlin::new(1), Perlin::new(2)];
+        let min = MinWithIndex::new(sources.clone());
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 + 0.1, i as f64 * 0.21 - 2.3];
+            let (value, index) = min.get_with_index(point);
+
+            assert_eq!(sources[index].get(point), value);
+            assert_eq!(sources[index].seed(), index as u32);
+            assert!(sources.iter().all(|source| source.get(point) >= value));
+            assert_eq!(value, min.get(point));
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the product of the output values from a tuple
+/// of source functions.
+///
+/// This gives a single flat type for multiplying several functions together,
+/// such as a stack of masks, instead of nesting [`Multiply`](crate::Multiply)
+/// functions. Tuples of up to eight source functions are supported.
+///
+/// ```
+/// use noise::{Constant, NoiseFn, Perlin, ProductTuple};
+///
+/// let masked = ProductTuple((Perlin::new(1), Perlin::new(2), Constant::new(0.5)));
+/// let value = masked.get([0.5, 0.25]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ProductTuple<Sources>(pub Sources);
+
+macro_rules! impl_product_tuple {
+    ($($source:ident),+) => {
+        impl<T, $($source),+, const DIM: usize> NoiseFn<T, DIM> for ProductTuple<($($source,)+)>
+        where
+            T: Copy,
+            $($source: NoiseFn<T, DIM>,)+
+        {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+                let point = point.into();
+                let ($($source,)+) = &self.0;
+                1.0 $(* $source.get(point))+
+            }
+        }
+    };
+}
+
+impl_product_tuple!(A);
+impl_product_tuple!(A, B);
+impl_product_tuple!(A, B, C);
+impl_product_tuple!(A, B, C, D);
+impl_product_tuple!(A, B, C, D, E);
+impl_product_tuple!(A, B, C, D, E, F);
+impl_product_tuple!(A, B, C, D, E, F, G);
+impl_product_tuple!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Perlin, Simplex, Value};
+
+    #[test]
+    fn test_product_tuple_matches_multiply() {
+        let (perlin, simplex, value) = (Perlin::new(1), Simplex::new(2), Value::new(3));
+        let product = ProductTuple((perlin, simplex, value));
+        let chained = perlin.multiply(simplex).multiply(value);
+
+        for i in 0..32 {
+            let t = i as f64 * 0.29;
+            let point = [t - 1.5, 0.7 - t, t * 0.5];
+
+            assert_eq!(chained.get(point), product.get(point));
+        }
+
+        let single = ProductTuple((perlin,));
+        assert_eq!(
+            NoiseFn::<f64, 2>::get(&perlin, [0.3, 0.4]),
+            single.get([0.3, 0.4])
+        );
+    }
+}
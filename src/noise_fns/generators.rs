@@ -1,12 +1,13 @@
 pub use self::{
-    checkerboard::*, constant::*, cylinders::*, fractals::*, open_simplex::*, perlin::*,
-    perlin_surflet::*, simplex::*, super_simplex::*, value::*, worley::*,
+    checkerboard::*, constant::*, cylinders::*, fractals::*, from_fn::*, open_simplex::*,
+    perlin::*, perlin_surflet::*, simplex::*, super_simplex::*, value::*, worley::*,
 };
 
 mod checkerboard;
 mod constant;
 mod cylinders;
 mod fractals;
+mod from_fn;
 mod open_simplex;
 mod perlin;
 mod perlin_surflet;
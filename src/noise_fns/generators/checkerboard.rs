@@ -6,12 +6,35 @@ use crate::noise_fns::NoiseFn;
 /// blocks of alternating values. The values of these blocks alternate between
 /// -1.0 and 1.0.
 ///
+/// The layout of the blocks is controlled by the [`Pattern`], which defaults
+/// to a square grid.
+///
 /// This noise function is not very useful by itself, but it can be used for
 /// debugging purposes.
 #[derive(Clone, Copy, Debug)]
 pub struct Checkerboard {
     // Controls the size of the block in 2^(size).
     size: usize,
+
+    // Controls the layout of the blocks.
+    pattern: Pattern,
+}
+
+/// Layout of the blocks of a [`Checkerboard`].
+///
+/// The `Brick` and `Diagonal` patterns affect the _x_ and _y_ axes only; any
+/// further axes alternate as in the `Square` pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    /// Square blocks on an axis-aligned grid.
+    Square,
+
+    /// Blocks whose alternate rows along _y_ are offset by half a block along
+    /// _x_.
+    Brick,
+
+    /// Square blocks on a grid rotated by 45 degrees.
+    Diagonal,
 }
 
 impl Checkerboard {
@@ -19,39 +42,152 @@ impl Checkerboard {
 
     /// Controls the size of the block in 2^(size) units.
     pub fn new(size: usize) -> Self {
-        Self { size: 1 << size }
+        Self {
+            size: 1 << size,
+            pattern: Pattern::Square,
+        }
     }
 
     pub fn set_size(self, size: usize) -> Self {
-        Self { size: 1 << size }
+        Self {
+            size: 1 << size,
+            ..self
+        }
     }
 
     pub fn size(self) -> usize {
         self.size
     }
+
+    /// Sets the layout of the blocks.
+    pub fn set_pattern(self, pattern: Pattern) -> Self {
+        Self { pattern, ..self }
+    }
+
+    pub fn pattern(self) -> Pattern {
+        self.pattern
+    }
 }
 
 impl Default for Checkerboard {
     fn default() -> Self {
-        Self {
-            size: 1 << Checkerboard::DEFAULT_SIZE,
-        }
+        Self::new(Checkerboard::DEFAULT_SIZE)
     }
 }
 
 impl<const N: usize> NoiseFn<f64, N> for Checkerboard {
     fn get(&self, point: impl Into<[f64; N]>) -> f64 {
-        let result = point
-            .into()
-            .iter()
-            .map(|&a| a.floor() as isize)
-            .reduce(|a, b| (a & self.size as isize) ^ (b & self.size as isize))
-            .unwrap();
-
-        if result > 0 {
-            -1.0
+        let point = point.into();
+        let size = self.size as f64;
+        let cell = |a: f64| (a / size).floor() as isize;
+
+        // The value alternates with the parity of the sum of the block
+        // coordinates along every axis.
+        let parity = if N >= 2 && self.pattern != Pattern::Square {
+            let (x, y) = (point[0], point[1]);
+            let planar = match self.pattern {
+                Pattern::Brick => {
+                    let row = cell(y);
+                    let offset = if row & 1 == 0 { 0.0 } else { size * 0.5 };
+                    cell(x + offset) + row
+                }
+                _ => cell(x + y) + cell(x - y),
+            };
+
+            point[2..]
+                .iter()
+                .fold(planar, |parity, &a| parity + cell(a))
         } else {
+            point.iter().fold(0, |parity, &a| parity + cell(a))
+        };
+
+        if parity & 1 == 0 {
             1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(checkerboard: Checkerboard, x: f64, y: f64) -> f64 {
+        checkerboard.get([x, y])
+    }
+
+    #[test]
+    fn test_square_pattern() {
+        let checkerboard = Checkerboard::default();
+
+        assert_eq!(1.0, sample(checkerboard, 0.5, 0.5));
+        assert_eq!(-1.0, sample(checkerboard, 1.5, 0.5));
+        assert_eq!(-1.0, sample(checkerboard, 0.5, 1.5));
+        assert_eq!(1.0, sample(checkerboard, 1.5, 1.5));
+        assert_eq!(-1.0, sample(checkerboard, -0.5, 0.5));
+    }
+
+    #[test]
+    fn test_brick_pattern() {
+        let checkerboard = Checkerboard::new(1).set_pattern(Pattern::Brick);
+
+        // Even rows are aligned with the square grid.
+        assert_eq!(1.0, sample(checkerboard, 0.5, 0.5));
+        assert_eq!(1.0, sample(checkerboard, 1.5, 0.5));
+        assert_eq!(-1.0, sample(checkerboard, 2.5, 0.5));
+
+        // Odd rows are offset by half a block, so the block boundary moves
+        // from x = 2 to x = 1.
+        assert_eq!(-1.0, sample(checkerboard, 0.5, 2.5));
+        assert_eq!(1.0, sample(checkerboard, 1.5, 2.5));
+        assert_eq!(1.0, sample(checkerboard, 2.5, 2.5));
+        assert_eq!(-1.0, sample(checkerboard, 3.5, 2.5));
+    }
+
+    #[test]
+    fn test_diagonal_pattern() {
+        let checkerboard = Checkerboard::default().set_pattern(Pattern::Diagonal);
+
+        // Values only change when crossing the lines x + y = n or x - y = n,
+        // so the four triangles of the unit square alternate.
+        assert_eq!(1.0, sample(checkerboard, 0.5, 0.25));
+        assert_eq!(-1.0, sample(checkerboard, 0.75, 0.5));
+        assert_eq!(1.0, sample(checkerboard, 0.5, 0.75));
+        assert_eq!(-1.0, sample(checkerboard, 0.25, 0.5));
+        assert_eq!(1.0, sample(checkerboard, 1.5, 0.75));
+        assert_eq!(
+            sample(checkerboard, 0.4, 0.2),
+            sample(checkerboard, 1.4, 1.2)
+        );
+    }
+
+    fn check_square_pattern<const N: usize>(size: usize) {
+        let checkerboard = Checkerboard::new(size);
+        let block = checkerboard.size() as f64;
+
+        // Sample the center of every block in [-2, 2) along each axis.
+        for index in 0..4usize.pow(N as u32) {
+            let mut point = [0.0; N];
+            let mut sum = 0;
+            for (axis, coordinate) in point.iter_mut().enumerate() {
+                let cell = (index / 4usize.pow(axis as u32) % 4) as isize - 2;
+                *coordinate = (cell as f64 + 0.5) * block;
+                sum += cell;
+            }
+
+            let expected = if sum & 1 == 0 { 1.0 } else { -1.0 };
+            assert_eq!(expected, checkerboard.get(point), "{:?}", point);
+        }
+    }
+
+    #[test]
+    fn test_square_pattern_in_each_dimension() {
+        for &size in [0, 1, 2].iter() {
+            check_square_pattern::<1>(size);
+            check_square_pattern::<2>(size);
+            check_square_pattern::<3>(size);
+            check_square_pattern::<4>(size);
         }
     }
 }
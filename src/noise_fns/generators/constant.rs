@@ -17,10 +17,99 @@ impl Constant {
     pub fn new(value: f64) -> Self {
         Self { value }
     }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
 }
 
 impl<T: Copy, const N: usize> NoiseFn<T, N> for Constant {
+    #[inline]
     fn get(&self, _point: impl Into<[T; N]>) -> f64 {
         self.value
     }
+
+    fn value_range(&self) -> (f64, f64) {
+        (self.value, self.value)
+    }
+}
+
+/// Noise function that outputs 0.0 for all points.
+///
+/// This is the same as `Constant::new(0.0)`, but needs no value, which makes
+/// it a convenient identity for [`Add`](crate::Add) in generic pipelines.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Zero;
+
+impl<T: Copy, const N: usize> NoiseFn<T, N> for Zero {
+    #[inline]
+    fn get(&self, _point: impl Into<[T; N]>) -> f64 {
+        0.0
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+}
+
+/// Noise function that outputs 1.0 for all points.
+///
+/// This is the same as `Constant::new(1.0)`, but needs no value, which makes
+/// it a convenient identity for [`Multiply`](crate::Multiply) in generic
+/// pipelines.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct One;
+
+impl<T: Copy, const N: usize> NoiseFn<T, N> for One {
+    #[inline]
+    fn get(&self, _point: impl Into<[T; N]>) -> f64 {
+        1.0
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        (1.0, 1.0)
+    }
+}
+
+/// The unit type outputs 0.0 for all points, like [`Zero`].
+///
+/// This lets `()` stand in for a source function that has no effect, such as
+/// the displacement of the axes that [`Displace`](crate::Displace) leaves
+/// unchanged when built with `displace_xy` or `displace_xyz`.
+impl<T: Copy, const N: usize> NoiseFn<T, N> for () {
+    #[inline]
+    fn get(&self, _point: impl Into<[T; N]>) -> f64 {
+        0.0
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_and_range() {
+        let constant = Constant::new(0.375);
+
+        assert_eq!(0.375, constant.value());
+        assert_eq!((0.375, 0.375), NoiseFn::<f64, 2>::value_range(&constant));
+        assert_eq!((0.375, 0.375), NoiseFn::<f64, 3>::value_range(&&constant));
+    }
+
+    #[test]
+    fn test_unit_sources() {
+        assert_eq!(0.0, ().get([0.5]));
+        assert_eq!(0.0, ().get([0.5, -1.5]));
+        assert_eq!(0.0, ().get([0.5, -1.5, 2.0]));
+        assert_eq!(0.0, ().get([0.5, -1.5, 2.0, 1e9]));
+        assert_eq!((0.0, 0.0), NoiseFn::<f64, 3>::value_range(&()));
+
+        assert_eq!(0.0, Zero.get([0.5, -1.5, 2.0]));
+        assert_eq!(1.0, One.get([0.5, -1.5]));
+        assert_eq!((1.0, 1.0), NoiseFn::<f64, 4>::value_range(&One));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::noise_fns::{Frequency, NoiseFn};
 
 /// Noise function that outputs concentric cylinders.
 ///
@@ -31,6 +31,16 @@ impl Default for Cylinders {
     }
 }
 
+impl Frequency for Cylinders {
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency }
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+}
+
 impl<const N: usize> NoiseFn<f64, N> for Cylinders {
     fn get(&self, point: impl Into<[f64; N]>) -> f64 {
         let point = point.into();
@@ -7,17 +7,49 @@ mod fbm;
 mod hybridmulti;
 mod ridgedmulti;
 
-use crate::Seedable;
+use crate::{math::interpolate, Seedable};
 
 /// Trait for `MultiFractal` functions
+///
+/// ```rust
+/// use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
+///
+/// fn tune<F: MultiFractal>(fractal: F) -> F {
+///     fractal.set_octaves(3).set_frequency(2.0)
+/// }
+///
+/// let fbm = tune(Fbm::<Perlin>::new(0));
+/// assert_eq!(2.0, fbm.frequency());
+/// assert!(fbm.get([0.5, 0.25]).abs() <= 1.0);
+/// ```
 pub trait MultiFractal {
     fn set_octaves(self, octaves: usize) -> Self;
 
     fn set_frequency(self, frequency: f64) -> Self;
 
+    /// Sets the frequency multiplier between successive octaves. Values
+    /// around 2.0 are typical; lacunarities below 1.0, including negative
+    /// ones, are clamped to 1.0.
     fn set_lacunarity(self, lacunarity: f64) -> Self;
 
+    /// Sets the amplitude multiplier between successive octaves. Values
+    /// between 0.0 and 1.0 are typical. [`Fbm`] and [`Billow`] normalize their
+    /// output by the total amplitude, so they clamp the persistence to below
+    /// 1.0; the other fractals accept any persistence, but their output grows
+    /// without bound above 1.0.
     fn set_persistence(self, persistence: f64) -> Self;
+
+    /// Returns the number of octaves.
+    fn octaves(&self) -> usize;
+
+    /// Returns the frequency of the first octave.
+    fn frequency(&self) -> f64;
+
+    /// Returns the frequency multiplier between successive octaves.
+    fn lacunarity(&self) -> f64;
+
+    /// Returns the amplitude multiplier between successive octaves.
+    fn persistence(&self) -> f64;
 }
 
 fn build_sources<Source>(seed: u32, octaves: usize) -> Vec<Source>
@@ -27,7 +59,432 @@ where
     let mut sources = Vec::with_capacity(octaves);
     for x in 0..octaves {
         let source = Source::default();
-        sources.push(source.set_seed(seed + x as u32));
+        sources.push(source.set_seed(seed.wrapping_add(x as u32)));
     }
     sources
 }
+
+/// Returns the sine and cosine of the per-octave rotation, or `None` if the
+/// octaves are not rotated.
+fn octave_rotation(angle: f64) -> Option<(f64, f64)> {
+    if angle == 0.0 {
+        None
+    } else {
+        Some((angle.sin(), angle.cos()))
+    }
+}
+
+/// Rotates a point in the xy plane by the per-octave rotation.
+#[inline]
+fn rotate_octave(x: &mut f64, y: &mut f64, rotation: Option<(f64, f64)>) {
+    if let Some((sin, cos)) = rotation {
+        let (x0, y0) = (*x, *y);
+        *x = x0 * cos - y0 * sin;
+        *y = x0 * sin + y0 * cos;
+    }
+}
+
+/// Advances the accumulated sine and cosine of the octave rotation by one
+/// octave.
+#[inline]
+fn advance_rotation(sin: &mut f64, cos: &mut f64, rotation: Option<(f64, f64)>) {
+    if let Some((step_sin, step_cos)) = rotation {
+        let next_sin = *sin * step_cos + *cos * step_sin;
+        *cos = *cos * step_cos - *sin * step_sin;
+        *sin = next_sin;
+    }
+}
+
+/// Scales the gradient of an octave's source by the frequency of the octave,
+/// and rotates it back by the accumulated rotation into the frame of the input
+/// point.
+#[inline]
+fn octave_gradient<const DIM: usize>(
+    mut gradient: [f64; DIM],
+    scale: f64,
+    sin: f64,
+    cos: f64,
+) -> [f64; DIM] {
+    for derivative in gradient.iter_mut() {
+        *derivative *= scale;
+    }
+    if let [dx, dy, ..] = &mut gradient[..] {
+        let (dx0, dy0) = (*dx, *dy);
+        *dx = dx0 * cos + dy0 * sin;
+        *dy = dy0 * cos - dx0 * sin;
+    }
+    gradient
+}
+
+/// Returns the weight of the octave following one with the given value for a
+/// weighted strength, along with the gradient of the weight.
+#[inline]
+fn detail_weight<const DIM: usize>(
+    value: f64,
+    d_value: [f64; DIM],
+    weighted_strength: f64,
+) -> (f64, [f64; DIM]) {
+    let weight = interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, weighted_strength);
+    let slope = if value + 1.0 < 2.0 {
+        weighted_strength * 0.5
+    } else {
+        0.0
+    };
+
+    let mut d_weight = d_value;
+    for derivative in d_weight.iter_mut() {
+        *derivative *= slope;
+    }
+    (weight, d_weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{utils::FiniteDiff, NoiseFn, NoiseFnGradient, Perlin, Simplex};
+
+    fn assert_round_trip<F>(fractal: F)
+    where
+        F: MultiFractal + Seedable,
+    {
+        let fractal = fractal
+            .set_seed(42)
+            .set_octaves(5)
+            .set_frequency(1.75)
+            .set_lacunarity(2.25)
+            .set_persistence(0.4);
+
+        assert_eq!(42, fractal.seed());
+        assert_eq!(5, fractal.octaves());
+        assert_eq!(1.75, fractal.frequency());
+        assert_eq!(2.25, fractal.lacunarity());
+        assert_eq!(0.4, fractal.persistence());
+    }
+
+    #[test]
+    fn test_getters_round_trip() {
+        assert_round_trip(Fbm::<Perlin>::default());
+        assert_round_trip(Billow::<Perlin>::default());
+        assert_round_trip(BasicMulti::<Perlin>::default());
+        assert_round_trip(HybridMulti::<Perlin>::default());
+        assert_round_trip(RidgedMulti::<Perlin>::default());
+
+        let ridged = RidgedMulti::<Perlin>::default().set_attenuation(3.5);
+        assert_eq!(3.5, ridged.attenuation());
+    }
+
+    /// Asserts that an integral fractional octave count matches
+    /// `set_octaves`, and that half an octave lands halfway between the octave
+    /// counts on either side.
+    fn assert_fractional_octaves<F, B>(build: B)
+    where
+        F: MultiFractal + NoiseFn<f64, 2>,
+        B: Fn(f64) -> F,
+    {
+        let (three, four) = (build(3.0).set_octaves(3), build(3.0).set_octaves(4));
+        let (whole, half) = (build(3.0), build(3.5));
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.173 - 3.1, i as f64 * 0.291 + 0.4];
+            let (low, high) = (three.get(point), four.get(point));
+
+            assert_eq!(low, whole.get(point));
+
+            let value = half.get(point);
+            let midpoint = (low + high) * 0.5;
+            assert!(
+                (value - midpoint).abs() < 1e-12,
+                "{} != {}",
+                value,
+                midpoint
+            );
+        }
+    }
+
+    #[test]
+    fn test_multifractal_fractional_octaves() {
+        assert_fractional_octaves(|octaves| {
+            BasicMulti::<Perlin>::new(7).set_fractional_octaves(octaves)
+        });
+        assert_fractional_octaves(|octaves| {
+            HybridMulti::<Perlin>::new(7).set_fractional_octaves(octaves)
+        });
+        assert_fractional_octaves(|octaves| {
+            RidgedMulti::<Perlin>::new(7).set_fractional_octaves(octaves)
+        });
+    }
+
+    /// Asserts that a weighted strength of 0.0 leaves the output unchanged,
+    /// and that a positive strength smooths the output where the first octave
+    /// is low.
+    fn assert_weighted_strength<F>(plain: &F, unweighted: &F, weighted: &F, first_octave: &F)
+    where
+        F: NoiseFn<f64, 2>,
+    {
+        let roughness =
+            |noise: &F, [x, y]: [f64; 2]| (noise.get([x, y]) - noise.get([x + 0.01, y])).abs();
+
+        let (mut plain_roughness, mut weighted_roughness) = (0.0, 0.0);
+        for i in 0..256 {
+            let point = [i as f64 * 0.173 - 3.1, i as f64 * 0.291 + 0.4];
+            assert_eq!(plain.get(point), unweighted.get(point));
+
+            if first_octave.get(point) < -0.2 {
+                plain_roughness += roughness(plain, point);
+                weighted_roughness += roughness(weighted, point);
+            }
+        }
+
+        assert!(
+            weighted_roughness < plain_roughness,
+            "{} >= {}",
+            weighted_roughness,
+            plain_roughness
+        );
+    }
+
+    #[test]
+    fn test_multifractal_weighted_strength() {
+        let basic = BasicMulti::<Perlin>::new(7);
+        assert_weighted_strength(
+            &basic,
+            &basic.clone().set_weighted_strength(0.0),
+            &basic.clone().set_weighted_strength(1.0),
+            &basic.clone().set_octaves(1),
+        );
+
+        let hybrid = HybridMulti::<Perlin>::new(7);
+        assert_weighted_strength(
+            &hybrid,
+            &hybrid.clone().set_weighted_strength(0.0),
+            &hybrid.clone().set_weighted_strength(1.0),
+            &hybrid.clone().set_octaves(1),
+        );
+
+        let ridged = RidgedMulti::<Perlin>::new(7);
+        assert_weighted_strength(
+            &ridged,
+            &ridged.clone().set_weighted_strength(0.0),
+            &ridged.clone().set_weighted_strength(1.0),
+            &ridged.clone().set_octaves(1),
+        );
+    }
+
+    /// Asserts that the analytic gradient matches a fine central difference
+    /// estimate, and that the value matches `get`.
+    fn assert_gradient_matches<N>(noise: &N)
+    where
+        N: NoiseFnGradient<2> + NoiseFnGradient<3>,
+    {
+        let estimate_2d = FiniteDiff::<_, 2>::new(noise).set_epsilon(1e-6);
+        let estimate_3d = FiniteDiff::<_, 3>::new(noise).set_epsilon(1e-6);
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.173 - 3.1, i as f64 * 0.291 + 0.4];
+            let (value, gradient) = NoiseFnGradient::<2>::get_with_gradient(noise, point);
+            assert_eq!(NoiseFn::<f64, 2>::get(noise, point), value);
+            for (analytic, estimate) in gradient.iter().zip(estimate_2d.gradient(point).iter()) {
+                assert!(
+                    (analytic - estimate).abs() < 1e-4,
+                    "{} != {}",
+                    analytic,
+                    estimate
+                );
+            }
+
+            let point = [point[0], point[1], i as f64 * 0.117 - 1.9];
+            let (value, gradient) = NoiseFnGradient::<3>::get_with_gradient(noise, point);
+            assert_eq!(NoiseFn::<f64, 3>::get(noise, point), value);
+            for (analytic, estimate) in gradient.iter().zip(estimate_3d.gradient(point).iter()) {
+                assert!(
+                    (analytic - estimate).abs() < 1e-4,
+                    "{} != {}",
+                    analytic,
+                    estimate
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_gradient_matches_finite_differences() {
+        assert_gradient_matches(&Simplex::new(3).set_frequencies([2.0, 0.5, 1.5]));
+
+        let fbm = Fbm::<Simplex>::new(3)
+            .set_frequency(1.3)
+            .set_lacunarity(2.1);
+        assert_gradient_matches(&fbm);
+        assert_gradient_matches(&fbm.clone().set_fractional_octaves(3.4));
+        assert_gradient_matches(&fbm.clone().set_octave_rotation(0.6));
+        assert_gradient_matches(&fbm.set_weighted_strength(0.7));
+
+        let billow = Billow::<Simplex>::new(3)
+            .set_frequency(1.3)
+            .set_lacunarity(2.1);
+        assert_gradient_matches(&billow);
+        assert_gradient_matches(&billow.clone().set_fractional_octaves(3.4));
+        assert_gradient_matches(&billow.clone().set_octave_rotation(-1.1));
+        assert_gradient_matches(&billow.set_weighted_strength(0.7));
+
+        let basic = BasicMulti::<Simplex>::new(3)
+            .set_frequency(1.3)
+            .set_lacunarity(2.1);
+        assert_gradient_matches(&basic);
+        assert_gradient_matches(&basic.clone().set_fractional_octaves(3.4));
+        assert_gradient_matches(&basic.clone().set_octave_rotation(0.6));
+        assert_gradient_matches(&basic.set_weighted_strength(0.7));
+
+        let hybrid = HybridMulti::<Simplex>::new(3)
+            .set_frequency(1.3)
+            .set_lacunarity(2.1);
+        assert_gradient_matches(&hybrid);
+        assert_gradient_matches(&hybrid.clone().set_fractional_octaves(3.4));
+        assert_gradient_matches(&hybrid.clone().set_octave_rotation(-1.1));
+        assert_gradient_matches(&hybrid.set_weighted_strength(0.7));
+
+        let ridged = RidgedMulti::<Simplex>::new(3)
+            .set_frequency(1.3)
+            .set_lacunarity(2.1);
+        assert_gradient_matches(&ridged);
+        assert_gradient_matches(&ridged.clone().set_fractional_octaves(3.4));
+        assert_gradient_matches(&ridged.clone().set_octave_rotation(0.6));
+        assert_gradient_matches(&ridged.set_weighted_strength(0.7));
+    }
+
+    /// Asserts that a zero rotation leaves the output unchanged, and that a
+    /// nonzero rotation changes it while keeping it bounded.
+    fn assert_octave_rotation<F>(fractal: &F, unrotated: &F, rotated: &F)
+    where
+        F: NoiseFn<f64, 2> + NoiseFn<f64, 3>,
+    {
+        let mut changed = false;
+        for i in 0..64 {
+            let point = [i as f64 * 0.173 - 3.1, i as f64 * 0.291 + 0.4];
+            let value = NoiseFn::<f64, 2>::get(fractal, point);
+            assert_eq!(value, NoiseFn::<f64, 2>::get(unrotated, point));
+            let rotated_value = NoiseFn::<f64, 2>::get(rotated, point);
+            assert!(rotated_value.abs() <= 2.0, "{} out of range", rotated_value);
+            changed |= value != rotated_value;
+
+            let point = [point[0], point[1], i as f64 * 0.117 - 1.9];
+            let value = NoiseFn::<f64, 3>::get(fractal, point);
+            assert_eq!(value, NoiseFn::<f64, 3>::get(unrotated, point));
+            let rotated_value = NoiseFn::<f64, 3>::get(rotated, point);
+            assert!(rotated_value.abs() <= 2.0, "{} out of range", rotated_value);
+            changed |= value != rotated_value;
+        }
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_octave_rotation() {
+        let fbm = Fbm::<Perlin>::new(7);
+        assert_eq!(0.0, fbm.octave_rotation());
+        assert_octave_rotation(
+            &fbm,
+            &fbm.clone().set_octave_rotation(0.0),
+            &fbm.clone().set_octave_rotation(0.5),
+        );
+
+        let billow = Billow::<Perlin>::new(7);
+        assert_eq!(0.0, billow.octave_rotation());
+        assert_octave_rotation(
+            &billow,
+            &billow.clone().set_octave_rotation(0.0),
+            &billow.clone().set_octave_rotation(0.5),
+        );
+
+        let basicmulti = BasicMulti::<Perlin>::new(7);
+        assert_eq!(0.0, basicmulti.octave_rotation());
+        assert_octave_rotation(
+            &basicmulti,
+            &basicmulti.clone().set_octave_rotation(0.0),
+            &basicmulti.clone().set_octave_rotation(0.5),
+        );
+
+        let hybridmulti = HybridMulti::<Perlin>::new(7);
+        assert_eq!(0.0, hybridmulti.octave_rotation());
+        assert_octave_rotation(
+            &hybridmulti,
+            &hybridmulti.clone().set_octave_rotation(0.0),
+            &hybridmulti.clone().set_octave_rotation(0.5),
+        );
+
+        let ridgedmulti = RidgedMulti::<Perlin>::new(7);
+        assert_eq!(0.0, ridgedmulti.octave_rotation());
+        assert_octave_rotation(
+            &ridgedmulti,
+            &ridgedmulti.clone().set_octave_rotation(0.0),
+            &ridgedmulti.clone().set_octave_rotation(0.5),
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_parameters_are_clamped() {
+        let fbm = Fbm::<Perlin>::new(3)
+            .set_persistence(1.5)
+            .set_lacunarity(-2.0);
+        assert_eq!(Fbm::<Perlin>::MAX_PERSISTENCE, fbm.persistence());
+        assert_eq!(Fbm::<Perlin>::MIN_LACUNARITY, fbm.lacunarity());
+
+        let billow = Billow::<Perlin>::new(3).set_persistence(1.0);
+        assert_eq!(Billow::<Perlin>::MAX_PERSISTENCE, billow.persistence());
+        assert_eq!(0.0, billow.set_persistence(-0.5).persistence());
+
+        assert_eq!(
+            1.0,
+            BasicMulti::<Perlin>::new(3)
+                .set_lacunarity(0.0)
+                .lacunarity()
+        );
+        assert_eq!(
+            1.0,
+            HybridMulti::<Perlin>::new(3)
+                .set_lacunarity(-1.0)
+                .lacunarity()
+        );
+        assert_eq!(
+            1.0,
+            RidgedMulti::<Perlin>::new(3)
+                .set_lacunarity(-3.0)
+                .lacunarity()
+        );
+
+        // Persistences of 1.0 and above would otherwise divide by zero or flip
+        // the sign of the output.
+        for &persistence in [1.0, 1.5, 4.0].iter() {
+            let fbm = Fbm::<Perlin>::new(3).set_persistence(persistence);
+            let billow = Billow::<Perlin>::new(3).set_persistence(persistence);
+            for i in 0..64 {
+                let point = [i as f64 * 0.173 - 3.1, i as f64 * 0.291 + 0.4];
+                assert!(fbm.get(point).is_finite());
+                assert!(billow.get(point).is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn test_in_range_parameters_are_kept() {
+        for &(persistence, lacunarity) in [(0.0, 1.0), (0.5, 2.0), (0.9, 3.5)].iter() {
+            let fbm = Fbm::<Perlin>::new(3)
+                .set_persistence(persistence)
+                .set_lacunarity(lacunarity);
+            assert_eq!(persistence, fbm.persistence());
+            assert_eq!(lacunarity, fbm.lacunarity());
+
+            let billow = Billow::<Perlin>::new(3)
+                .set_persistence(persistence)
+                .set_lacunarity(lacunarity);
+            assert_eq!(persistence, billow.persistence());
+            assert_eq!(lacunarity, billow.lacunarity());
+        }
+
+        // The other fractals keep persistences above 1.0.
+        assert_eq!(
+            1.5,
+            RidgedMulti::<Perlin>::new(3)
+                .set_persistence(1.5)
+                .persistence()
+        );
+    }
+}
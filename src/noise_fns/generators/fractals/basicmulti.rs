@@ -1,6 +1,6 @@
 use crate::{
-    math::vectors::*,
-    noise_fns::{MultiFractal, NoiseFn, Seedable},
+    math::{interpolate, vectors::*},
+    noise_fns::{Frequency, MultiFractal, NoiseFn, NoiseFnGradient, Seedable},
 };
 use alloc::vec::Vec;
 
@@ -46,6 +46,9 @@ pub struct BasicMulti<T> {
 
     seed: u32,
     sources: Vec<T>,
+    octave_rotation: f64,
+    last_octave_weight: f64,
+    weighted_strength: f64,
 }
 
 impl<T> BasicMulti<T>
@@ -58,6 +61,7 @@ where
     pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 0.5;
     pub const MAX_OCTAVES: usize = 32;
+    pub const MIN_LACUNARITY: f64 = 1.0;
 
     pub fn new(seed: u32) -> Self {
         Self {
@@ -67,12 +71,69 @@ where
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
             sources: super::build_sources(seed, Self::DEFAULT_OCTAVES),
+            octave_rotation: 0.0,
+            last_octave_weight: 1.0,
+            weighted_strength: 0.0,
         }
     }
 
     pub fn set_sources(self, sources: Vec<T>) -> Self {
         Self { sources, ..self }
     }
+
+    /// Sets the angle in radians by which the domain is rotated in the xy
+    /// plane between successive octaves.
+    ///
+    /// The rotation accumulates, so octave `n` is rotated by `n * radians`.
+    /// This keeps the lattices of the octaves from lining up, which reduces
+    /// the axis-aligned artifacts of stacking them. The default of 0.0 leaves
+    /// the octaves unrotated.
+    pub fn set_octave_rotation(self, octave_rotation: f64) -> Self {
+        Self {
+            octave_rotation,
+            ..self
+        }
+    }
+
+    /// Returns the angle in radians by which successive octaves are rotated.
+    pub fn octave_rotation(&self) -> f64 {
+        self.octave_rotation
+    }
+
+    /// Sets how strongly each octave is weighted by the value of the previous
+    /// octave, on top of the weighting by the running value that this
+    /// fractal already applies.
+    ///
+    /// With a strength above 0.0, octaves following a low value are
+    /// attenuated, which suppresses detail in low areas the way erosion
+    /// smooths valleys. At 1.0, an octave following the minimum value of -1.0
+    /// is removed entirely. The default strength of 0.0 disables the
+    /// weighting.
+    pub fn set_weighted_strength(self, weighted_strength: f64) -> Self {
+        Self {
+            weighted_strength,
+            ..self
+        }
+    }
+
+    /// Sets a possibly fractional number of octaves.
+    ///
+    /// The noise is generated with `octaves` rounded up, and the output is
+    /// blended between the whole octave counts on either side by the
+    /// fractional part of `octaves`. This allows the amount of detail to be
+    /// animated without popping. An integral `octaves` behaves exactly like
+    /// [`set_octaves`].
+    ///
+    /// [`set_octaves`]: MultiFractal::set_octaves
+    pub fn set_fractional_octaves(self, octaves: f64) -> Self {
+        let whole = octaves.ceil().max(1.0).min(Self::MAX_OCTAVES as f64);
+        let fraction = 1.0 - (whole - octaves).clamp(0.0, 1.0);
+
+        Self {
+            last_octave_weight: fraction,
+            ..self.set_octaves(whole as usize)
+        }
+    }
 }
 
 impl<T> Default for BasicMulti<T>
@@ -84,29 +145,65 @@ where
     }
 }
 
+impl<T> BasicMulti<T> {
+    /// Sets the frequency of the first octave.
+    ///
+    /// The same method is provided by [`MultiFractal`] and [`Frequency`];
+    /// this inherent one keeps calls unambiguous when both are in scope.
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    /// Returns the frequency of the first octave.
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+}
+
+impl<T> Frequency for BasicMulti<T> {
+    fn set_frequency(self, frequency: f64) -> Self {
+        BasicMulti::set_frequency(self, frequency)
+    }
+
+    fn frequency(&self) -> f64 {
+        BasicMulti::frequency(self)
+    }
+}
+
 impl<T> MultiFractal for BasicMulti<T>
 where
     T: Default + Seedable,
 {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
-            return self;
+            return Self {
+                last_octave_weight: 1.0,
+                ..self
+            };
         }
 
         octaves = octaves.clamp(1, Self::MAX_OCTAVES);
         Self {
             octaves,
+            last_octave_weight: 1.0,
             sources: super::build_sources(self.seed, octaves),
             ..self
         }
     }
 
     fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency, ..self }
+        BasicMulti::set_frequency(self, frequency)
     }
 
+    /// Sets the lacunarity, clamped to at least [`MIN_LACUNARITY`] so that
+    /// the frequency never decreases between octaves.
+    ///
+    /// [`MIN_LACUNARITY`]: Self::MIN_LACUNARITY
     fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
+        Self {
+            lacunarity: lacunarity.max(Self::MIN_LACUNARITY),
+            ..self
+        }
     }
 
     fn set_persistence(self, persistence: f64) -> Self {
@@ -115,6 +212,22 @@ where
             ..self
         }
     }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        BasicMulti::frequency(self)
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
 }
 
 impl<T> Seedable for BasicMulti<T>
@@ -138,6 +251,84 @@ where
     }
 }
 
+impl<T> BasicMulti<T> {
+    /// Scales the result to the [-1,1] range, blending out the last octave by
+    /// its fractional weight.
+    fn scale_result(&self, result: f64, last_signal: f64) -> f64 {
+        if self.last_octave_weight >= 1.0 {
+            return result * 0.5;
+        }
+
+        (result - last_signal * (1.0 - self.last_octave_weight)) * 0.5
+    }
+
+    /// Sums the octaves along with their gradients, each of which is the
+    /// gradient of its source scaled by the frequency of the octave and rotated
+    /// back by its rotation.
+    fn fractal_gradient<const DIM: usize>(&self, mut point: [f64; DIM]) -> (f64, [f64; DIM])
+    where
+        T: NoiseFnGradient<DIM>,
+    {
+        let mut scale = self.frequency;
+        for coordinate in point.iter_mut() {
+            *coordinate *= scale;
+        }
+        let rotation = super::octave_rotation(self.octave_rotation);
+        let (mut sin, mut cos) = (0.0, 1.0);
+
+        let (mut result, d_value) = self.sources[0].get_with_gradient(point);
+        let mut d_result = super::octave_gradient(d_value, scale, sin, cos);
+        let (mut last_signal, mut d_last_signal) = (result, d_result);
+        let (mut detail_weight, mut d_detail_weight) =
+            super::detail_weight(result, d_result, self.weighted_strength);
+
+        for x in 1..self.octaves {
+            for coordinate in point.iter_mut() {
+                *coordinate *= self.lacunarity;
+            }
+            if let [x, y, ..] = &mut point[..] {
+                super::rotate_octave(x, y, rotation);
+            }
+            super::advance_rotation(&mut sin, &mut cos, rotation);
+            scale *= self.lacunarity;
+
+            let (value, d_value) = self.sources[x].get_with_gradient(point);
+            let d_value = super::octave_gradient(d_value, scale, sin, cos);
+
+            // The signal is the value scaled by its amplitude and weight, and
+            // by the running result.
+            let amplitude = self.persistence.powi(x as i32);
+            let signal = value * (amplitude * detail_weight) * result;
+            let mut d_signal = [0.0; DIM];
+            for (i, derivative) in d_signal.iter_mut().enumerate() {
+                let d_weighted = d_value[i] * detail_weight + value * d_detail_weight[i];
+                *derivative =
+                    amplitude * (d_weighted * result + value * detail_weight * d_result[i]);
+            }
+
+            let (weight, d_weight) = super::detail_weight(value, d_value, self.weighted_strength);
+            detail_weight = weight;
+            d_detail_weight = d_weight;
+
+            result += signal;
+            last_signal = signal;
+            for (i, &derivative) in d_signal.iter().enumerate() {
+                d_result[i] += derivative;
+                d_last_signal[i] = derivative;
+            }
+        }
+
+        // Scaling the result is linear, so it scales the gradient the same
+        // way.
+        let mut gradient = [0.0; DIM];
+        for (i, derivative) in gradient.iter_mut().enumerate() {
+            *derivative = self.scale_result(d_result[i], d_last_signal[i]);
+        }
+
+        (self.scale_result(result, last_signal), gradient)
+    }
+}
+
 /// 2-dimensional `BasicMulti` noise
 impl<T> NoiseFn<f64, 2> for BasicMulti<T>
 where
@@ -148,28 +339,38 @@ where
 
         // First unscaled octave of function; later octaves are scaled.
         point *= self.frequency;
+        let rotation = super::octave_rotation(self.octave_rotation);
         let mut result = self.sources[0].get(point.into_array());
+        let mut last_signal = result;
+        let value = result;
+        let mut detail_weight =
+            interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
             // Raise the spatial frequency.
             point *= self.lacunarity;
+            super::rotate_octave(&mut point.x, &mut point.y, rotation);
 
             // Get noise value.
             let mut signal = self.sources[x].get(point.into_array());
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and weight
+            // the next octave by the value of this one.
+            let value = signal;
+            signal *= self.persistence.powi(x as i32) * detail_weight;
+            detail_weight =
+                interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
 
             // Add signal to result.
             result += signal;
+            last_signal = signal;
         }
 
-        // Scale the result to the [-1,1] range.
-        result * 0.5
+        self.scale_result(result, last_signal)
     }
 }
 
@@ -183,28 +384,38 @@ where
 
         // First unscaled octave of function; later octaves are scaled.
         point *= self.frequency;
+        let rotation = super::octave_rotation(self.octave_rotation);
         let mut result = self.sources[0].get(point.into_array());
+        let mut last_signal = result;
+        let value = result;
+        let mut detail_weight =
+            interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
             // Raise the spatial frequency.
             point *= self.lacunarity;
+            super::rotate_octave(&mut point.x, &mut point.y, rotation);
 
             // Get noise value.
             let mut signal = self.sources[x].get(point.into_array());
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and weight
+            // the next octave by the value of this one.
+            let value = signal;
+            signal *= self.persistence.powi(x as i32) * detail_weight;
+            detail_weight =
+                interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
 
             // Add signal to result.
             result += signal;
+            last_signal = signal;
         }
 
-        // Scale the result to the [-1,1] range.
-        result * 0.5
+        self.scale_result(result, last_signal)
     }
 }
 
@@ -218,27 +429,64 @@ where
 
         // First unscaled octave of function; later octaves are scaled.
         point *= self.frequency;
+        let rotation = super::octave_rotation(self.octave_rotation);
         let mut result = self.sources[0].get(point.into_array());
+        let mut last_signal = result;
+        let value = result;
+        let mut detail_weight =
+            interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
             // Raise the spatial frequency.
             point *= self.lacunarity;
+            super::rotate_octave(&mut point.x, &mut point.y, rotation);
 
             // Get noise value.
             let mut signal = self.sources[x].get(point.into_array());
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and weight
+            // the next octave by the value of this one.
+            let value = signal;
+            signal *= self.persistence.powi(x as i32) * detail_weight;
+            detail_weight =
+                interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
 
             // Add signal to result.
             result += signal;
+            last_signal = signal;
         }
 
-        // Scale the result to the [-1,1] range.
-        result * 0.5
+        self.scale_result(result, last_signal)
+    }
+}
+
+impl<T> NoiseFnGradient<2> for BasicMulti<T>
+where
+    T: NoiseFnGradient<2>,
+{
+    fn get_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        self.fractal_gradient(point)
+    }
+}
+
+impl<T> NoiseFnGradient<3> for BasicMulti<T>
+where
+    T: NoiseFnGradient<3>,
+{
+    fn get_with_gradient(&self, point: [f64; 3]) -> (f64, [f64; 3]) {
+        self.fractal_gradient(point)
+    }
+}
+
+impl<T> NoiseFnGradient<4> for BasicMulti<T>
+where
+    T: NoiseFnGradient<4>,
+{
+    fn get_with_gradient(&self, point: [f64; 4]) -> (f64, [f64; 4]) {
+        self.fractal_gradient(point)
     }
 }
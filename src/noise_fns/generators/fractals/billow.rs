@@ -1,6 +1,6 @@
 use crate::{
-    math::{scale_shift, vectors::*},
-    noise_fns::{MultiFractal, NoiseFn, Seedable},
+    math::{interpolate, scale_shift, vectors::*},
+    noise_fns::{Frequency, MultiFractal, NoiseFn, NoiseFnGradient, Seedable},
 };
 use alloc::vec::Vec;
 
@@ -43,7 +43,10 @@ pub struct Billow<T> {
 
     seed: u32,
     sources: Vec<T>,
+    octave_rotation: f64,
     scale_factor: f64,
+    last_octave_weight: f64,
+    weighted_strength: f64,
 }
 
 fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
@@ -60,6 +63,8 @@ where
     pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 0.5;
     pub const MAX_OCTAVES: usize = 32;
+    pub const MIN_LACUNARITY: f64 = 1.0;
+    pub const MAX_PERSISTENCE: f64 = 0.99;
 
     pub fn new(seed: u32) -> Self {
         Self {
@@ -69,10 +74,13 @@ where
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
             sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
+            octave_rotation: 0.0,
             scale_factor: Self::calc_scale_factor(
                 Self::DEFAULT_PERSISTENCE,
                 Self::DEFAULT_OCTAVE_COUNT,
             ),
+            last_octave_weight: 1.0,
+            weighted_strength: 0.0,
         }
     }
 
@@ -83,6 +91,59 @@ where
     pub fn set_sources(self, sources: Vec<T>) -> Self {
         Self { sources, ..self }
     }
+
+    /// Sets the angle in radians by which the domain is rotated in the xy
+    /// plane between successive octaves.
+    ///
+    /// The rotation accumulates, so octave `n` is rotated by `n * radians`.
+    /// This keeps the lattices of the octaves from lining up, which reduces
+    /// the axis-aligned artifacts of stacking them. The default of 0.0 leaves
+    /// the octaves unrotated.
+    pub fn set_octave_rotation(self, octave_rotation: f64) -> Self {
+        Self {
+            octave_rotation,
+            ..self
+        }
+    }
+
+    /// Returns the angle in radians by which successive octaves are rotated.
+    pub fn octave_rotation(&self) -> f64 {
+        self.octave_rotation
+    }
+
+    /// Sets how strongly each octave is weighted by the value of the previous
+    /// octave.
+    ///
+    /// With a strength above 0.0, octaves following a low value are
+    /// attenuated, which suppresses detail in low areas the way erosion
+    /// smooths valleys. At 1.0, an octave following the minimum value of -1.0
+    /// is removed entirely. The default strength of 0.0 disables the
+    /// weighting.
+    pub fn set_weighted_strength(self, weighted_strength: f64) -> Self {
+        Self {
+            weighted_strength,
+            ..self
+        }
+    }
+
+    /// Sets a possibly fractional number of octaves.
+    ///
+    /// The noise is generated with `octaves` rounded up, and the amplitude of
+    /// the last octave is scaled by the fractional part of `octaves`, so the
+    /// output blends smoothly between the whole octave counts on either side.
+    /// This allows the amount of detail to be animated without popping. An
+    /// integral `octaves` behaves exactly like [`set_octaves`].
+    ///
+    /// [`set_octaves`]: MultiFractal::set_octaves
+    pub fn set_fractional_octaves(self, octaves: f64) -> Self {
+        let whole = octaves.ceil().max(1.0).min(Self::MAX_OCTAVES as f64);
+        let fraction = 1.0 - (whole - octaves).clamp(0.0, 1.0);
+
+        Self {
+            last_octave_weight: fraction,
+            ..self.set_octaves(whole as usize)
+        }
+    }
 }
 
 impl<T> Default for Billow<T>
@@ -94,18 +155,47 @@ where
     }
 }
 
+impl<T> Billow<T> {
+    /// Sets the frequency of the first octave.
+    ///
+    /// The same method is provided by [`MultiFractal`] and [`Frequency`];
+    /// this inherent one keeps calls unambiguous when both are in scope.
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    /// Returns the frequency of the first octave.
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+}
+
+impl<T> Frequency for Billow<T> {
+    fn set_frequency(self, frequency: f64) -> Self {
+        Billow::set_frequency(self, frequency)
+    }
+
+    fn frequency(&self) -> f64 {
+        Billow::frequency(self)
+    }
+}
+
 impl<T> MultiFractal for Billow<T>
 where
     T: Default + Seedable,
 {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
-            return self;
+            return Self {
+                last_octave_weight: 1.0,
+                ..self
+            };
         }
 
         octaves = octaves.clamp(1, Self::MAX_OCTAVES);
         Self {
             octaves,
+            last_octave_weight: 1.0,
             sources: super::build_sources(self.seed, octaves),
             scale_factor: calc_scale_factor(self.persistence, octaves),
             ..self
@@ -113,20 +203,50 @@ where
     }
 
     fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency, ..self }
+        Billow::set_frequency(self, frequency)
     }
 
+    /// Sets the lacunarity, clamped to at least [`MIN_LACUNARITY`] so that
+    /// the frequency never decreases between octaves.
+    ///
+    /// [`MIN_LACUNARITY`]: Self::MIN_LACUNARITY
     fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
+        Self {
+            lacunarity: lacunarity.max(Self::MIN_LACUNARITY),
+            ..self
+        }
     }
 
+    /// Sets the persistence, clamped to the range 0.0 to
+    /// [`MAX_PERSISTENCE`]. The output is scaled by the total amplitude of the
+    /// octaves, which vanishes for a persistence of 1.0 and changes sign
+    /// above it.
+    ///
+    /// [`MAX_PERSISTENCE`]: Self::MAX_PERSISTENCE
     fn set_persistence(self, persistence: f64) -> Self {
+        let persistence = persistence.clamp(0.0, Self::MAX_PERSISTENCE);
         Self {
             persistence,
             scale_factor: calc_scale_factor(persistence, self.octaves),
             ..self
         }
     }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        Billow::frequency(self)
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
 }
 
 impl<T> Seedable for Billow<T>
@@ -150,6 +270,111 @@ where
     }
 }
 
+impl<T> Billow<T> {
+    /// Scales the sum of the octaves into the [-1,1] range, blending out the
+    /// last octave by its fractional weight.
+    fn scale_result(&self, result: f64, last_signal: f64) -> f64 {
+        let scaled = result / self.scale_factor;
+        if self.last_octave_weight >= 1.0 {
+            return scaled;
+        }
+
+        let previous = if self.octaves > 1 {
+            (result - last_signal) / calc_scale_factor(self.persistence, self.octaves - 1)
+        } else {
+            0.0
+        };
+
+        previous + (scaled - previous) * self.last_octave_weight
+    }
+
+    /// Sums the octaves along with their gradients, each of which is the
+    /// gradient of its source scaled by the frequency of the octave and rotated
+    /// back by its rotation.
+    fn fractal_gradient<const DIM: usize>(&self, mut point: [f64; DIM]) -> (f64, [f64; DIM])
+    where
+        T: NoiseFnGradient<DIM>,
+    {
+        let mut scale = self.frequency;
+        for coordinate in point.iter_mut() {
+            *coordinate *= scale;
+        }
+        let rotation = super::octave_rotation(self.octave_rotation);
+        let (mut sin, mut cos) = (0.0, 1.0);
+
+        let (mut result, mut d_result) = (0.0, [0.0; DIM]);
+        let (mut last_signal, mut d_last_signal) = (0.0, [0.0; DIM]);
+        let (mut weight, mut d_weight) = (1.0, [0.0; DIM]);
+
+        for x in 0..self.octaves {
+            let (value, mut d_value) = self.sources[x].get_with_gradient(point);
+            let slope = 2.0 * value.signum();
+            for derivative in d_value.iter_mut() {
+                *derivative *= scale * slope;
+            }
+
+            // Rotate the gradient back into the frame of the input point.
+            if let [dx, dy, ..] = &mut d_value[..] {
+                let (dx0, dy0) = (*dx, *dy);
+                *dx = dx0 * cos + dy0 * sin;
+                *dy = dy0 * cos - dx0 * sin;
+            }
+
+            // Take the abs of the signal, then scale and shift back to
+            // the [-1,1] range.
+            let value = scale_shift(value, 2.0);
+
+            let amplitude = self.persistence.powi(x as i32);
+            let signal = value * (amplitude * weight);
+            let mut d_signal = [0.0; DIM];
+            for (i, derivative) in d_signal.iter_mut().enumerate() {
+                *derivative = amplitude * (d_value[i] * weight + value * d_weight[i]);
+            }
+
+            // The weight of the next octave follows the value of this one,
+            // except where it is clamped.
+            let d_weight_scale = if value + 1.0 < 2.0 {
+                self.weighted_strength * 0.5
+            } else {
+                0.0
+            };
+            weight = interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
+            for (d_weight, d_value) in d_weight.iter_mut().zip(d_value.iter()) {
+                *d_weight = d_weight_scale * d_value;
+            }
+
+            result += signal;
+            last_signal = signal;
+            for (i, &derivative) in d_signal.iter().enumerate() {
+                d_result[i] += derivative;
+                d_last_signal[i] = derivative;
+            }
+
+            for coordinate in point.iter_mut() {
+                *coordinate *= self.lacunarity;
+            }
+            if let [x, y, ..] = &mut point[..] {
+                super::rotate_octave(x, y, rotation);
+            }
+            if let Some((step_sin, step_cos)) = rotation {
+                let next_sin = sin * step_cos + cos * step_sin;
+                cos = cos * step_cos - sin * step_sin;
+                sin = next_sin;
+            }
+            scale *= self.lacunarity;
+        }
+
+        // Scaling the result is linear, so it scales the gradient the same
+        // way.
+        let mut gradient = [0.0; DIM];
+        for (i, derivative) in gradient.iter_mut().enumerate() {
+            *derivative = self.scale_result(d_result[i], d_last_signal[i]);
+        }
+
+        (self.scale_result(result, last_signal), gradient)
+    }
+}
+
 /// 2-dimensional Billow noise
 impl<T> NoiseFn<f64, 2> for Billow<T>
 where
@@ -159,8 +384,11 @@ where
         let mut point = Vector2::from(point.into());
 
         let mut result = 0.0;
+        let mut last_signal = 0.0;
+        let mut weight = 1.0;
 
         point *= self.frequency;
+        let rotation = super::octave_rotation(self.octave_rotation);
 
         for x in 0..self.octaves {
             // Get the signal.
@@ -170,18 +398,23 @@ where
             // the [-1,1] range.
             signal = scale_shift(signal, 2.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and weight
+            // the next octave by the value of this one.
+            let value = signal;
+            signal *= self.persistence.powi(x as i32) * weight;
+            weight = interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
 
             // Add the signal to the result.
             result += signal;
+            last_signal = signal;
 
             // Increase the frequency for the next octave.
             point *= self.lacunarity;
+            super::rotate_octave(&mut point.x, &mut point.y, rotation);
         }
 
         // Scale the result to the [-1,1] range.
-        result / self.scale_factor
+        self.scale_result(result, last_signal)
     }
 }
 
@@ -194,8 +427,11 @@ where
         let mut point = Vector3::from(point.into());
 
         let mut result = 0.0;
+        let mut last_signal = 0.0;
+        let mut weight = 1.0;
 
         point *= self.frequency;
+        let rotation = super::octave_rotation(self.octave_rotation);
 
         for x in 0..self.octaves {
             // Get the signal.
@@ -205,18 +441,23 @@ where
             // the [-1,1] range.
             signal = scale_shift(signal, 2.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and weight
+            // the next octave by the value of this one.
+            let value = signal;
+            signal *= self.persistence.powi(x as i32) * weight;
+            weight = interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
 
             // Add the signal to the result.
             result += signal;
+            last_signal = signal;
 
             // Increase the frequency for the next octave.
             point *= self.lacunarity;
+            super::rotate_octave(&mut point.x, &mut point.y, rotation);
         }
 
         // Scale the result to the [-1,1] range.
-        result / self.scale_factor
+        self.scale_result(result, last_signal)
     }
 }
 
@@ -229,8 +470,11 @@ where
         let mut point = Vector4::from(point.into());
 
         let mut result = 0.0;
+        let mut last_signal = 0.0;
+        let mut weight = 1.0;
 
         point *= self.frequency;
+        let rotation = super::octave_rotation(self.octave_rotation);
 
         for x in 0..self.octaves {
             // Get the signal.
@@ -240,17 +484,49 @@ where
             // the [-1,1] range.
             signal = scale_shift(signal, 2.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and weight
+            // the next octave by the value of this one.
+            let value = signal;
+            signal *= self.persistence.powi(x as i32) * weight;
+            weight = interpolate::linear(1.0, (value + 1.0).min(2.0) * 0.5, self.weighted_strength);
 
             // Add the signal to the result.
             result += signal;
+            last_signal = signal;
 
             // Increase the frequency for the next octave.
             point *= self.lacunarity;
+            super::rotate_octave(&mut point.x, &mut point.y, rotation);
         }
 
         // Scale the result to the [-1,1] range.
-        result / self.scale_factor
+        self.scale_result(result, last_signal)
+    }
+}
+
+impl<T> NoiseFnGradient<2> for Billow<T>
+where
+    T: NoiseFnGradient<2>,
+{
+    fn get_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        self.fractal_gradient(point)
+    }
+}
+
+impl<T> NoiseFnGradient<3> for Billow<T>
+where
+    T: NoiseFnGradient<3>,
+{
+    fn get_with_gradient(&self, point: [f64; 3]) -> (f64, [f64; 3]) {
+        self.fractal_gradient(point)
+    }
+}
+
+impl<T> NoiseFnGradient<4> for Billow<T>
+where
+    T: NoiseFnGradient<4>,
+{
+    fn get_with_gradient(&self, point: [f64; 4]) -> (f64, [f64; 4]) {
+        self.fractal_gradient(point)
     }
 }
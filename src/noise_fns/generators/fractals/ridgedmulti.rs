@@ -1,6 +1,6 @@
 use crate::{
-    math::{scale_shift, vectors::*},
-    noise_fns::{MultiFractal, NoiseFn, Seedable},
+    math::{interpolate, scale_shift, vectors::*},
+    noise_fns::{Frequency, MultiFractal, NoiseFn, NoiseFnGradient, Seedable},
 };
 use alloc::vec::Vec;
 
@@ -58,6 +58,9 @@ pub struct RidgedMulti<T> {
 
     seed: u32,
     sources: Vec<T>,
+    octave_rotation: f64,
+    last_octave_weight: f64,
+    weighted_strength: f64,
 }
 
 impl<T> RidgedMulti<T>
@@ -71,6 +74,7 @@ where
     pub const DEFAULT_PERSISTENCE: f64 = 1.0;
     pub const DEFAULT_ATTENUATION: f64 = 2.0;
     pub const MAX_OCTAVES: usize = 32;
+    pub const MIN_LACUNARITY: f64 = 1.0;
 
     pub fn new(seed: u32) -> Self {
         Self {
@@ -81,6 +85,9 @@ where
             persistence: Self::DEFAULT_PERSISTENCE,
             attenuation: Self::DEFAULT_ATTENUATION,
             sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
+            octave_rotation: 0.0,
+            last_octave_weight: 1.0,
+            weighted_strength: 0.0,
         }
     }
 
@@ -91,9 +98,65 @@ where
         }
     }
 
+    pub fn attenuation(&self) -> f64 {
+        self.attenuation
+    }
+
     pub fn set_sources(self, sources: Vec<T>) -> Self {
         Self { sources, ..self }
     }
+
+    /// Sets the angle in radians by which the domain is rotated in the xy
+    /// plane between successive octaves.
+    ///
+    /// The rotation accumulates, so octave `n` is rotated by `n * radians`.
+    /// This keeps the lattices of the octaves from lining up, which reduces
+    /// the axis-aligned artifacts of stacking them. The default of 0.0 leaves
+    /// the octaves unrotated.
+    pub fn set_octave_rotation(self, octave_rotation: f64) -> Self {
+        Self {
+            octave_rotation,
+            ..self
+        }
+    }
+
+    /// Returns the angle in radians by which successive octaves are rotated.
+    pub fn octave_rotation(&self) -> f64 {
+        self.octave_rotation
+    }
+
+    /// Sets how strongly each octave is weighted by the ridge value of the
+    /// previous octave, which is 1.0 on a ridge and 0.0 in a valley.
+    ///
+    /// With a strength above 0.0, octaves following a valley are attenuated,
+    /// which suppresses detail in low areas the way erosion smooths valleys.
+    /// At 1.0, an octave following the bottom of a valley is removed
+    /// entirely. The default strength of 0.0 disables the weighting.
+    pub fn set_weighted_strength(self, weighted_strength: f64) -> Self {
+        Self {
+            weighted_strength,
+            ..self
+        }
+    }
+
+    /// Sets a possibly fractional number of octaves.
+    ///
+    /// The noise is generated with `octaves` rounded up, and the output is
+    /// blended between the whole octave counts on either side by the
+    /// fractional part of `octaves`. This allows the amount of detail to be
+    /// animated without popping. An integral `octaves` behaves exactly like
+    /// [`set_octaves`].
+    ///
+    /// [`set_octaves`]: MultiFractal::set_octaves
+    pub fn set_fractional_octaves(self, octaves: f64) -> Self {
+        let whole = octaves.ceil().max(1.0).min(Self::MAX_OCTAVES as f64);
+        let fraction = 1.0 - (whole - octaves).clamp(0.0, 1.0);
+
+        Self {
+            last_octave_weight: fraction,
+            ..self.set_octaves(whole as usize)
+        }
+    }
 }
 
 impl<T> Default for RidgedMulti<T>
@@ -105,29 +168,65 @@ where
     }
 }
 
+impl<T> RidgedMulti<T> {
+    /// Sets the frequency of the first octave.
+    ///
+    /// The same method is provided by [`MultiFractal`] and [`Frequency`];
+    /// this inherent one keeps calls unambiguous when both are in scope.
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    /// Returns the frequency of the first octave.
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+}
+
+impl<T> Frequency for RidgedMulti<T> {
+    fn set_frequency(self, frequency: f64) -> Self {
+        RidgedMulti::set_frequency(self, frequency)
+    }
+
+    fn frequency(&self) -> f64 {
+        RidgedMulti::frequency(self)
+    }
+}
+
 impl<T> MultiFractal for RidgedMulti<T>
 where
     T: Default + Seedable,
 {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
-            return self;
+            return Self {
+                last_octave_weight: 1.0,
+                ..self
+            };
         }
 
         octaves = octaves.clamp(1, Self::MAX_OCTAVES);
         Self {
             octaves,
+            last_octave_weight: 1.0,
             sources: super::build_sources(self.seed, octaves),
             ..self
         }
     }
 
     fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency, ..self }
+        RidgedMulti::set_frequency(self, frequency)
     }
 
+    /// Sets the lacunarity, clamped to at least [`MIN_LACUNARITY`] so that
+    /// the frequency never decreases between octaves.
+    ///
+    /// [`MIN_LACUNARITY`]: Self::MIN_LACUNARITY
     fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
+        Self {
+            lacunarity: lacunarity.max(Self::MIN_LACUNARITY),
+            ..self
+        }
     }
 
     fn set_persistence(self, persistence: f64) -> Self {
@@ -136,6 +235,22 @@ where
             ..self
         }
     }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        RidgedMulti::frequency(self)
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
 }
 
 impl<T> Seedable for RidgedMulti<T>
@@ -159,6 +274,157 @@ where
     }
 }
 
+/// Scales and shifts the sum of the given number of octaves into the [-1,1]
+/// range.
+fn scale_octaves(result: f64, octaves: usize) -> f64 {
+    let scale = 2.0 - 0.5_f64.powi(octaves as i32 - 1);
+    scale_shift(result, 2.0 / scale)
+}
+
+/// Returns the slope of [`scale_octaves`] at the given sum of octaves.
+fn scale_octaves_slope(result: f64, octaves: usize) -> f64 {
+    let slope = 2.0 / (2.0 - 0.5_f64.powi(octaves as i32 - 1));
+    if result < 0.0 {
+        -slope
+    } else {
+        slope
+    }
+}
+
+impl<T> RidgedMulti<T> {
+    /// Scales and shifts the result into the [-1,1] range, blending out the
+    /// last octave by its fractional weight.
+    fn scale_result(&self, result: f64, last_signal: f64) -> f64 {
+        let scaled = scale_octaves(result, self.octaves);
+        if self.last_octave_weight >= 1.0 {
+            return scaled;
+        }
+
+        let previous = if self.octaves > 1 {
+            scale_octaves(result - last_signal, self.octaves - 1)
+        } else {
+            0.0
+        };
+
+        previous + (scaled - previous) * self.last_octave_weight
+    }
+
+    /// Sums the octaves along with their gradients, each of which is the
+    /// gradient of its source scaled by the frequency of the octave and rotated
+    /// back by its rotation.
+    fn fractal_gradient<const DIM: usize>(&self, mut point: [f64; DIM]) -> (f64, [f64; DIM])
+    where
+        T: NoiseFnGradient<DIM>,
+    {
+        let mut scale = self.frequency;
+        for coordinate in point.iter_mut() {
+            *coordinate *= scale;
+        }
+        let rotation = super::octave_rotation(self.octave_rotation);
+        let (mut sin, mut cos) = (0.0, 1.0);
+
+        let (mut result, mut d_result) = (0.0, [0.0; DIM]);
+        let (mut last_signal, mut d_last_signal) = (0.0, [0.0; DIM]);
+        let (mut weight, mut d_weight) = (1.0, [0.0; DIM]);
+        let (mut detail_weight, mut d_detail_weight) = (1.0, [0.0; DIM]);
+
+        for x in 0..self.octaves {
+            let (value, d_value) = self.sources[x].get_with_gradient(point);
+            let d_value = super::octave_gradient(d_value, scale, sin, cos);
+
+            // Make the ridges, square them, and weight them by the previous
+            // octave.
+            let ridge = 1.0 - value.abs();
+            let mut d_ridge = d_value;
+            if value >= 0.0 {
+                for derivative in d_ridge.iter_mut() {
+                    *derivative = -*derivative;
+                }
+            }
+            let squared = ridge * ridge;
+            let signal = squared * weight;
+            let mut d_signal = [0.0; DIM];
+            for (i, derivative) in d_signal.iter_mut().enumerate() {
+                *derivative = 2.0 * ridge * d_ridge[i] * weight + squared * d_weight[i];
+            }
+
+            // The weight of the next octave follows the signal, except where
+            // it is clamped.
+            let unclamped = signal / self.attenuation;
+            weight = unclamped.clamp(0.0, 1.0);
+            for (d_weight, &d_signal) in d_weight.iter_mut().zip(d_signal.iter()) {
+                *d_weight = if unclamped > 0.0 && unclamped < 1.0 {
+                    d_signal / self.attenuation
+                } else {
+                    0.0
+                };
+            }
+
+            let amplitude = self.persistence.powi(x as i32);
+            let scaled = signal * (amplitude * detail_weight);
+            for (i, derivative) in d_signal.iter_mut().enumerate() {
+                *derivative =
+                    amplitude * (*derivative * detail_weight + signal * d_detail_weight[i]);
+            }
+
+            detail_weight = interpolate::linear(1.0, ridge.max(0.0), self.weighted_strength);
+            for (d_detail_weight, &d_ridge) in d_detail_weight.iter_mut().zip(d_ridge.iter()) {
+                *d_detail_weight = if ridge > 0.0 {
+                    self.weighted_strength * d_ridge
+                } else {
+                    0.0
+                };
+            }
+
+            result += scaled;
+            last_signal = scaled;
+            for (i, &derivative) in d_signal.iter().enumerate() {
+                d_result[i] += derivative;
+                d_last_signal[i] = derivative;
+            }
+
+            for coordinate in point.iter_mut() {
+                *coordinate *= self.lacunarity;
+            }
+            if let [x, y, ..] = &mut point[..] {
+                super::rotate_octave(x, y, rotation);
+            }
+            super::advance_rotation(&mut sin, &mut cos, rotation);
+            scale *= self.lacunarity;
+        }
+
+        let mut gradient = [0.0; DIM];
+        for (i, derivative) in gradient.iter_mut().enumerate() {
+            *derivative = self.scale_gradient(result, last_signal, d_result[i], d_last_signal[i]);
+        }
+
+        (self.scale_result(result, last_signal), gradient)
+    }
+
+    /// Returns a derivative of the scaled result, given the same derivative of
+    /// the sum of the octaves and of the last octave.
+    fn scale_gradient(
+        &self,
+        result: f64,
+        last_signal: f64,
+        d_result: f64,
+        d_last_signal: f64,
+    ) -> f64 {
+        let scaled = scale_octaves_slope(result, self.octaves) * d_result;
+        if self.last_octave_weight >= 1.0 {
+            return scaled;
+        }
+
+        let previous = if self.octaves > 1 {
+            scale_octaves_slope(result - last_signal, self.octaves - 1) * (d_result - d_last_signal)
+        } else {
+            0.0
+        };
+
+        previous + (scaled - previous) * self.last_octave_weight
+    }
+}
+
 /// 2-dimensional `RidgedMulti` noise
 impl<T> NoiseFn<f64, 2> for RidgedMulti<T>
 where
@@ -168,9 +434,12 @@ where
         let mut point = Vector2::from(point.into());
 
         let mut result = 0.0;
+        let mut last_signal = 0.0;
         let mut weight = 1.0;
+        let mut detail_weight = 1.0;
 
         point *= self.frequency;
+        let rotation = super::octave_rotation(self.octave_rotation);
 
         for x in 0..self.octaves {
             // Get the value.
@@ -179,6 +448,7 @@ where
             // Make the ridges.
             signal = signal.abs();
             signal = 1.0 - signal;
+            let ridge = signal;
 
             // Square the signal to increase the sharpness of the ridges.
             signal *= signal;
@@ -194,19 +464,21 @@ where
             // Clamp the weight to [0,1] to prevent the result from diverging.
             weight = weight.clamp(0.0, 1.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and weight
+            // the next octave by the ridge value of this one.
+            signal *= self.persistence.powi(x as i32) * detail_weight;
+            detail_weight = interpolate::linear(1.0, ridge.max(0.0), self.weighted_strength);
 
             // Add the signal to the result.
             result += signal;
+            last_signal = signal;
 
             // Increase the frequency.
             point *= self.lacunarity;
+            super::rotate_octave(&mut point.x, &mut point.y, rotation);
         }
 
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
+        self.scale_result(result, last_signal)
     }
 }
 
@@ -219,9 +491,12 @@ where
         let mut point = Vector3::from(point.into());
 
         let mut result = 0.0;
+        let mut last_signal = 0.0;
         let mut weight = 1.0;
+        let mut detail_weight = 1.0;
 
         point *= self.frequency;
+        let rotation = super::octave_rotation(self.octave_rotation);
 
         for x in 0..self.octaves {
             // Get the value.
@@ -230,6 +505,7 @@ where
             // Make the ridges.
             signal = signal.abs();
             signal = 1.0 - signal;
+            let ridge = signal;
 
             // Square the signal to increase the sharpness of the ridges.
             signal *= signal;
@@ -245,19 +521,21 @@ where
             // Clamp the weight to [0,1] to prevent the result from diverging.
             weight = weight.clamp(0.0, 1.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and weight
+            // the next octave by the ridge value of this one.
+            signal *= self.persistence.powi(x as i32) * detail_weight;
+            detail_weight = interpolate::linear(1.0, ridge.max(0.0), self.weighted_strength);
 
             // Add the signal to the result.
             result += signal;
+            last_signal = signal;
 
             // Increase the frequency.
             point *= self.lacunarity;
+            super::rotate_octave(&mut point.x, &mut point.y, rotation);
         }
 
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
+        self.scale_result(result, last_signal)
     }
 }
 
@@ -270,9 +548,12 @@ where
         let mut point = Vector4::from(point.into());
 
         let mut result = 0.0;
+        let mut last_signal = 0.0;
         let mut weight = 1.0;
+        let mut detail_weight = 1.0;
 
         point *= self.frequency;
+        let rotation = super::octave_rotation(self.octave_rotation);
 
         for x in 0..self.octaves {
             // Get the value.
@@ -281,6 +562,7 @@ where
             // Make the ridges.
             signal = signal.abs();
             signal = 1.0 - signal;
+            let ridge = signal;
 
             // Square the signal to increase the sharpness of the ridges.
             signal *= signal;
@@ -296,18 +578,47 @@ where
             // Clamp the weight to [0,1] to prevent the result from diverging.
             weight = weight.clamp(0.0, 1.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            // Scale the amplitude appropriately for this frequency, and weight
+            // the next octave by the ridge value of this one.
+            signal *= self.persistence.powi(x as i32) * detail_weight;
+            detail_weight = interpolate::linear(1.0, ridge.max(0.0), self.weighted_strength);
 
             // Add the signal to the result.
             result += signal;
+            last_signal = signal;
 
             // Increase the frequency.
             point *= self.lacunarity;
+            super::rotate_octave(&mut point.x, &mut point.y, rotation);
         }
 
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
+        self.scale_result(result, last_signal)
+    }
+}
+
+impl<T> NoiseFnGradient<2> for RidgedMulti<T>
+where
+    T: NoiseFnGradient<2>,
+{
+    fn get_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        self.fractal_gradient(point)
+    }
+}
+
+impl<T> NoiseFnGradient<3> for RidgedMulti<T>
+where
+    T: NoiseFnGradient<3>,
+{
+    fn get_with_gradient(&self, point: [f64; 3]) -> (f64, [f64; 3]) {
+        self.fractal_gradient(point)
+    }
+}
+
+impl<T> NoiseFnGradient<4> for RidgedMulti<T>
+where
+    T: NoiseFnGradient<4>,
+{
+    fn get_with_gradient(&self, point: [f64; 4]) -> (f64, [f64; 4]) {
+        self.fractal_gradient(point)
     }
 }
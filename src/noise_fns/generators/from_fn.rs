@@ -0,0 +1,65 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the value returned by a closure.
+///
+/// This lets a bare closure be used anywhere a source function is expected.
+/// Closures can't implement [`NoiseFn`] directly: a blanket implementation
+/// for every `Fn` would conflict with the implementation for references,
+/// since references to closures are closures too.
+///
+/// Use [`from_fn`] to create one.
+#[derive(Clone, Copy)]
+pub struct FromFn<F> {
+    function: F,
+}
+
+/// Creates a noise function that outputs the value returned by `function` at
+/// each point.
+///
+/// ```
+/// use noise::{from_fn, NoiseFn, Perlin};
+///
+/// let gradient = from_fn(|[x, _y]: [f64; 2]| x * 0.1);
+/// let sloped = gradient.add(Perlin::new(1));
+/// let value = sloped.get([3.0, 0.5]);
+/// ```
+pub fn from_fn<F>(function: F) -> FromFn<F> {
+    FromFn { function }
+}
+
+impl<T, F, const DIM: usize> NoiseFn<T, DIM> for FromFn<F>
+where
+    F: Fn([T; DIM]) -> f64,
+{
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        (self.function)(point.into())
+    }
+}
+
+impl<F> core::fmt::Debug for FromFn<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FromFn")
+            .field("function", &core::format_args!("..."))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn test_closure_as_source() {
+        let perlin = Perlin::new(4);
+        let sloped = from_fn(|[x, _y]: [f64; 2]| x).add(perlin);
+        let scaled = perlin.multiply(from_fn(|[_x, y, z]: [f64; 3]| y * z));
+
+        for i in 0..32 {
+            let x = i as f64 * 0.31 - 2.0;
+
+            assert_eq!(x + perlin.get([x, 0.7]), sloped.get([x, 0.7]));
+            assert_eq!(perlin.get([x, 0.5, 0.3]) * 0.15, scaled.get([x, 0.5, 0.3]));
+        }
+    }
+}
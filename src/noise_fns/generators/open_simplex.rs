@@ -4,26 +4,56 @@
 
 use crate::{
     core::open_simplex::{open_simplex_2d, open_simplex_3d, open_simplex_4d},
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    math::{axis_frequencies, scale_axes},
+    noise_fns::{Frequency, NoiseFn, Seedable},
+    permutationtable::{DefaultHasher, NoiseHasher, PermutationTable, SeedableHasher},
 };
 
 /// Noise function that outputs 2/3/4-dimensional Open Simplex noise.
 #[derive(Clone, Copy, Debug)]
-pub struct OpenSimplex {
+pub struct OpenSimplex<H = DefaultHasher> {
     seed: u32,
-    perm_table: PermutationTable,
+    perm_table: H,
+    frequencies: [f64; 4],
 }
 
 impl OpenSimplex {
     const DEFAULT_SEED: u32 = 0;
 
     pub fn new(seed: u32) -> Self {
+        Self::with_hasher(seed, PermutationTable::new(seed))
+    }
+}
+
+impl<H> OpenSimplex<H>
+where
+    H: NoiseHasher,
+{
+    /// Creates the noise function using the given hasher, which should have
+    /// been constructed from `seed`.
+    pub fn with_hasher(seed: u32, hasher: H) -> Self {
         Self {
             seed,
-            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            perm_table: hasher,
+            frequencies: [1.0; 4],
+        }
+    }
+
+    /// Sets the frequency of each input axis, in order. The input point is
+    /// scaled per axis before sampling, which is equivalent to wrapping the
+    /// generator in `ScalePoint`. Axes without a frequency use 1.0.
+    pub fn set_frequencies<const DIM: usize>(self, frequencies: [f64; DIM]) -> Self {
+        Self {
+            frequencies: axis_frequencies(frequencies),
+            ..self
         }
     }
+
+    /// Returns the frequency of each input axis, in order. The default is 1.0
+    /// for every axis.
+    pub fn frequencies(&self) -> [f64; 4] {
+        self.frequencies
+    }
 }
 
 impl Default for OpenSimplex {
@@ -32,7 +62,10 @@ impl Default for OpenSimplex {
     }
 }
 
-impl Seedable for OpenSimplex {
+impl<H> Seedable for OpenSimplex<H>
+where
+    H: SeedableHasher,
+{
     /// Sets the seed value for Open Simplex noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -43,7 +76,8 @@ impl Seedable for OpenSimplex {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: H::from_seed(seed),
+            ..self
         }
     }
 
@@ -52,29 +86,62 @@ impl Seedable for OpenSimplex {
     }
 }
 
+impl<H> Frequency for OpenSimplex<H> {
+    /// Sets the same frequency on every input axis.
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self {
+            frequencies: [frequency; 4],
+            ..self
+        }
+    }
+
+    /// Returns the frequency of the x axis.
+    fn frequency(&self) -> f64 {
+        self.frequencies[0]
+    }
+}
+
 /// 2-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 2D.
-impl NoiseFn<f64, 2> for OpenSimplex {
+impl<H> NoiseFn<f64, 2> for OpenSimplex<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
-        open_simplex_2d(point.into(), &self.perm_table)
+        open_simplex_2d(
+            scale_axes(point.into(), &self.frequencies),
+            &self.perm_table,
+        )
     }
 }
 
 /// 3-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 3D.
-impl NoiseFn<f64, 3> for OpenSimplex {
+impl<H> NoiseFn<f64, 3> for OpenSimplex<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
-        open_simplex_3d(point.into(), &self.perm_table)
+        open_simplex_3d(
+            scale_axes(point.into(), &self.frequencies),
+            &self.perm_table,
+        )
     }
 }
 
 /// 4-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 4D.
-impl NoiseFn<f64, 4> for OpenSimplex {
+impl<H> NoiseFn<f64, 4> for OpenSimplex<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
-        open_simplex_4d(point.into(), &self.perm_table)
+        open_simplex_4d(
+            scale_axes(point.into(), &self.frequencies),
+            &self.perm_table,
+        )
     }
 }
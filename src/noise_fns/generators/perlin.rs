@@ -1,25 +1,104 @@
 use crate::{
     core::perlin::*,
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    math::{axis_frequencies, scale_axes},
+    noise_fns::{Frequency, NoiseFn, Seedable},
+    permutationtable::{
+        DefaultHasher, NoiseHasher, PeriodicHasher, PermutationTable, SeedableHasher,
+    },
 };
 
 /// Noise function that outputs 2/3/4-dimensional Perlin noise.
 #[derive(Clone, Copy, Debug)]
-pub struct Perlin {
+pub struct Perlin<H = DefaultHasher> {
     seed: u32,
-    perm_table: PermutationTable,
+    perm_table: H,
+    frequencies: [f64; 4],
+    period: [u32; 4],
 }
 
 impl Perlin {
     pub const DEFAULT_SEED: u32 = 0;
 
     pub fn new(seed: u32) -> Self {
+        Self::with_hasher(seed, PermutationTable::new(seed))
+    }
+}
+
+impl<H> Perlin<H>
+where
+    H: NoiseHasher,
+{
+    /// Creates the noise function using the given hasher, which should have
+    /// been constructed from `seed`.
+    pub fn with_hasher(seed: u32, hasher: H) -> Self {
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: hasher,
+            frequencies: [1.0; 4],
+            period: [0; 4],
         }
     }
+
+    /// Sets the frequency of each input axis, in order. The input point is
+    /// scaled per axis before sampling, which is equivalent to wrapping the
+    /// generator in `ScalePoint`. Axes without a frequency use 1.0.
+    pub fn set_frequencies<const DIM: usize>(self, frequencies: [f64; DIM]) -> Self {
+        Self {
+            frequencies: axis_frequencies(frequencies),
+            ..self
+        }
+    }
+
+    /// Returns the frequency of each input axis, in order. The default is 1.0
+    /// for every axis.
+    pub fn frequencies(&self) -> [f64; 4] {
+        self.frequencies
+    }
+
+    /// Sets the period of the integer lattice along each input axis, in
+    /// order, so that the noise tiles exactly: sampling a point and the same
+    /// point moved by a whole period along an axis gives the same output. A
+    /// period of 0 disables tiling along its axis, as do missing axes.
+    ///
+    /// The period is measured in lattice cells, i.e. after the input point is
+    /// scaled by the frequencies.
+    pub fn set_period<const DIM: usize>(self, period: [u32; DIM]) -> Self {
+        assert!(DIM <= 4, "at most four axis periods are supported");
+
+        let mut axes = [0; 4];
+        axes[..DIM].copy_from_slice(&period);
+        Self {
+            period: axes,
+            ..self
+        }
+    }
+
+    /// Returns the period of the lattice along each input axis, in order. The
+    /// default is 0, i.e. no tiling, for every axis.
+    pub fn period(&self) -> [u32; 4] {
+        self.period
+    }
+
+    /// Returns the lattice cell containing `point`, the position of `point`
+    /// within it, and the gradient dot products at its corners before they
+    /// are faded and interpolated. This is a diagnostics aid for verifying
+    /// the gradients of the lattice.
+    pub fn lattice_debug<const DIM: usize>(
+        &self,
+        point: impl Into<[f64; DIM]>,
+    ) -> LatticeSample<DIM> {
+        let point = scale_axes(point.into(), &self.frequencies);
+
+        if self.is_periodic() {
+            perlin_lattice_sample(point, &PeriodicHasher::new(&self.perm_table, &self.period))
+        } else {
+            perlin_lattice_sample(point, &self.perm_table)
+        }
+    }
+
+    fn is_periodic(&self) -> bool {
+        self.period.iter().any(|&period| period != 0)
+    }
 }
 
 impl Default for Perlin {
@@ -28,7 +107,10 @@ impl Default for Perlin {
     }
 }
 
-impl Seedable for Perlin {
+impl<H> Seedable for Perlin<H>
+where
+    H: SeedableHasher,
+{
     /// Sets the seed value for Perlin noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -39,7 +121,8 @@ impl Seedable for Perlin {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: H::from_seed(seed),
+            ..self
         }
     }
 
@@ -48,23 +131,212 @@ impl Seedable for Perlin {
     }
 }
 
+impl<H> Frequency for Perlin<H> {
+    /// Sets the same frequency on every input axis.
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self {
+            frequencies: [frequency; 4],
+            ..self
+        }
+    }
+
+    /// Returns the frequency of the x axis.
+    fn frequency(&self) -> f64 {
+        self.frequencies[0]
+    }
+}
+
 /// 2-dimensional perlin noise
-impl NoiseFn<f64, 2> for Perlin {
+impl<H> NoiseFn<f64, 2> for Perlin<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
-        perlin_2d(point.into(), &self.perm_table)
+        let point = scale_axes(point.into(), &self.frequencies);
+
+        if self.is_periodic() {
+            perlin_2d(point, &PeriodicHasher::new(&self.perm_table, &self.period))
+        } else {
+            perlin_2d(point, &self.perm_table)
+        }
     }
 }
 
 /// 3-dimensional perlin noise
-impl NoiseFn<f64, 3> for Perlin {
+impl<H> NoiseFn<f64, 3> for Perlin<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
-        perlin_3d(point.into(), &self.perm_table)
+        let point = scale_axes(point.into(), &self.frequencies);
+
+        if self.is_periodic() {
+            perlin_3d(point, &PeriodicHasher::new(&self.perm_table, &self.period))
+        } else {
+            perlin_3d(point, &self.perm_table)
+        }
     }
 }
 
 /// 4-dimensional perlin noise
-impl NoiseFn<f64, 4> for Perlin {
+impl<H> NoiseFn<f64, 4> for Perlin<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
-        perlin_4d(point.into(), &self.perm_table)
+        let point = scale_axes(point.into(), &self.frequencies);
+
+        if self.is_periodic() {
+            perlin_4d(point, &PeriodicHasher::new(&self.perm_table, &self.period))
+        } else {
+            perlin_4d(point, &self.perm_table)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OpenSimplex, PerlinSurflet, ScalePoint, Simplex, SuperSimplex, Value};
+
+    #[test]
+    fn test_frequencies_match_scale_point() {
+        let perlin = Perlin::new(3);
+        let scaled = ScalePoint::new(perlin).set_all_scales(2.5, 0.5, 1.25, 1.0);
+        let stretched = perlin.set_frequencies([2.5, 0.5, 1.25]);
+
+        let simplex = Simplex::new(3);
+        let scaled_simplex = ScalePoint::new(simplex).set_x_scale(4.0).set_y_scale(0.25);
+        let stretched_simplex = simplex.set_frequencies([4.0, 0.25]);
+
+        for i in 0..50 {
+            let t = i as f64 * 0.37 - 9.0;
+            let point = [t, t * -0.6 + 1.3, t * 0.2];
+
+            assert_eq!(
+                scaled.get([point[0], point[1]]),
+                stretched.get([point[0], point[1]])
+            );
+            assert_eq!(scaled.get(point), stretched.get(point));
+            assert_eq!(
+                scaled.get([point[0], point[1], point[2], 0.5]),
+                stretched.get([point[0], point[1], point[2], 0.5])
+            );
+            assert_eq!(
+                scaled_simplex.get([point[0], point[1]]),
+                stretched_simplex.get([point[0], point[1]])
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_ref_matches_get() {
+        let perlin = Perlin::new(5);
+        let point = [1.25, -0.75, 3.5];
+
+        assert_eq!(perlin.get(point), perlin.get_ref(&point));
+        assert_eq!(perlin.get(point), NoiseFn::get_ref(&&perlin, &point));
+    }
+
+    #[test]
+    fn test_period_tiles_exactly() {
+        let perlin = Perlin::new(5).set_period([4, 3]);
+        let value = Value::new(5).set_period([4, 3]);
+        let perlin_3d = Perlin::new(5).set_period([2, 5, 3]);
+
+        for i in 0..32 {
+            // Dyadic coordinates, so that moving them by whole periods keeps
+            // their position within the lattice cell exact.
+            let t = i as f64 * 0.1875 - 2.03125;
+
+            for &(a, b) in [
+                ([0.0, t], [4.0, t]),
+                ([t, 0.25], [t, 3.25]),
+                ([t, t], [t - 8.0, t + 6.0]),
+            ]
+            .iter()
+            {
+                assert_eq!(perlin.get(a), perlin.get(b));
+                assert_eq!(value.get(a), value.get(b));
+            }
+
+            assert_eq!(
+                perlin_3d.get([t, 0.375, 0.625]),
+                perlin_3d.get([t + 2.0, 5.375, -2.375])
+            );
+        }
+
+        // Without a period, the noise does not repeat.
+        assert_ne!(
+            Perlin::new(5).get([0.3, 0.4]),
+            Perlin::new(5).get([4.3, 0.4])
+        );
+    }
+
+    #[test]
+    fn test_lattice_debug() {
+        let perlin = Perlin::new(5);
+
+        let sample = perlin.lattice_debug([1.25, -0.75]);
+        assert_eq!([1, -1], sample.cell);
+        assert_eq!([0.25, 0.25], sample.fraction);
+        assert_eq!(4, sample.corners.len());
+
+        let sample = perlin
+            .set_frequencies([2.0, 0.5, 1.0])
+            .lattice_debug([1.25, -0.75, 3.5]);
+        assert_eq!([2, -1, 3], sample.cell);
+        assert_eq!([0.5, 0.625, 0.5], sample.fraction);
+        assert_eq!(8, sample.corners.len());
+
+        // At a lattice point, the noise is the value of its own corner, which
+        // is always zero.
+        let sample = perlin.lattice_debug([3.0, -2.0, 1.0, 4.0]);
+        assert_eq!([0.0; 4], sample.fraction);
+        assert_eq!(0.0, sample.corners[0]);
+        assert_eq!(16, sample.corners.len());
+
+        // Each corner value is the dot product of a gradient whose components
+        // are 0 or +-1 with the offset from the corner.
+        for i in 0..32 {
+            let point = [i as f64 * 0.173 - 3.1, i as f64 * 0.291 + 0.4];
+            let sample = perlin.lattice_debug(point);
+            for (index, corner) in sample.corners.iter().enumerate() {
+                let (x, y) = (
+                    sample.fraction[0] - (index & 1) as f64,
+                    sample.fraction[1] - (index >> 1 & 1) as f64,
+                );
+                assert!(
+                    [x + y, -x + y, x - y, -x - y].contains(corner),
+                    "{} is not a corner value at {:?}",
+                    corner,
+                    point
+                );
+            }
+        }
+    }
+
+    fn assert_seeded_consistently<Source>(new: fn(u32) -> Source)
+    where
+        Source: Default + Seedable + NoiseFn<f64, 2>,
+    {
+        let point = [0.37, -1.21];
+        let source = new(9);
+        let reseeded = Source::default().set_seed(9);
+
+        assert_eq!(source.get(point), source.get(point));
+        assert_eq!(source.get(point), new(9).get(point));
+        assert_eq!(source.get(point), reseeded.get(point));
+        assert_ne!(source.get(point), new(10).get(point));
+    }
+
+    #[test]
+    fn test_new_builds_table_from_seed() {
+        assert_seeded_consistently(Perlin::new);
+        assert_seeded_consistently(PerlinSurflet::new);
+        assert_seeded_consistently(Value::new);
+        assert_seeded_consistently(Simplex::new);
+        assert_seeded_consistently(OpenSimplex::new);
+        assert_seeded_consistently(SuperSimplex::new);
     }
 }
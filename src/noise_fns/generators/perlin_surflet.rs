@@ -1,7 +1,8 @@
 use crate::{
     core::perlin_surflet::*,
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    math::{axis_frequencies, scale_axes},
+    noise_fns::{Frequency, NoiseFn, Seedable},
+    permutationtable::{DefaultHasher, NoiseHasher, PermutationTable, SeedableHasher},
 };
 
 /// Noise function that outputs 2/3/4-dimensional Perlin noise.
@@ -9,20 +10,49 @@ use crate::{
 /// THis is a variant of original perlin noise, based on the principles of simplex noise to
 /// calculate the values at a point using wavelets instead of interpolated gradients.
 #[derive(Clone, Copy, Debug)]
-pub struct PerlinSurflet {
+pub struct PerlinSurflet<H = DefaultHasher> {
     seed: u32,
-    perm_table: PermutationTable,
+    perm_table: H,
+    frequencies: [f64; 4],
 }
 
 impl PerlinSurflet {
     pub const DEFAULT_SEED: u32 = 0;
 
     pub fn new(seed: u32) -> Self {
+        Self::with_hasher(seed, PermutationTable::new(seed))
+    }
+}
+
+impl<H> PerlinSurflet<H>
+where
+    H: NoiseHasher,
+{
+    /// Creates the noise function using the given hasher, which should have
+    /// been constructed from `seed`.
+    pub fn with_hasher(seed: u32, hasher: H) -> Self {
         Self {
             seed,
-            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            perm_table: hasher,
+            frequencies: [1.0; 4],
+        }
+    }
+
+    /// Sets the frequency of each input axis, in order. The input point is
+    /// scaled per axis before sampling, which is equivalent to wrapping the
+    /// generator in `ScalePoint`. Axes without a frequency use 1.0.
+    pub fn set_frequencies<const DIM: usize>(self, frequencies: [f64; DIM]) -> Self {
+        Self {
+            frequencies: axis_frequencies(frequencies),
+            ..self
         }
     }
+
+    /// Returns the frequency of each input axis, in order. The default is 1.0
+    /// for every axis.
+    pub fn frequencies(&self) -> [f64; 4] {
+        self.frequencies
+    }
 }
 
 impl Default for PerlinSurflet {
@@ -31,7 +61,10 @@ impl Default for PerlinSurflet {
     }
 }
 
-impl Seedable for PerlinSurflet {
+impl<H> Seedable for PerlinSurflet<H>
+where
+    H: SeedableHasher,
+{
     /// Sets the seed value for Perlin noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -42,7 +75,8 @@ impl Seedable for PerlinSurflet {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: H::from_seed(seed),
+            ..self
         }
     }
 
@@ -51,23 +85,56 @@ impl Seedable for PerlinSurflet {
     }
 }
 
+impl<H> Frequency for PerlinSurflet<H> {
+    /// Sets the same frequency on every input axis.
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self {
+            frequencies: [frequency; 4],
+            ..self
+        }
+    }
+
+    /// Returns the frequency of the x axis.
+    fn frequency(&self) -> f64 {
+        self.frequencies[0]
+    }
+}
+
 /// 2-dimensional perlin noise
-impl NoiseFn<f64, 2> for PerlinSurflet {
+impl<H> NoiseFn<f64, 2> for PerlinSurflet<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
-        perlin_surflet_2d(point.into(), &self.perm_table)
+        perlin_surflet_2d(
+            scale_axes(point.into(), &self.frequencies),
+            &self.perm_table,
+        )
     }
 }
 
 /// 3-dimensional perlin noise
-impl NoiseFn<f64, 3> for PerlinSurflet {
+impl<H> NoiseFn<f64, 3> for PerlinSurflet<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
-        perlin_surflet_3d(point.into(), &self.perm_table)
+        perlin_surflet_3d(
+            scale_axes(point.into(), &self.frequencies),
+            &self.perm_table,
+        )
     }
 }
 
 /// 4-dimensional perlin noise
-impl NoiseFn<f64, 4> for PerlinSurflet {
+impl<H> NoiseFn<f64, 4> for PerlinSurflet<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
-        perlin_surflet_4d(point.into(), &self.perm_table)
+        perlin_surflet_4d(
+            scale_axes(point.into(), &self.frequencies),
+            &self.perm_table,
+        )
     }
 }
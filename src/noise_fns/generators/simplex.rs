@@ -1,26 +1,68 @@
 use crate::{
-    core::simplex::*,
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    core::{perlin::LatticeSample, simplex::*},
+    math::{axis_frequencies, scale_axes},
+    noise_fns::{Frequency, NoiseFn, NoiseFnGradient, Seedable},
+    permutationtable::{DefaultHasher, NoiseHasher, PermutationTable, SeedableHasher},
 };
 
 /// Noise function that outputs N-dimensional Simplex noise.
 ///
 #[derive(Clone, Copy, Debug)]
-pub struct Simplex {
+pub struct Simplex<H = DefaultHasher> {
     seed: u32,
-    hasher: PermutationTable,
+    hasher: H,
+    frequencies: [f64; 4],
 }
 
 impl Simplex {
     pub const DEFAULT_SEED: u32 = 0;
 
     pub fn new(seed: u32) -> Self {
-        Simplex {
+        Self::with_hasher(seed, PermutationTable::new(seed))
+    }
+}
+
+impl<H> Simplex<H>
+where
+    H: NoiseHasher,
+{
+    /// Creates the noise function using the given hasher, which should have
+    /// been constructed from `seed`.
+    pub fn with_hasher(seed: u32, hasher: H) -> Self {
+        Self {
             seed,
-            hasher: PermutationTable::new(seed),
+            hasher,
+            frequencies: [1.0; 4],
+        }
+    }
+
+    /// Sets the frequency of each input axis, in order. The input point is
+    /// scaled per axis before sampling, which is equivalent to wrapping the
+    /// generator in `ScalePoint`. Axes without a frequency use 1.0.
+    pub fn set_frequencies<const DIM: usize>(self, frequencies: [f64; DIM]) -> Self {
+        Self {
+            frequencies: axis_frequencies(frequencies),
+            ..self
         }
     }
+
+    /// Returns the frequency of each input axis, in order. The default is 1.0
+    /// for every axis.
+    pub fn frequencies(&self) -> [f64; 4] {
+        self.frequencies
+    }
+
+    /// Returns the cell of the skewed lattice containing `point`, the offset
+    /// of `point` from the origin of that cell, and the gradient dot products
+    /// at the corners of the simplex containing `point` before they are
+    /// attenuated and summed. This is a diagnostics aid for verifying the
+    /// gradients of the lattice.
+    pub fn lattice_debug<const DIM: usize>(
+        &self,
+        point: impl Into<[f64; DIM]>,
+    ) -> LatticeSample<DIM> {
+        simplex_lattice_sample(scale_axes(point.into(), &self.frequencies), &self.hasher)
+    }
 }
 
 impl Default for Simplex {
@@ -29,7 +71,10 @@ impl Default for Simplex {
     }
 }
 
-impl Seedable for Simplex {
+impl<H> Seedable for Simplex<H>
+where
+    H: SeedableHasher,
+{
     /// Sets the seed value for Simplex noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -40,7 +85,8 @@ impl Seedable for Simplex {
         // Otherwise, regenerate the permutation table based on the new seed.
         Simplex {
             seed,
-            hasher: PermutationTable::new(seed),
+            hasher: H::from_seed(seed),
+            ..self
         }
     }
 
@@ -49,29 +95,127 @@ impl Seedable for Simplex {
     }
 }
 
+impl<H> Frequency for Simplex<H> {
+    /// Sets the same frequency on every input axis.
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self {
+            frequencies: [frequency; 4],
+            ..self
+        }
+    }
+
+    /// Returns the frequency of the x axis.
+    fn frequency(&self) -> f64 {
+        self.frequencies[0]
+    }
+}
+
 /// 2-dimensional Simplex noise
-impl NoiseFn<f64, 2> for Simplex {
+impl<H> NoiseFn<f64, 2> for Simplex<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
-        let (result, _) = simplex_2d(point.into(), &self.hasher);
+        let (result, _) = simplex_2d(scale_axes(point.into(), &self.frequencies), &self.hasher);
 
         result
     }
 }
 
 /// 3-dimensional Simplex noise
-impl NoiseFn<f64, 3> for Simplex {
+impl<H> NoiseFn<f64, 3> for Simplex<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
-        let (result, _) = simplex_3d(point.into(), &self.hasher);
+        let (result, _) = simplex_3d(scale_axes(point.into(), &self.frequencies), &self.hasher);
 
         result
     }
 }
 
 /// 4-dimensional Simplex noise
-impl NoiseFn<f64, 4> for Simplex {
+impl<H> NoiseFn<f64, 4> for Simplex<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
-        let (result, _) = simplex_4d(point.into(), &self.hasher);
+        let (result, _) = simplex_4d(scale_axes(point.into(), &self.frequencies), &self.hasher);
 
         result
     }
 }
+
+impl<H> NoiseFnGradient<2> for Simplex<H>
+where
+    H: NoiseHasher,
+{
+    fn get_with_gradient(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        let (result, mut gradient) = simplex_2d(scale_axes(point, &self.frequencies), &self.hasher);
+        scale_gradient(&mut gradient, &self.frequencies);
+
+        (result, gradient)
+    }
+}
+
+impl<H> NoiseFnGradient<3> for Simplex<H>
+where
+    H: NoiseHasher,
+{
+    fn get_with_gradient(&self, point: [f64; 3]) -> (f64, [f64; 3]) {
+        let (result, mut gradient) = simplex_3d(scale_axes(point, &self.frequencies), &self.hasher);
+        scale_gradient(&mut gradient, &self.frequencies);
+
+        (result, gradient)
+    }
+}
+
+impl<H> NoiseFnGradient<4> for Simplex<H>
+where
+    H: NoiseHasher,
+{
+    fn get_with_gradient(&self, point: [f64; 4]) -> (f64, [f64; 4]) {
+        let (result, mut gradient) = simplex_4d(scale_axes(point, &self.frequencies), &self.hasher);
+        scale_gradient(&mut gradient, &self.frequencies);
+
+        (result, gradient)
+    }
+}
+
+/// Applies the chain rule for the per-axis frequencies to a gradient taken
+/// with respect to the scaled input point.
+fn scale_gradient<const DIM: usize>(gradient: &mut [f64; DIM], frequencies: &[f64; 4]) {
+    for (derivative, frequency) in gradient.iter_mut().zip(frequencies) {
+        *derivative *= frequency;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lattice_debug() {
+        let simplex = Simplex::new(5);
+
+        let sample = simplex.lattice_debug([1.25, -0.75]);
+        assert_eq!([1, -1], sample.cell);
+        assert_eq!([0.25, 0.25], sample.fraction);
+        assert_eq!(3, sample.corners.len());
+
+        let sample = simplex
+            .set_frequencies([2.0, 0.5, 1.0])
+            .lattice_debug([0.25, 0.5, 0.0]);
+        assert_eq!([0, 0, 0], sample.cell);
+        assert_eq!([0.5, 0.25, 0.0], sample.fraction);
+        assert_eq!(4, sample.corners.len());
+
+        // At the origin of a cell, the offset from the first corner is zero,
+        // and so is its gradient dot product.
+        let sample = simplex.lattice_debug([0.0; 4]);
+        assert_eq!([0; 4], sample.cell);
+        assert_eq!([0.0; 4], sample.fraction);
+        assert_eq!(0.0, sample.corners[0]);
+        assert_eq!(5, sample.corners.len());
+    }
+}
@@ -1,25 +1,55 @@
 use crate::{
     core::super_simplex::*,
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    math::{axis_frequencies, scale_axes},
+    noise_fns::{Frequency, NoiseFn, Seedable},
+    permutationtable::{DefaultHasher, NoiseHasher, PermutationTable, SeedableHasher},
 };
 
 /// Noise function that outputs 2/3-dimensional Super Simplex noise.
 #[derive(Clone, Copy, Debug)]
-pub struct SuperSimplex {
+pub struct SuperSimplex<H = DefaultHasher> {
     seed: u32,
-    perm_table: PermutationTable,
+    perm_table: H,
+    frequencies: [f64; 4],
 }
 
 impl SuperSimplex {
     pub const DEFAULT_SEED: u32 = 0;
 
     pub fn new(seed: u32) -> Self {
+        Self::with_hasher(seed, PermutationTable::new(seed))
+    }
+}
+
+impl<H> SuperSimplex<H>
+where
+    H: NoiseHasher,
+{
+    /// Creates the noise function using the given hasher, which should have
+    /// been constructed from `seed`.
+    pub fn with_hasher(seed: u32, hasher: H) -> Self {
         Self {
             seed,
-            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            perm_table: hasher,
+            frequencies: [1.0; 4],
+        }
+    }
+
+    /// Sets the frequency of each input axis, in order. The input point is
+    /// scaled per axis before sampling, which is equivalent to wrapping the
+    /// generator in `ScalePoint`. Axes without a frequency use 1.0.
+    pub fn set_frequencies<const DIM: usize>(self, frequencies: [f64; DIM]) -> Self {
+        Self {
+            frequencies: axis_frequencies(frequencies),
+            ..self
         }
     }
+
+    /// Returns the frequency of each input axis, in order. The default is 1.0
+    /// for every axis.
+    pub fn frequencies(&self) -> [f64; 4] {
+        self.frequencies
+    }
 }
 
 impl Default for SuperSimplex {
@@ -28,7 +58,10 @@ impl Default for SuperSimplex {
     }
 }
 
-impl Seedable for SuperSimplex {
+impl<H> Seedable for SuperSimplex<H>
+where
+    H: SeedableHasher,
+{
     /// Sets the seed value for Super Simplex noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -39,7 +72,8 @@ impl Seedable for SuperSimplex {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: H::from_seed(seed),
+            ..self
         }
     }
 
@@ -48,16 +82,43 @@ impl Seedable for SuperSimplex {
     }
 }
 
+impl<H> Frequency for SuperSimplex<H> {
+    /// Sets the same frequency on every input axis.
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self {
+            frequencies: [frequency; 4],
+            ..self
+        }
+    }
+
+    /// Returns the frequency of the x axis.
+    fn frequency(&self) -> f64 {
+        self.frequencies[0]
+    }
+}
+
 /// 2-dimensional Super Simplex noise
-impl NoiseFn<f64, 2> for SuperSimplex {
+impl<H> NoiseFn<f64, 2> for SuperSimplex<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
-        super_simplex_2d(point.into(), &self.perm_table)
+        super_simplex_2d(
+            scale_axes(point.into(), &self.frequencies),
+            &self.perm_table,
+        )
     }
 }
 
 /// 3-dimensional Super Simplex noise
-impl NoiseFn<f64, 3> for SuperSimplex {
+impl<H> NoiseFn<f64, 3> for SuperSimplex<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
-        super_simplex_3d(point.into(), &self.perm_table)
+        super_simplex_3d(
+            scale_axes(point.into(), &self.frequencies),
+            &self.perm_table,
+        )
     }
 }
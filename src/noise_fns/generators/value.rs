@@ -1,25 +1,107 @@
 use crate::{
-    core::value::{value_2d, value_3d, value_4d},
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    core::{
+        perlin::LatticeSample,
+        value::{value_2d, value_3d, value_4d, value_lattice_sample},
+    },
+    math::{axis_frequencies, scale_axes},
+    noise_fns::{Frequency, NoiseFn, Seedable},
+    permutationtable::{
+        DefaultHasher, NoiseHasher, PeriodicHasher, PermutationTable, SeedableHasher,
+    },
 };
 
 /// Noise function that outputs 2/3/4-dimensional Value noise.
 #[derive(Clone, Copy, Debug)]
-pub struct Value {
+pub struct Value<H = DefaultHasher> {
     seed: u32,
-    perm_table: PermutationTable,
+    perm_table: H,
+    frequencies: [f64; 4],
+    period: [u32; 4],
 }
 
 impl Value {
     pub const DEFAULT_SEED: u32 = 0;
 
     pub fn new(seed: u32) -> Self {
+        Self::with_hasher(seed, PermutationTable::new(seed))
+    }
+}
+
+impl<H> Value<H>
+where
+    H: NoiseHasher,
+{
+    /// Creates the noise function using the given hasher, which should have
+    /// been constructed from `seed`.
+    pub fn with_hasher(seed: u32, hasher: H) -> Self {
         Self {
             seed,
-            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            perm_table: hasher,
+            frequencies: [1.0; 4],
+            period: [0; 4],
+        }
+    }
+
+    /// Sets the frequency of each input axis, in order. The input point is
+    /// scaled per axis before sampling, which is equivalent to wrapping the
+    /// generator in `ScalePoint`. Axes without a frequency use 1.0.
+    pub fn set_frequencies<const DIM: usize>(self, frequencies: [f64; DIM]) -> Self {
+        Self {
+            frequencies: axis_frequencies(frequencies),
+            ..self
+        }
+    }
+
+    /// Returns the frequency of each input axis, in order. The default is 1.0
+    /// for every axis.
+    pub fn frequencies(&self) -> [f64; 4] {
+        self.frequencies
+    }
+
+    /// Sets the period of the integer lattice along each input axis, in
+    /// order, so that the noise tiles exactly: sampling a point and the same
+    /// point moved by a whole period along an axis gives the same output. A
+    /// period of 0 disables tiling along its axis, as do missing axes.
+    ///
+    /// The period is measured in lattice cells, i.e. after the input point is
+    /// scaled by the frequencies.
+    pub fn set_period<const DIM: usize>(self, period: [u32; DIM]) -> Self {
+        assert!(DIM <= 4, "at most four axis periods are supported");
+
+        let mut axes = [0; 4];
+        axes[..DIM].copy_from_slice(&period);
+        Self {
+            period: axes,
+            ..self
         }
     }
+
+    /// Returns the period of the lattice along each input axis, in order. The
+    /// default is 0, i.e. no tiling, for every axis.
+    pub fn period(&self) -> [u32; 4] {
+        self.period
+    }
+
+    /// Returns the lattice cell containing `point`, the position of `point`
+    /// within it, and the values at its corners before they are faded and
+    /// interpolated. This is a diagnostics aid for verifying the values of
+    /// the lattice.
+    pub fn lattice_debug<const DIM: usize>(
+        &self,
+        point: impl Into<[f64; DIM]>,
+    ) -> LatticeSample<DIM> {
+        let point = scale_axes(point.into(), &self.frequencies);
+
+        if self.is_periodic() {
+            value_lattice_sample(point, &PeriodicHasher::new(&self.perm_table, &self.period))
+        } else {
+            value_lattice_sample(point, &self.perm_table)
+        }
+    }
+
+    fn is_periodic(&self) -> bool {
+        self.period.iter().any(|&period| period != 0)
+    }
 }
 
 impl Default for Value {
@@ -28,7 +110,10 @@ impl Default for Value {
     }
 }
 
-impl Seedable for Value {
+impl<H> Seedable for Value<H>
+where
+    H: SeedableHasher,
+{
     /// Sets the seed value for Value noise
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -39,7 +124,8 @@ impl Seedable for Value {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: H::from_seed(seed),
+            ..self
         }
     }
 
@@ -48,23 +134,101 @@ impl Seedable for Value {
     }
 }
 
+impl<H> Frequency for Value<H> {
+    /// Sets the same frequency on every input axis.
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self {
+            frequencies: [frequency; 4],
+            ..self
+        }
+    }
+
+    /// Returns the frequency of the x axis.
+    fn frequency(&self) -> f64 {
+        self.frequencies[0]
+    }
+}
+
 /// 2-dimensional value noise
-impl NoiseFn<f64, 2> for Value {
+impl<H> NoiseFn<f64, 2> for Value<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
-        value_2d(point.into(), &self.perm_table)
+        let point = scale_axes(point.into(), &self.frequencies);
+
+        if self.is_periodic() {
+            value_2d(point, &PeriodicHasher::new(&self.perm_table, &self.period))
+        } else {
+            value_2d(point, &self.perm_table)
+        }
     }
 }
 
 /// 3-dimensional value noise
-impl NoiseFn<f64, 3> for Value {
+impl<H> NoiseFn<f64, 3> for Value<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
-        value_3d(point.into(), &self.perm_table)
+        let point = scale_axes(point.into(), &self.frequencies);
+
+        if self.is_periodic() {
+            value_3d(point, &PeriodicHasher::new(&self.perm_table, &self.period))
+        } else {
+            value_3d(point, &self.perm_table)
+        }
     }
 }
 
 /// 4-dimensional value noise
-impl NoiseFn<f64, 4> for Value {
+impl<H> NoiseFn<f64, 4> for Value<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
-        value_4d(point.into(), &self.perm_table)
+        let point = scale_axes(point.into(), &self.frequencies);
+
+        if self.is_periodic() {
+            value_4d(point, &PeriodicHasher::new(&self.perm_table, &self.period))
+        } else {
+            value_4d(point, &self.perm_table)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lattice_debug() {
+        let value = Value::new(5);
+
+        let sample = value.lattice_debug([1.25, -0.75]);
+        assert_eq!([1, -1], sample.cell);
+        assert_eq!([0.25, 0.25], sample.fraction);
+        assert_eq!(4, sample.corners.len());
+
+        let sample = value
+            .set_frequencies([2.0, 0.5, 1.0])
+            .lattice_debug([1.25, -0.75, 3.5]);
+        assert_eq!([2, -1, 3], sample.cell);
+        assert_eq!([0.5, 0.625, 0.5], sample.fraction);
+        assert_eq!(8, sample.corners.len());
+
+        // At a lattice point, the noise is the value of its own corner.
+        let point = [3.0, -2.0, 1.0, 4.0];
+        let sample = value.lattice_debug(point);
+        assert_eq!([0.0; 4], sample.fraction);
+        assert_eq!(value.get(point), sample.corners[0]);
+        assert_eq!(16, sample.corners.len());
+
+        // Tiling wraps the corners of the cells at the edge of the period.
+        let tiled = value.set_period([2, 2]);
+        assert_eq!(
+            tiled.lattice_debug([0.5, 0.5]).corners,
+            tiled.lattice_debug([2.5, 2.5]).corners
+        );
     }
 }
@@ -1,19 +1,30 @@
 use crate::{
     core::worley::*,
     math::vectors::*,
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    noise_fns::{Frequency, NoiseFn, Seedable},
+    permutationtable::{
+        DefaultHasher, NoiseHasher, PeriodicHasher, PermutationTable, SeedableHasher,
+    },
 };
-use alloc::rc::Rc;
+use alloc::{sync::Arc, vec::Vec};
 
 pub use crate::core::worley::ReturnType;
 
 /// Noise function that outputs Worley noise.
+///
+/// Every dimension hashes the integer coordinates of a lattice cell with the
+/// same hasher, so 2D, 3D and 4D Worley noise with the same seed share their
+/// cell hashing: the cell `[x, y]` in 2D and the cell `[x, y, z]` in 3D are
+/// both looked up in the one permutation table built from the seed. The hash
+/// covers every coordinate of the cell, and the seed points are offset along
+/// every axis, so 2D Worley noise is not a slice of 3D Worley noise. To mix
+/// them consistently, sample the 3D noise at a fixed _z_ instead, for example
+/// with [`slice_z`](crate::NoiseFn::slice_z).
 #[derive(Clone)]
-pub struct Worley {
+pub struct Worley<H = DefaultHasher> {
     /// Specifies the distance function to use when calculating the boundaries of
     /// the cell.
-    pub distance_function: Rc<DistanceFunction>,
+    pub distance_function: Arc<DistanceFunction>,
 
     /// Signifies whether the distance from the borders of the cell should be returned, or the
     /// value for the cell.
@@ -23,32 +34,68 @@ pub struct Worley {
     pub frequency: f64,
 
     seed: u32,
-    perm_table: PermutationTable,
+    smoothness: f64,
+    normalized: bool,
+    points_per_cell: usize,
+    period: [u32; 4],
+    perm_table: H,
+    feature_cache: Option<Arc<FeatureCache>>,
 }
 
-pub type DistanceFunction = dyn Fn(&[f64], &[f64]) -> f64;
+pub type DistanceFunction = dyn Fn(&[f64], &[f64]) -> f64 + Send + Sync;
 
 impl Worley {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
 
+    /// Upper bound of the Euclidean distance from a 2D point to the nearest
+    /// seed point, `(sqrt(2) + 1) / 2`: the lattice point nearest to the point
+    /// is at most `sqrt(2) / 2` away, and its seed point at most 0.5 further.
+    pub const MAX_DISTANCE_2D: f64 = 1.207_106_781_186_547_5;
+
+    /// Upper bound of the Euclidean distance from a 3D point to the nearest
+    /// seed point, `(sqrt(3) + 1) / 2`.
+    pub const MAX_DISTANCE_3D: f64 = 1.366_025_403_784_438_6;
+
+    /// Upper bound of the Euclidean distance from a 4D point to the nearest
+    /// seed point, `(sqrt(4) + 1) / 2`.
+    pub const MAX_DISTANCE_4D: f64 = 1.5;
+
     pub fn new(seed: u32) -> Self {
+        Self::with_hasher(seed, PermutationTable::new(seed))
+    }
+}
+
+impl<H> Worley<H>
+where
+    H: NoiseHasher,
+{
+    /// Creates the noise function using the given hasher, which should have
+    /// been constructed from `seed`.
+    pub fn with_hasher(seed: u32, hasher: H) -> Self {
         Self {
-            perm_table: PermutationTable::new(seed),
+            perm_table: hasher,
             seed,
-            distance_function: Rc::new(distance_functions::euclidean),
+            distance_function: Arc::new(distance_functions::euclidean),
             return_type: ReturnType::Value,
-            frequency: Self::DEFAULT_FREQUENCY,
+            frequency: Worley::DEFAULT_FREQUENCY,
+            smoothness: 0.0,
+            normalized: false,
+            points_per_cell: 1,
+            period: [0; 4],
+            feature_cache: None,
         }
     }
 
-    /// Sets the distance function used by the Worley cells.
+    /// Sets the distance function used by the Worley cells. See
+    /// [`distance_functions`] for the built-in ones; the range of the
+    /// distances output for [`ReturnType::Distance`] depends on it.
     pub fn set_distance_function<F>(self, function: F) -> Self
     where
-        F: Fn(&[f64], &[f64]) -> f64 + 'static,
+        F: Fn(&[f64], &[f64]) -> f64 + Send + Sync + 'static,
     {
         Self {
-            distance_function: Rc::new(function),
+            distance_function: Arc::new(function),
             ..self
         }
     }
@@ -66,6 +113,321 @@ impl Worley {
     pub fn set_frequency(self, frequency: f64) -> Self {
         Self { frequency, ..self }
     }
+
+    /// Feathers the cell boundaries of [`ReturnType::Distance`] by taking a
+    /// smooth minimum of the distances to the nearby seed points, which
+    /// avoids aliasing when the noise is minified.
+    ///
+    /// Larger values of `k` give smoother boundaries and lower distances. As
+    /// `k` goes to zero, the output converges to the hard distance to the
+    /// nearest seed point; a `k` of zero disables smoothing. Other return
+    /// types are unaffected.
+    pub fn smooth(self, k: f64) -> Self {
+        Self {
+            smoothness: k,
+            ..self
+        }
+    }
+
+    /// Returns the smoothness of the cell boundaries. The default is 0.0, i.e.
+    /// no smoothing.
+    pub fn smoothness(&self) -> f64 {
+        self.smoothness
+    }
+
+    /// Rescales the output of [`ReturnType::Distance`] so that distances from
+    /// zero up to the largest possible distance to the nearest seed point,
+    /// [`MAX_DISTANCE_2D`], [`MAX_DISTANCE_3D`] or [`MAX_DISTANCE_4D`]
+    /// depending on the dimension, span -1.0 to 1.0.
+    ///
+    /// Without normalization, distances are mapped from 0.0 to 1.0 onto -1.0
+    /// to 1.0 in every dimension, so the output can exceed 1.0 and its range
+    /// differs between dimensions. The bounds are for the Euclidean distance
+    /// function; distances that exceed them, such as from other distance
+    /// functions, or that fall below zero through smoothing, are clamped.
+    /// Other return types are unaffected.
+    ///
+    /// [`MAX_DISTANCE_2D`]: Worley::MAX_DISTANCE_2D
+    /// [`MAX_DISTANCE_3D`]: Worley::MAX_DISTANCE_3D
+    /// [`MAX_DISTANCE_4D`]: Worley::MAX_DISTANCE_4D
+    pub fn normalized(self, normalized: bool) -> Self {
+        Self { normalized, ..self }
+    }
+
+    /// Returns whether the output of [`ReturnType::Distance`] is normalized.
+    /// The default is `false`.
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Sets the number of feature points in each cell, of which the nearest
+    /// is used. Several points per cell give irregular, more organic cells
+    /// than the single seed point of regular Worley noise. Sampling is slower
+    /// with more than one point per cell. The default is 1.
+    pub fn set_points_per_cell(self, points_per_cell: usize) -> Self {
+        Self {
+            points_per_cell: points_per_cell.max(1),
+            ..self
+        }
+    }
+
+    /// Returns the number of feature points in each cell. The default is 1.
+    pub fn points_per_cell(&self) -> usize {
+        self.points_per_cell
+    }
+
+    /// Sets the period of the cells along each input axis, in order, so that
+    /// the cellular pattern tiles exactly: sampling a point and the same point
+    /// moved by a whole period along an axis gives the same output. A period
+    /// of 0 disables tiling along its axis, as do missing axes.
+    ///
+    /// The period is measured in cells, i.e. after the input point is scaled
+    /// by the frequency.
+    pub fn set_period<const DIM: usize>(self, period: [u32; DIM]) -> Self {
+        assert!(DIM <= 4, "at most four axis periods are supported");
+
+        let mut axes = [0; 4];
+        axes[..DIM].copy_from_slice(&period);
+        Self {
+            period: axes,
+            ..self
+        }
+    }
+
+    /// Returns the period of the cells along each input axis, in order. The
+    /// default is 0, i.e. no tiling, for every axis.
+    pub fn period(&self) -> [u32; 4] {
+        self.period
+    }
+
+    /// Hashes the feature points of every cell that sampling a point within
+    /// `bounds` can visit, so that sampling that region doesn't need to hash
+    /// them again. `bounds` holds the lower and upper bound of the region
+    /// along each input axis, in order, and only affects sampling in as many
+    /// dimensions as it has axes.
+    ///
+    /// This trades memory for speed when densely sampling a fixed region: the
+    /// cache holds one entry per feature point of each cell, and points
+    /// outside of the region are sampled as usual. The output is exactly the
+    /// same with or without the cache. Clones share the cache, so it can be
+    /// computed once and sampled from several threads.
+    ///
+    /// The cache covers the region at the current frequency, and is discarded
+    /// when the seed changes.
+    pub fn precompute_region<const DIM: usize>(self, bounds: [(f64, f64); DIM]) -> Self {
+        assert!(DIM <= 4, "at most four axis bounds are supported");
+
+        let mut origin = [0; 4];
+        let mut size = [1; 4];
+        for (axis, &(lower, upper)) in bounds.iter().enumerate() {
+            let (lower, upper) = (lower * self.frequency, upper * self.frequency);
+            // Sampling visits the cells surrounding the lattice point nearest
+            // to the point.
+            origin[axis] = lower.min(upper).floor() as isize - 1;
+            size[axis] = (lower.max(upper).ceil() as isize + 2 - origin[axis]) as usize;
+        }
+
+        let cells = size[..DIM].iter().product::<usize>();
+        let mut hashes = Vec::with_capacity(cells * self.points_per_cell);
+        for i in 0..cells {
+            // The cell coordinates, followed by the index of the feature
+            // point, keyed the same way as when sampling.
+            let mut key = [0; 5];
+            let mut digits = i;
+            for axis in 0..DIM {
+                key[axis] = origin[axis] + (digits % size[axis]) as isize;
+                digits /= size[axis];
+            }
+
+            for index in 0..self.points_per_cell {
+                hashes.push(if index == 0 {
+                    self.perm_table.hash(&key[..DIM])
+                } else {
+                    key[DIM] = index as isize;
+                    self.perm_table.hash(&key[..=DIM])
+                });
+            }
+        }
+
+        Self {
+            feature_cache: Some(Arc::new(FeatureCache {
+                dim: DIM,
+                origin,
+                size,
+                points_per_cell: self.points_per_cell,
+                hashes,
+            })),
+            ..self
+        }
+    }
+
+    /// Returns the feature point cache, if it was computed for sampling in
+    /// `dim` dimensions.
+    fn feature_cache(&self, dim: usize) -> Option<&FeatureCache> {
+        self.feature_cache
+            .as_deref()
+            .filter(|cache| cache.dim == dim)
+    }
+
+    /// Applies the normalization set by `normalized` to an output value,
+    /// given the largest distance to the nearest seed point.
+    fn normalize(&self, value: f64, max_distance: f64) -> f64 {
+        if self.normalized && matches!(self.return_type, ReturnType::Distance) {
+            let distance = (value + 1.0) / 2.0;
+            (distance / max_distance * 2.0 - 1.0).clamp(-1.0, 1.0)
+        } else {
+            value
+        }
+    }
+
+    fn is_periodic(&self) -> bool {
+        self.period.iter().any(|&period| period != 0)
+    }
+
+    /// Moves the point into the first period along each periodic axis. The
+    /// seed points are positioned relative to the cells rather than hashed
+    /// from the point, so the point itself must be wrapped for both copies of
+    /// a tile to measure the same distances.
+    fn wrap<const DIM: usize>(&self, mut point: [f64; DIM]) -> [f64; DIM] {
+        for (coordinate, &period) in point.iter_mut().zip(self.period.iter()) {
+            if period != 0 {
+                let period = f64::from(period);
+                *coordinate %= period;
+                if *coordinate < 0.0 {
+                    *coordinate += period;
+                }
+            }
+        }
+        point
+    }
+
+    fn get_2d<NH>(&self, hasher: &NH, point: [f64; 2]) -> f64
+    where
+        NH: NoiseHasher + ?Sized,
+    {
+        if self.is_periodic() {
+            let hasher = PeriodicHasher::new(hasher, &self.period);
+            self.sample_2d(&hasher, self.wrap(point))
+        } else {
+            self.sample_2d(hasher, point)
+        }
+    }
+
+    fn get_3d<NH>(&self, hasher: &NH, point: [f64; 3]) -> f64
+    where
+        NH: NoiseHasher + ?Sized,
+    {
+        if self.is_periodic() {
+            let hasher = PeriodicHasher::new(hasher, &self.period);
+            self.sample_3d(&hasher, self.wrap(point))
+        } else {
+            self.sample_3d(hasher, point)
+        }
+    }
+
+    fn get_4d<NH>(&self, hasher: &NH, point: [f64; 4]) -> f64
+    where
+        NH: NoiseHasher + ?Sized,
+    {
+        if self.is_periodic() {
+            let hasher = PeriodicHasher::new(hasher, &self.period);
+            self.sample_4d(&hasher, self.wrap(point))
+        } else {
+            self.sample_4d(hasher, point)
+        }
+    }
+
+    fn sample_2d<NH>(&self, hasher: &NH, point: [f64; 2]) -> f64
+    where
+        NH: NoiseHasher + ?Sized,
+    {
+        if self.tests_all_points() {
+            worley_points_2d(
+                hasher,
+                &*self.distance_function,
+                self.return_type,
+                self.points_per_cell,
+                self.smoothness,
+                point,
+            )
+        } else {
+            worley_2d(hasher, &*self.distance_function, self.return_type, point)
+        }
+    }
+
+    fn sample_3d<NH>(&self, hasher: &NH, point: [f64; 3]) -> f64
+    where
+        NH: NoiseHasher + ?Sized,
+    {
+        if self.tests_all_points() {
+            worley_points_3d(
+                hasher,
+                &*self.distance_function,
+                self.return_type,
+                self.points_per_cell,
+                self.smoothness,
+                point,
+            )
+        } else {
+            worley_3d(hasher, &*self.distance_function, self.return_type, point)
+        }
+    }
+
+    fn sample_4d<NH>(&self, hasher: &NH, point: [f64; 4]) -> f64
+    where
+        NH: NoiseHasher + ?Sized,
+    {
+        if self.tests_all_points() {
+            worley_points_4d(
+                hasher,
+                &*self.distance_function,
+                self.return_type,
+                self.points_per_cell,
+                self.smoothness,
+                point,
+            )
+        } else {
+            worley_4d(hasher, &*self.distance_function, self.return_type, point)
+        }
+    }
+
+    /// Whether every feature point of the surrounding cells must be tested,
+    /// rather than only the seed points of the nearest cells.
+    fn tests_all_points(&self) -> bool {
+        self.points_per_cell > 1
+            || matches!(
+                self.return_type,
+                ReturnType::SecondDistance | ReturnType::AverageDistance
+            )
+            || (self.smoothness > 0.0 && matches!(self.return_type, ReturnType::Distance))
+    }
+
+    /// Returns the distances to the nearest and second nearest feature points
+    /// from a single search of the surrounding cells, see [`WorleyF1F2`].
+    fn f1_f2<O, const DIM: usize>(&self, point: [f64; DIM], search: O) -> (f64, f64)
+    where
+        O: Fn(&dyn NoiseHasher, [f64; DIM]) -> (f64, f64),
+    {
+        let mut point = point;
+        for coordinate in point.iter_mut() {
+            *coordinate *= self.frequency;
+        }
+
+        let cached;
+        let hasher: &dyn NoiseHasher = match self.feature_cache(DIM) {
+            Some(cache) => {
+                cached = CachedHasher::new(cache, &self.perm_table);
+                &cached
+            }
+            None => &self.perm_table,
+        };
+
+        if self.is_periodic() {
+            search(&PeriodicHasher::new(hasher, &self.period), self.wrap(point))
+        } else {
+            search(hasher, point)
+        }
+    }
 }
 
 impl Default for Worley {
@@ -74,7 +436,10 @@ impl Default for Worley {
     }
 }
 
-impl Seedable for Worley {
+impl<H> Seedable for Worley<H>
+where
+    H: SeedableHasher,
+{
     /// Sets the seed value used by the Worley cells.
     fn set_seed(self, seed: u32) -> Self {
         // If the new seed is the same as the current seed, just return self.
@@ -84,8 +449,9 @@ impl Seedable for Worley {
 
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
-            perm_table: PermutationTable::new(seed),
+            perm_table: H::from_seed(seed),
             seed,
+            feature_cache: None,
             ..self
         }
     }
@@ -95,48 +461,567 @@ impl Seedable for Worley {
     }
 }
 
-impl NoiseFn<f64, 2> for Worley {
+impl<H> Frequency for Worley<H> {
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+}
+
+impl<H> NoiseFn<f64, 2> for Worley<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
-        worley_2d(
-            &self.perm_table,
-            &*self.distance_function,
-            self.return_type,
-            (Vector2::from(point.into()) * self.frequency).into_array(),
-        )
+        let point = (Vector2::from(point.into()) * self.frequency).into_array();
+
+        let value = match self.feature_cache(2) {
+            Some(cache) => self.get_2d(&CachedHasher::new(cache, &self.perm_table), point),
+            None => self.get_2d(&self.perm_table, point),
+        };
+        self.normalize(value, Worley::MAX_DISTANCE_2D)
     }
 }
 
-impl NoiseFn<f64, 3> for Worley {
+impl<H> NoiseFn<f64, 3> for Worley<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
-        worley_3d(
-            &self.perm_table,
-            &*self.distance_function,
-            self.return_type,
-            (Vector3::from(point.into()) * self.frequency).into_array(),
-        )
+        let point = (Vector3::from(point.into()) * self.frequency).into_array();
+
+        let value = match self.feature_cache(3) {
+            Some(cache) => self.get_3d(&CachedHasher::new(cache, &self.perm_table), point),
+            None => self.get_3d(&self.perm_table, point),
+        };
+        self.normalize(value, Worley::MAX_DISTANCE_3D)
     }
 }
 
 #[allow(clippy::cognitive_complexity)]
-impl NoiseFn<f64, 4> for Worley {
+impl<H> NoiseFn<f64, 4> for Worley<H>
+where
+    H: NoiseHasher,
+{
     fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
-        worley_4d(
-            &self.perm_table,
-            &*self.distance_function,
-            self.return_type,
-            (Vector4::from(point.into()) * self.frequency).into_array(),
-        )
+        let point = (Vector4::from(point.into()) * self.frequency).into_array();
+
+        let value = match self.feature_cache(4) {
+            Some(cache) => self.get_4d(&CachedHasher::new(cache, &self.perm_table), point),
+            None => self.get_4d(&self.perm_table, point),
+        };
+        self.normalize(value, Worley::MAX_DISTANCE_4D)
     }
 }
 
-impl core::fmt::Debug for Worley {
+impl<H> core::fmt::Debug for Worley<H>
+where
+    H: core::fmt::Debug,
+{
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Worley")
             .field("distance_function", &core::format_args!("..."))
             .field("return_type", &self.return_type)
             .field("frequency", &self.frequency)
             .field("seed", &self.seed)
+            .field("smoothness", &self.smoothness)
+            .field("normalized", &self.normalized)
+            .field("points_per_cell", &self.points_per_cell)
+            .field("period", &self.period)
             .field("perm_table", &self.perm_table)
+            .field(
+                "feature_cache",
+                &self
+                    .feature_cache
+                    .as_ref()
+                    .map(|_| core::format_args!("...")),
+            )
             .finish()
     }
 }
+
+/// Noise function that outputs the difference between the distances to the
+/// second nearest and nearest feature points of a [`Worley`] function, known
+/// as F2 - F1, which gives crystalline textures with bright cell edges.
+///
+/// Both distances are found in a single search of the surrounding cells, and
+/// are available together from [`get_f1_f2`](Self::get_f1_f2). This is about
+/// half the cost of evaluating two Worley functions with
+/// [`ReturnType::Distance`] and [`ReturnType::SecondDistance`].
+///
+/// The frequency, distance function, feature points per cell, period and
+/// precomputed region of the Worley function are used; its return type,
+/// smoothing and normalization are ignored.
+#[derive(Clone, Debug)]
+pub struct WorleyF1F2<H = DefaultHasher> {
+    /// Worley function whose feature points are measured.
+    pub worley: Worley<H>,
+}
+
+impl WorleyF1F2 {
+    pub fn new(seed: u32) -> Self {
+        Self::from_worley(Worley::new(seed))
+    }
+}
+
+impl<H> WorleyF1F2<H>
+where
+    H: NoiseHasher,
+{
+    pub fn from_worley(worley: Worley<H>) -> Self {
+        Self { worley }
+    }
+
+    /// Returns the distances from `point` to the nearest and second nearest
+    /// feature points, scaled like [`ReturnType::Distance`].
+    pub fn get_f1_f2<const DIM: usize>(&self, point: impl Into<[f64; DIM]>) -> (f64, f64) {
+        let worley = &self.worley;
+        let distance_function = &*worley.distance_function;
+        let points_per_cell = worley.points_per_cell;
+        let point = point.into();
+        match DIM {
+            2 => worley.f1_f2([point[0], point[1]], |hasher, point| {
+                worley_f1_f2_2d(hasher, distance_function, points_per_cell, point)
+            }),
+            3 => worley.f1_f2([point[0], point[1], point[2]], |hasher, point| {
+                worley_f1_f2_3d(hasher, distance_function, points_per_cell, point)
+            }),
+            4 => worley.f1_f2([point[0], point[1], point[2], point[3]], |hasher, point| {
+                worley_f1_f2_4d(hasher, distance_function, points_per_cell, point)
+            }),
+            _ => panic!("F1 and F2 are only supported in 2 to 4 dimensions"),
+        }
+    }
+}
+
+impl Default for WorleyF1F2 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<H> Seedable for WorleyF1F2<H>
+where
+    H: SeedableHasher,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            worley: self.worley.set_seed(seed),
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.worley.seed()
+    }
+}
+
+impl<H> Frequency for WorleyF1F2<H> {
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self {
+            worley: Frequency::set_frequency(self.worley, frequency),
+        }
+    }
+
+    fn frequency(&self) -> f64 {
+        self.worley.frequency
+    }
+}
+
+impl<H> NoiseFn<f64, 2> for WorleyF1F2<H>
+where
+    H: NoiseHasher,
+{
+    fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
+        let (f1, f2) = self.get_f1_f2(point);
+        f2 - f1
+    }
+}
+
+impl<H> NoiseFn<f64, 3> for WorleyF1F2<H>
+where
+    H: NoiseHasher,
+{
+    fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
+        let (f1, f2) = self.get_f1_f2(point);
+        f2 - f1
+    }
+}
+
+impl<H> NoiseFn<f64, 4> for WorleyF1F2<H>
+where
+    H: NoiseHasher,
+{
+    fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
+        let (f1, f2) = self.get_f1_f2(point);
+        f2 - f1
+    }
+}
+
+/// Hashes of the feature points of every cell within a region, computed by
+/// [`Worley::precompute_region`].
+struct FeatureCache {
+    dim: usize,
+    origin: [isize; 4],
+    size: [usize; 4],
+    points_per_cell: usize,
+    hashes: Vec<usize>,
+}
+
+impl FeatureCache {
+    /// Returns the cached hash of a key, which is either the coordinates of a
+    /// cell, or those followed by the index of a feature point within it.
+    fn get(&self, key: &[isize]) -> Option<usize> {
+        let (cell, index) = match key.len() {
+            len if len == self.dim => (key, 0),
+            len if len == self.dim + 1 => (&key[..self.dim], key[self.dim]),
+            _ => return None,
+        };
+        if index < 0 || index as usize >= self.points_per_cell {
+            return None;
+        }
+
+        let mut offset = 0;
+        for axis in (0..self.dim).rev() {
+            let coordinate = cell[axis] - self.origin[axis];
+            if coordinate < 0 || coordinate as usize >= self.size[axis] {
+                return None;
+            }
+            offset = offset * self.size[axis] + coordinate as usize;
+        }
+
+        Some(self.hashes[offset * self.points_per_cell + index as usize])
+    }
+}
+
+/// Hasher that looks keys up in a [`FeatureCache`] before hashing them.
+struct CachedHasher<'a, H: ?Sized> {
+    cache: &'a FeatureCache,
+    hasher: &'a H,
+}
+
+impl<'a, H: ?Sized> CachedHasher<'a, H> {
+    fn new(cache: &'a FeatureCache, hasher: &'a H) -> Self {
+        Self { cache, hasher }
+    }
+}
+
+impl<H> NoiseHasher for CachedHasher<'_, H>
+where
+    H: NoiseHasher + ?Sized,
+{
+    fn hash(&self, to_hash: &[isize]) -> usize {
+        self.cache
+            .get(to_hash)
+            .unwrap_or_else(|| self.hasher.hash(to_hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dimensions_share_cell_hashing() {
+        let worley = Worley::new(11);
+        let hasher = PermutationTable::new(11);
+        let cell_value = |cell: &[isize]| hasher.hash(cell) as f64 / 255.0 * 2.0 - 1.0;
+
+        // Seed points lie within 0.5 of their lattice points, so a lattice
+        // point always falls in its own cell and outputs the hash of it.
+        for x in -4..4 {
+            for y in -4..4 {
+                let point = [x as f64, y as f64];
+                assert_eq!(cell_value(&[x, y]), worley.get(point));
+
+                for z in -2..2 {
+                    let point = [x as f64, y as f64, z as f64];
+                    assert_eq!(cell_value(&[x, y, z]), worley.get(point));
+                    assert_eq!(
+                        cell_value(&[x, y, z, 1]),
+                        worley.get([point[0], point[1], point[2], 1.0])
+                    );
+                }
+            }
+        }
+
+        // A shared hasher reproduces the noise of every dimension.
+        let shared = Worley::with_hasher(11, hasher).set_return_type(ReturnType::Distance);
+        let distance = worley.set_return_type(ReturnType::Distance);
+        for i in 0..32 {
+            let (x, y) = (i as f64 * 0.37 - 2.1, i as f64 * 0.53 + 0.6);
+            assert_eq!(distance.get([x, y]), shared.get([x, y]));
+            assert_eq!(distance.get([x, y, 0.7]), shared.get([x, y, 0.7]));
+        }
+    }
+
+    #[test]
+    fn test_period_tiles_exactly() {
+        let distance = Worley::new(3)
+            .set_return_type(ReturnType::Distance)
+            .set_period([4, 3]);
+        let points = distance.clone().set_points_per_cell(3);
+        let value = Worley::new(3).set_period([4, 3]);
+        let distance_3d = distance.clone().set_period([2, 5, 3]);
+        let points_4d = points.clone().set_period([2, 2, 2, 2]);
+
+        for i in 0..32 {
+            // Dyadic coordinates, so that moving them by whole periods keeps
+            // them exact.
+            let t = i as f64 * 0.125;
+
+            for noise in [&distance, &points, &value].iter() {
+                assert_eq!(noise.get([0.0, t]), noise.get([4.0, t]));
+                assert_eq!(noise.get([t, 0.0]), noise.get([t, 3.0]));
+                assert_eq!(noise.get([t - 4.0, t]), noise.get([t + 8.0, t - 6.0]));
+            }
+
+            assert_eq!(
+                distance_3d.get([0.0, t, 0.5]),
+                distance_3d.get([2.0, t, 3.5])
+            );
+            assert_eq!(
+                distance_3d.get([t, 0.0, 0.0]),
+                distance_3d.get([t, 5.0, 3.0])
+            );
+            assert_eq!(
+                points_4d.get([t, 0.5, 0.0, t]),
+                points_4d.get([t, 2.5, 2.0, t - 2.0])
+            );
+        }
+
+        // Without a period, the pattern does not repeat.
+        let plain = Worley::new(3).set_return_type(ReturnType::Distance);
+        assert!((0..32).any(|i| {
+            let t = i as f64 * 0.125;
+            plain.get([0.0, t]) != plain.get([4.0, t])
+        }));
+    }
+
+    #[test]
+    fn test_precomputed_region_matches() {
+        let worleys = [
+            Worley::new(5).set_frequency(1.5),
+            Worley::new(5).set_return_type(ReturnType::Distance),
+            Worley::new(5)
+                .set_return_type(ReturnType::EdgeDistance)
+                .set_points_per_cell(3),
+        ];
+
+        for worley in worleys.iter() {
+            let cached_2d = worley.clone().precompute_region([(-2.0, 2.0), (-1.0, 3.0)]);
+            let cached_3d = worley.clone().precompute_region([(-2.0, 2.0); 3]);
+            let cached_4d = worley.clone().precompute_region([(-1.0, 1.0); 4]);
+
+            // Every cell of the region is cached, with the hashes of all of
+            // its feature points.
+            let cache = cached_2d.feature_cache(2).unwrap();
+            for x in -3..=3 {
+                for y in -2..=4 {
+                    assert_eq!(Some(worley.perm_table.hash(&[x, y])), cache.get(&[x, y]));
+                    for index in 1..worley.points_per_cell as isize {
+                        assert_eq!(
+                            Some(worley.perm_table.hash(&[x, y, index])),
+                            cache.get(&[x, y, index])
+                        );
+                    }
+                }
+            }
+            assert_eq!(None, cache.get(&[-8, 0]));
+            assert!(cached_2d.feature_cache(3).is_none());
+
+            // Sample both within and beyond the precomputed regions.
+            for i in 0..64 {
+                let t = i as f64 * 0.0731;
+                let point = [t * 5.0 - 3.0, 3.5 - t * 4.0, t - 1.5, 2.0 - t];
+
+                let point_2d = [point[0], point[1]];
+                assert_eq!(worley.get(point_2d), cached_2d.get(point_2d));
+                let point_3d = [point[0], point[1], point[2]];
+                assert_eq!(worley.get(point_3d), cached_3d.get(point_3d));
+                assert_eq!(worley.get(point), cached_4d.get(point));
+            }
+        }
+    }
+
+    #[test]
+    fn test_squared_distance_is_square_of_euclidean() {
+        let euclidean = Worley::new(2).set_return_type(ReturnType::Distance);
+        let squared = euclidean
+            .clone()
+            .set_distance_function(distance_functions::euclidean_squared);
+        let distance = |value: f64| (value + 1.0) / 2.0;
+        let assert_squared = |euclidean: f64, squared: f64| {
+            let (euclidean, squared) = (distance(euclidean), distance(squared));
+            assert!((euclidean * euclidean - squared).abs() < 1e-12);
+        };
+
+        for i in 0..128 {
+            let t = i as f64 * 0.173;
+            let point = [t - 5.0, 3.0 - t * 0.7, t * 0.3, 1.0 - t * 0.2];
+
+            let point_2d = [point[0], point[1]];
+            assert_squared(euclidean.get(point_2d), squared.get(point_2d));
+            let point_3d = [point[0], point[1], point[2]];
+            assert_squared(euclidean.get(point_3d), squared.get(point_3d));
+            assert_squared(euclidean.get(point), squared.get(point));
+        }
+    }
+
+    /// Asserts that Worley distances using `function` match those using
+    /// `expected`.
+    fn assert_distances_match<F, E>(function: F, expected: E)
+    where
+        F: Fn(&[f64], &[f64]) -> f64 + Send + Sync + 'static,
+        E: Fn(&[f64], &[f64]) -> f64 + Send + Sync + 'static,
+    {
+        let worley = Worley::new(2).set_return_type(ReturnType::Distance);
+        let actual = worley.clone().set_distance_function(function);
+        let expected = worley.set_distance_function(expected);
+
+        for i in 0..128 {
+            let t = i as f64 * 0.173;
+            let point = [t - 5.0, 3.0 - t * 0.7, t * 0.3, 1.0 - t * 0.2];
+
+            let point_2d = [point[0], point[1]];
+            assert!((expected.get(point_2d) - actual.get(point_2d)).abs() < 1e-12);
+            let point_3d = [point[0], point[1], point[2]];
+            assert!((expected.get(point_3d) - actual.get(point_3d)).abs() < 1e-12);
+            assert!((expected.get(point) - actual.get(point)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_minkowski_generalizes_distances() {
+        use distance_functions::*;
+
+        assert_distances_match(minkowski(1.0), manhattan);
+        assert_distances_match(minkowski(2.0), euclidean);
+        assert_distances_match(minkowski(f64::INFINITY), chebyshev);
+
+        // Higher orders give distances between the euclidean and chebyshev
+        // ones.
+        let (a, b) = ([0.1, 0.7, -0.2], [0.4, 0.3, 0.5]);
+        let cubic = minkowski(3.0)(&a, &b);
+        assert!(cubic < euclidean(&a, &b));
+        assert!(cubic > chebyshev(&a, &b));
+    }
+
+    #[test]
+    #[should_panic(expected = "the order of the Minkowski distance must be positive")]
+    fn test_minkowski_rejects_nan_order() {
+        let _ = distance_functions::minkowski(f64::NAN);
+    }
+
+    #[test]
+    fn test_f1_f2_matches_single_modes() {
+        let worleys = [
+            Worley::new(4),
+            Worley::new(4).set_frequency(1.5).set_points_per_cell(3),
+            Worley::new(4)
+                .set_distance_function(distance_functions::manhattan)
+                .set_period([3, 4, 5, 6]),
+        ];
+
+        for worley in worleys.iter() {
+            let f1 = worley.clone().set_return_type(ReturnType::Distance);
+            let f2 = worley.clone().set_return_type(ReturnType::SecondDistance);
+            let f1_f2 = WorleyF1F2::from_worley(worley.clone());
+
+            for i in 0..64 {
+                let t = i as f64 * 0.173;
+                let point = [t - 3.1, 0.4 - t * 1.7, t * 0.6, 1.3 - t];
+
+                let point_2d = [point[0], point[1]];
+                let expected = (f1.get(point_2d), f2.get(point_2d));
+                assert_eq!(expected, f1_f2.get_f1_f2(point_2d));
+
+                let point_3d = [point[0], point[1], point[2]];
+                let expected = (f1.get(point_3d), f2.get(point_3d));
+                assert_eq!(expected, f1_f2.get_f1_f2(point_3d));
+
+                assert_eq!((f1.get(point), f2.get(point)), f1_f2.get_f1_f2(point));
+
+                assert!(f1.get(point) <= f2.get(point));
+                assert_eq!(f2.get(point) - f1.get(point), f1_f2.get(point));
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalized_distance_is_bounded() {
+        let raw = Worley::new(6).set_return_type(ReturnType::Distance);
+        let normalized = raw.clone().normalized(true);
+        let rescale = |value: f64, max_distance: f64| (value + 1.0) / max_distance - 1.0;
+
+        let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for i in 0..4096 {
+            let point = [
+                (i % 64) as f64 * 0.137 - 4.0,
+                (i / 64) as f64 * 0.113 + 0.5,
+                (i % 7) as f64 * 0.31,
+                (i % 11) as f64 * 0.29,
+            ];
+
+            let point_2d = [point[0], point[1]];
+            let value = normalized.get(point_2d);
+            let expected = rescale(raw.get(point_2d), Worley::MAX_DISTANCE_2D);
+            assert!((value - expected).abs() < 1e-12);
+            min = min.min(value);
+            max = max.max(value);
+
+            let point_3d = [point[0], point[1], point[2]];
+            let value = normalized.get(point_3d);
+            let expected = rescale(raw.get(point_3d), Worley::MAX_DISTANCE_3D);
+            assert!((value - expected).abs() < 1e-12);
+            min = min.min(value);
+            max = max.max(value);
+
+            let value = normalized.get(point);
+            let expected = rescale(raw.get(point), Worley::MAX_DISTANCE_4D);
+            assert!((value - expected).abs() < 1e-12);
+            min = min.min(value);
+            max = max.max(value);
+        }
+        assert!(min >= -1.0 && max <= 1.0, "{} to {}", min, max);
+        assert!(min < -0.9 && max > 0.3, "{} to {}", min, max);
+
+        // Other return types are unaffected.
+        let value = Worley::new(6);
+        let point = [0.3, 1.7, -2.2];
+        assert_eq!(value.get(point), value.clone().normalized(true).get(point));
+    }
+
+    #[test]
+    fn test_average_distance_matches_f1_f2() {
+        let worleys = [
+            Worley::new(8),
+            Worley::new(8)
+                .set_points_per_cell(2)
+                .set_period([5, 4, 3, 2]),
+        ];
+
+        for worley in worleys.iter() {
+            let average = worley.clone().set_return_type(ReturnType::AverageDistance);
+            let f1_f2 = WorleyF1F2::from_worley(worley.clone());
+
+            for i in 0..64 {
+                let t = i as f64 * 0.173;
+                let point = [t - 3.1, 0.4 - t * 1.7, t * 0.6, 1.3 - t];
+
+                let point_2d = [point[0], point[1]];
+                let (f1, f2) = f1_f2.get_f1_f2(point_2d);
+                assert!((average.get(point_2d) - (f1 + f2) / 2.0).abs() < 1e-12);
+
+                let point_3d = [point[0], point[1], point[2]];
+                let (f1, f2) = f1_f2.get_f1_f2(point_3d);
+                assert!((average.get(point_3d) - (f1 + f2) / 2.0).abs() < 1e-12);
+
+                let (f1, f2) = f1_f2.get_f1_f2(point);
+                assert!((average.get(point) - (f1 + f2) / 2.0).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -1,9 +1,18 @@
-pub use self::{abs::*, clamp::*, curve::*, exponent::*, negate::*, scale_bias::*, terrace::*};
+pub use self::{
+    abs::*, clamp::*, curve::*, dither::*, exponent::*, inspect::*, map::*, negate::*, ridge::*,
+    scale_bias::*, soft_clamp::*, terrace::*, transfer_2d::*,
+};
 
 mod abs;
 mod clamp;
 mod curve;
+mod dither;
 mod exponent;
+mod inspect;
+mod map;
 mod negate;
+mod ridge;
 mod scale_bias;
+mod soft_clamp;
 mod terrace;
+mod transfer_2d;
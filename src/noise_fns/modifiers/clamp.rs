@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::noise_fns::{NoiseFn, Seedable};
 use core::marker::PhantomData;
 
 /// Noise function that clamps the output value from the source function to a
@@ -14,6 +14,10 @@ where
     /// Bound of the clamping range. Default is -1.0 to 1.0.
     pub bounds: (f64, f64),
 
+    /// Value that replaces non-finite output values from the source function
+    /// before clamping. Default is `None`, which lets `NaN` pass through.
+    pub nan_replacement: Option<f64>,
+
     phantom: PhantomData<T>,
 }
 
@@ -25,6 +29,7 @@ where
         Self {
             source,
             bounds: (-1.0, 1.0),
+            nan_replacement: None,
             phantom: PhantomData,
         }
     }
@@ -49,6 +54,32 @@ where
             ..self
         }
     }
+
+    /// Replaces `NaN` and infinite output values from the source function with
+    /// `replacement` before clamping, so that one bad noise function doesn't
+    /// poison a whole map.
+    pub fn sanitize_nan(self, replacement: f64) -> Self {
+        Self {
+            nan_replacement: Some(replacement),
+            ..self
+        }
+    }
+}
+
+impl<T, Source, const DIM: usize> Seedable for Clamp<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
 }
 
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Clamp<T, Source, DIM>
@@ -56,8 +87,38 @@ where
     Source: NoiseFn<T, DIM>,
 {
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
-        let value = self.source.get(point);
+        let mut value = self.source.get(point);
+
+        if let Some(replacement) = self.nan_replacement {
+            if !value.is_finite() {
+                value = replacement;
+            }
+        }
 
         value.clamp(self.bounds.0, self.bounds.1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Power};
+
+    #[test]
+    fn test_sanitize_nan() {
+        // A negative base with a fractional exponent produces NaN.
+        let nan = Power::new(Constant::new(-1.0), Constant::new(0.5));
+        let infinite = Constant::new(f64::INFINITY);
+
+        assert!(NoiseFn::<f64, 2>::get(&Clamp::new(&nan), [0.0, 0.0]).is_nan());
+
+        let sanitized = Clamp::new(&nan).sanitize_nan(0.25);
+        assert_eq!(0.25, sanitized.get([0.0, 0.0]));
+
+        let sanitized = Clamp::new(infinite).sanitize_nan(-3.0);
+        assert_eq!(-1.0, NoiseFn::<f64, 2>::get(&sanitized, [0.0, 0.0]));
+
+        let finite = Clamp::new(Constant::new(0.5)).sanitize_nan(0.0);
+        assert_eq!(0.5, NoiseFn::<f64, 2>::get(&finite, [0.0, 0.0]));
+    }
+}
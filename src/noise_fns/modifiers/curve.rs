@@ -1,4 +1,7 @@
-use crate::{math::interpolate, noise_fns::NoiseFn};
+use crate::{
+    math::interpolate,
+    noise_fns::{NoiseFn, Seedable},
+};
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
@@ -16,6 +19,10 @@ use core::marker::PhantomData;
 /// four control points to the curve. If there is less than four control
 /// points, the get() method panics. Each control point can have any input
 /// and output value, although no two control points can have the same input.
+///
+/// By default, source values outside the range of the control points map to
+/// the output value of the nearest end point. Use `set_clamp_ends` to
+/// extrapolate the spline instead.
 #[derive(Clone, Debug)]
 pub struct Curve<T, Source, const DIM: usize>
 where
@@ -24,18 +31,12 @@ where
     /// Outputs a value.
     pub source: Source,
 
-    /// Vec that stores the control points.
-    control_points: Vec<ControlPoint<f64>>,
+    /// Spline through the control points.
+    spline: Spline,
 
     phantom: PhantomData<T>,
 }
 
-#[derive(Clone, Debug)]
-struct ControlPoint<T> {
-    input: T,
-    output: T,
-}
-
 impl<T, Source, const DIM: usize> Curve<T, Source, DIM>
 where
     Source: NoiseFn<T, DIM>,
@@ -43,12 +44,98 @@ where
     pub fn new(source: Source) -> Self {
         Self {
             source,
-            control_points: Vec::with_capacity(4),
+            spline: Spline::new(),
             phantom: PhantomData,
         }
     }
 
     pub fn add_control_point(mut self, input_value: f64, output_value: f64) -> Self {
+        self.spline.add_control_point(input_value, output_value);
+        self
+    }
+
+    /// Enables or disables clamping source values outside the control point
+    /// range. When enabled, inputs below the first control point or above the
+    /// last return that point's output value rather than an extrapolated
+    /// spline value, which can overshoot wildly.
+    pub fn set_clamp_ends(mut self, clamp_ends: bool) -> Self {
+        self.spline.clamp_ends = clamp_ends;
+        self
+    }
+
+    /// Samples the curve at `samples` evenly spaced inputs from the first to
+    /// the last control point, inclusive.
+    ///
+    /// This bakes the curve into a lookup table, e.g. to evaluate the same
+    /// curve on the GPU. A single sample is taken at the first control point.
+    pub fn to_lut(&self, samples: usize) -> Vec<f64> {
+        let control_points = &self.spline.control_points;
+        assert!(control_points.len() >= 4);
+
+        let first = control_points[0].input;
+        let last = control_points[control_points.len() - 1].input;
+        let step = if samples > 1 {
+            (last - first) / (samples - 1) as f64
+        } else {
+            0.0
+        };
+
+        (0..samples)
+            .map(|i| self.map_value(first + step * i as f64))
+            .collect()
+    }
+
+    /// Returns the slope of the curve at a source value, i.e. the analytic
+    /// derivative of the spline, so that the steepness of the transfer
+    /// function can drive other effects.
+    ///
+    /// Where the curve is flat, outside the control point range with the ends
+    /// clamped or beyond the last control point, the slope is 0.0. The slope
+    /// jumps at control points whose neighbors are unevenly spaced; at the
+    /// control points themselves, the slope of the segment starting there is
+    /// returned.
+    pub fn derivative(&self, source_value: f64) -> f64 {
+        self.spline.derivative(source_value)
+    }
+
+    /// Maps a source value onto the curve.
+    pub(crate) fn map_value(&self, source_value: f64) -> f64 {
+        self.spline.map_value(source_value)
+    }
+}
+
+/// Cubic spline through a set of control points, shared by [`Curve`] and the
+/// control curve of [`Select`](crate::Select).
+#[derive(Clone, Debug)]
+pub(crate) struct Spline {
+    /// Vec that stores the control points.
+    control_points: Vec<ControlPoint<f64>>,
+
+    /// Whether source values outside the control point range are clamped to
+    /// the end points.
+    clamp_ends: bool,
+}
+
+#[derive(Clone, Debug)]
+struct ControlPoint<T> {
+    input: T,
+    output: T,
+}
+
+impl Spline {
+    pub(crate) fn new() -> Self {
+        Self {
+            control_points: Vec::with_capacity(4),
+            clamp_ends: true,
+        }
+    }
+
+    /// Returns the number of control points.
+    pub(crate) fn len(&self) -> usize {
+        self.control_points.len()
+    }
+
+    pub(crate) fn add_control_point(&mut self, input_value: f64, output_value: f64) {
         // check to see if the vector already contains the input point.
         if !self
             .control_points
@@ -61,7 +148,7 @@ where
                 .control_points
                 .iter()
                 .position(|x| x.input >= input_value)
-                .unwrap_or_else(|| self.control_points.len());
+                .unwrap_or(self.control_points.len());
 
             // add the new control point at the correct position.
             self.control_points.insert(
@@ -72,21 +159,45 @@ where
                 },
             );
         }
+    }
 
-        self
+    /// Returns the slope of the spline at a source value. See
+    /// [`Curve::derivative`].
+    pub(crate) fn derivative(&self, source_value: f64) -> f64 {
+        match self.segment(source_value) {
+            Ok(([n0, n1, n2, n3], alpha, width)) => {
+                interpolate::cubic_derivative(n0, n1, n2, n3, alpha) / width
+            }
+            Err(_) => 0.0,
+        }
     }
-}
 
-impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Curve<T, Source, DIM>
-where
-    Source: NoiseFn<T, DIM>,
-{
-    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+    /// Maps a source value onto the spline.
+    pub(crate) fn map_value(&self, source_value: f64) -> f64 {
+        match self.segment(source_value) {
+            Ok(([n0, n1, n2, n3], alpha, _)) => interpolate::cubic(n0, n1, n2, n3, alpha),
+            Err(output) => output,
+        }
+    }
+
+    /// Finds the spline segment containing a source value. Returns the
+    /// outputs of the four nearest control points, the position of the value
+    /// within the segment, and the width of the segment, or the output value
+    /// if the curve is flat there.
+    fn segment(&self, source_value: f64) -> Result<([f64; 4], f64, f64), f64> {
         // confirm that there's at least 4 control points in the vector.
         assert!(self.control_points.len() >= 4);
 
-        // get output value from the source function
-        let source_value = self.source.get(point);
+        if self.clamp_ends {
+            let first = &self.control_points[0];
+            let last = &self.control_points[self.control_points.len() - 1];
+
+            if source_value <= first.input {
+                return Err(first.output);
+            } else if source_value >= last.input {
+                return Err(last.output);
+            }
+        }
 
         // Find the first element in the control point array that has a input
         // value larger than the output value from the source function
@@ -94,7 +205,7 @@ where
             .control_points
             .iter()
             .position(|x| x.input > source_value)
-            .unwrap_or_else(|| self.control_points.len());
+            .unwrap_or(self.control_points.len());
 
         // if index_pos < 2 {
         //     println!(
@@ -118,7 +229,7 @@ where
         // than the smallest input value of the control point array), get the
         // corresponding output value of the nearest control point and exit.
         if index1 == index2 {
-            return self.control_points[index1].output;
+            return Err(self.control_points[index1].output);
         }
 
         // Compute the alpha value used for cubic interpolation
@@ -126,13 +237,129 @@ where
         let input1 = self.control_points[index2].input;
         let alpha = (source_value - input0) / (input1 - input0);
 
-        // Now perform the cubic interpolation and return.
-        interpolate::cubic(
-            self.control_points[index0].output,
-            self.control_points[index1].output,
-            self.control_points[index2].output,
-            self.control_points[index3].output,
+        Ok((
+            [
+                self.control_points[index0].output,
+                self.control_points[index1].output,
+                self.control_points[index2].output,
+                self.control_points[index3].output,
+            ],
             alpha,
-        )
+            input1 - input0,
+        ))
+    }
+}
+
+impl<T, Source, const DIM: usize> Seedable for Curve<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Curve<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        // get output value from the source function
+        let source_value = self.source.get(point);
+
+        self.map_value(source_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn curve(value: f64, clamp_ends: bool) -> f64 {
+        let curve = Curve::new(Constant::new(value))
+            .add_control_point(-1.0, -1.0)
+            .add_control_point(-0.5, 0.8)
+            .add_control_point(0.5, -0.8)
+            .add_control_point(1.0, 1.0)
+            .set_clamp_ends(clamp_ends);
+        NoiseFn::<f64, 2>::get(&curve, [0.0, 0.0])
+    }
+
+    #[test]
+    fn test_clamp_ends() {
+        assert_eq!(-1.0, curve(-1.5, true));
+        assert_eq!(-1.0, curve(-1.0, true));
+        assert_eq!(1.0, curve(1.0, true));
+        assert_eq!(1.0, curve(3.0, true));
+
+        // Inside the range, clamping makes no difference.
+        assert_eq!(curve(0.25, false), curve(0.25, true));
+
+        // Without clamping, the spline is extrapolated below the first point.
+        assert_ne!(-1.0, curve(-1.5, false));
+    }
+
+    #[test]
+    fn test_to_lut() {
+        let lut = Curve::<f64, Constant, 2>::new(Constant::new(0.0))
+            .add_control_point(-1.0, -1.0)
+            .add_control_point(-0.5, 0.8)
+            .add_control_point(0.5, -0.8)
+            .add_control_point(1.0, 1.0)
+            .to_lut(9);
+
+        assert_eq!(9, lut.len());
+        for (i, &entry) in lut.iter().enumerate() {
+            assert_eq!(curve(-1.0 + i as f64 * 0.25, true), entry);
+        }
+    }
+
+    #[test]
+    fn test_derivative_matches_finite_differences() {
+        let curve = |clamp_ends| {
+            Curve::<f64, Constant, 2>::new(Constant::new(0.0))
+                .add_control_point(-1.0, -1.0)
+                .add_control_point(-0.5, 0.8)
+                .add_control_point(0.25, -0.8)
+                .add_control_point(0.6, 0.1)
+                .add_control_point(1.0, 1.0)
+                .set_clamp_ends(clamp_ends)
+        };
+        let (clamped, extrapolated) = (curve(true), curve(false));
+
+        let h = 1e-6;
+        for i in 0..64 {
+            let x = -1.4 + i as f64 * 0.0437;
+            // Finite differences are inaccurate across control points.
+            let inputs = [-1.0_f64, -0.5, 0.25, 0.6, 1.0];
+            if inputs.iter().any(|input| (x - input).abs() < 2.0 * h) {
+                continue;
+            }
+
+            for curve in [&clamped, &extrapolated].iter() {
+                let estimate = (curve.map_value(x + h) - curve.map_value(x - h)) / (2.0 * h);
+                let derivative = curve.derivative(x);
+                assert!(
+                    (derivative - estimate).abs() < 1e-5,
+                    "{} != {} at {}",
+                    derivative,
+                    estimate,
+                    x
+                );
+            }
+        }
+
+        // The clamped ends are flat.
+        assert_eq!(0.0, clamped.derivative(-1.2));
+        assert_eq!(0.0, clamped.derivative(1.2));
+        assert_ne!(0.0, extrapolated.derivative(-1.2));
     }
 }
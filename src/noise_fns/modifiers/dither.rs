@@ -0,0 +1,117 @@
+use crate::noise_fns::{NoiseFn, Seedable};
+use core::marker::PhantomData;
+
+/// Noise function that adds a small pseudo-random offset to the output value
+/// from the source function.
+///
+/// The offset is derived from a hash of the coordinates of the input value, so
+/// it is the same every time a point is sampled. Adding it before quantizing
+/// the output, for example to 8-bit color, breaks up the visible banding of
+/// smooth gradients.
+#[derive(Clone, Copy, Debug)]
+pub struct Dither<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Largest offset added to or subtracted from the output value. Default is
+    /// 2.0 / 255.0, a single step of 8-bit output when the range -1.0 to 1.0
+    /// is mapped onto 0 to 255. Halve it for output in the range 0.0 to 1.0.
+    pub amplitude: f64,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Dither<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub const DEFAULT_AMPLITUDE: f64 = 2.0 / 255.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            amplitude: Self::DEFAULT_AMPLITUDE,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_amplitude(self, amplitude: f64) -> Self {
+        Self { amplitude, ..self }
+    }
+}
+
+impl<T, Source, const DIM: usize> Seedable for Dither<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<Source, const DIM: usize> NoiseFn<f64, DIM> for Dither<f64, Source, DIM>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: impl Into<[f64; DIM]>) -> f64 {
+        let point = point.into();
+
+        self.source.get(point) + offset(&point) * self.amplitude
+    }
+}
+
+/// Hashes the coordinates of the point to a value between -1.0 and 1.0.
+fn offset(point: &[f64]) -> f64 {
+    // Mix each coordinate into the hash with the SplitMix64 finalizer.
+    let hash = point.iter().fold(0, |hash: u64, coordinate| {
+        let mut z = (hash ^ coordinate.to_bits()).wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    });
+
+    // Use the top 53 bits as the mantissa of a value between 0.0 and 1.0.
+    (hash >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin};
+
+    #[test]
+    fn test_offset_is_deterministic_and_bounded() {
+        let dither = Dither::new(Constant::new(0.5)).set_amplitude(0.01);
+        let perlin_dither: Dither<f64, _, 3> = Dither::new(Perlin::new(1));
+
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for i in 0..1000 {
+            let point = [i as f64 * 0.731, i as f64 * -0.113];
+            let value = dither.get(point);
+
+            assert_eq!(value, dither.get(point));
+            assert!((value - 0.5).abs() <= 0.01);
+            min = min.min(value);
+            max = max.max(value);
+
+            let point = [point[0], point[1], 2.5];
+            let offset = perlin_dither.get(point) - perlin_dither.source.get(point);
+            assert!(offset.abs() <= Dither::<f64, Perlin, 3>::DEFAULT_AMPLITUDE);
+        }
+
+        // The offsets are spread across the whole range.
+        assert!(min < 0.5 - 0.009);
+        assert!(max > 0.5 + 0.009);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{math::scale_shift, noise_fns::NoiseFn};
+use crate::{
+    math::scale_shift,
+    noise_fns::{NoiseFn, Seedable},
+};
 use core::marker::PhantomData;
 
 /// Noise function that maps the output value from the source function onto an
@@ -8,6 +11,16 @@ use core::marker::PhantomData;
 /// this noise function first normalizes the output value (the range becomes 0.0
 /// to 1.0), maps that value onto an exponential curve, then rescales that
 /// value back to the original range.
+///
+/// Alternatively, separate exponents can be set for the negative and positive
+/// halves of the range with `set_exponents`. In that case the absolute value
+/// of the output is raised to the exponent for its sign and the sign is then
+/// restored, so that, for example, valleys can be flattened while peaks are
+/// sharpened.
+///
+/// If the source function outputs values outside of -1.0 to 1.0, such as after
+/// combining several functions, set its range with `set_input_range` so that
+/// the output stays within that range instead of growing with the exponent.
 #[derive(Clone, Copy, Debug)]
 pub struct Exponent<T, Source, const DIM: usize>
 where
@@ -20,6 +33,18 @@ where
     /// is 1.0.
     pub exponent: f64,
 
+    /// Exponents applied to negative and positive output values respectively,
+    /// preserving the sign of the value. When set, these are used instead of
+    /// `exponent`. Default is `None`.
+    pub signed_exponents: Option<(f64, f64)>,
+
+    /// Range of the output values from the source function, which is mapped
+    /// onto -1.0 to 1.0 before applying the exponent and back afterwards.
+    /// Default is `None`, which assumes the range -1.0 to 1.0. An empty range,
+    /// where the lower bound is not below the upper bound, outputs the lower
+    /// bound.
+    pub input_range: Option<(f64, f64)>,
+
     phantom: PhantomData<T>,
 }
 
@@ -31,12 +56,79 @@ where
         Self {
             source,
             exponent: 1.0,
+            signed_exponents: None,
+            input_range: None,
             phantom: PhantomData,
         }
     }
 
+    /// Sets the exponent applied to the normalized output value, replacing
+    /// any per-sign exponents set with `set_exponents`.
     pub fn set_exponent(self, exponent: f64) -> Self {
-        Self { exponent, ..self }
+        Self {
+            exponent,
+            signed_exponents: None,
+            ..self
+        }
+    }
+
+    /// Sets separate exponents for negative and positive output values. The
+    /// output is `-|x|^neg` for negative values and `x^pos` otherwise.
+    pub fn set_exponents(self, neg: f64, pos: f64) -> Self {
+        Self {
+            signed_exponents: Some((neg, pos)),
+            ..self
+        }
+    }
+
+    /// Sets the range of the output values from the source function.
+    ///
+    /// The output value is normalized from this range before the exponent is
+    /// applied, then mapped back onto it, so the output stays within the
+    /// range. Values outside of the range are clamped to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not less than `max`.
+    pub fn set_input_range(self, min: f64, max: f64) -> Self {
+        assert!(min < max, "the input range must not be empty");
+
+        Self {
+            input_range: Some((min, max)),
+            ..self
+        }
+    }
+
+    /// Applies the exponent to a value in the range -1.0 to 1.0.
+    fn apply(&self, mut value: f64) -> f64 {
+        if let Some((neg, pos)) = self.signed_exponents {
+            return if value < 0.0 {
+                -pow(-value, neg)
+            } else {
+                pow(value, pos)
+            };
+        }
+
+        value = (value + 1.0) / 2.0;
+        value = value.abs();
+        value = pow(value, self.exponent);
+        scale_shift(value, 2.0)
+    }
+}
+
+impl<T, Source, const DIM: usize> Seedable for Exponent<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
     }
 }
 
@@ -45,10 +137,152 @@ where
     Source: NoiseFn<T, DIM>,
 {
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
-        let mut value = self.source.get(point);
-        value = (value + 1.0) / 2.0;
-        value = value.abs();
-        value = value.powf(self.exponent);
-        scale_shift(value, 2.0)
+        let value = self.source.get(point);
+
+        match self.input_range {
+            Some((min, max)) => {
+                let extent = max - min;
+                if extent <= 0.0 {
+                    return min;
+                }
+
+                let normalized = ((value - min) / extent).clamp(0.0, 1.0);
+                let value = self.apply(normalized * 2.0 - 1.0);
+
+                min + (value + 1.0) * 0.5 * extent
+            }
+            None => self.apply(value),
+        }
+    }
+}
+
+/// Raises the value to the exponent, using repeated multiplication when the
+/// exponent is an integer since that is much faster than `powf`.
+#[inline]
+fn pow(value: f64, exponent: f64) -> f64 {
+    if exponent.fract() == 0.0 && exponent.abs() <= f64::from(i32::MAX) {
+        value.powi(exponent as i32)
+    } else {
+        value.powf(exponent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn signed(value: f64, neg: f64, pos: f64) -> f64 {
+        Exponent::new(Constant::new(value))
+            .set_exponents(neg, pos)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn test_equal_exponents_are_symmetric() {
+        for &value in &[-0.8_f64, -0.3, 0.0, 0.3, 0.8] {
+            let expected = value.signum() * value.abs().powf(3.0);
+            assert!((signed(value, 3.0, 3.0) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_unequal_exponents_differ_per_sign() {
+        assert!((signed(-0.5, 0.5, 2.0) + 0.5_f64.sqrt()).abs() < 1e-12);
+        assert!((signed(0.5, 0.5, 2.0) - 0.25).abs() < 1e-12);
+        assert!((signed(-0.5, 0.5, 2.0) + signed(0.5, 0.5, 2.0)).abs() > 0.1);
+    }
+
+    #[test]
+    fn test_integer_exponents_match_powf() {
+        for &exponent in &[0.0, 1.0, 2.0, 3.0, 7.0, -2.0] {
+            for i in 0..=20 {
+                let value = i as f64 / 20.0;
+                let expected = value.powf(exponent);
+                let actual = pow(value, exponent);
+
+                assert!(
+                    actual == expected || ((actual - expected) / expected).abs() < 1e-12,
+                    "{}^{}: {} != {}",
+                    value,
+                    exponent,
+                    actual,
+                    expected
+                );
+            }
+
+            let exponent_fn = Exponent::new(Constant::new(0.3)).set_exponent(exponent);
+            let expected = scale_shift(0.65_f64.powf(exponent), 2.0);
+            let actual = exponent_fn.get([0.0, 0.0]);
+            assert!((actual - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_contrast() {
+        let contrast = |value, amount| {
+            NoiseFn::<f64, 2>::contrast(Constant::new(value), amount).get([0.0, 0.0])
+        };
+
+        for &value in &[-1.0, -0.6, -0.2, 0.0, 0.2, 0.6, 1.0] {
+            assert_eq!(value, contrast(value, 0.0));
+
+            let (higher, lower) = (contrast(value, 1.5), contrast(value, -1.5));
+            assert_eq!(value.signum(), higher.signum());
+            assert!(higher.abs() >= value.abs() && lower.abs() <= value.abs());
+            if value.abs() != 0.0 && value.abs() != 1.0 {
+                assert!(higher.abs() > value.abs() && lower.abs() < value.abs());
+            }
+        }
+    }
+
+    #[test]
+    fn test_input_range_keeps_output_bounded() {
+        let exponent = |value, exponent| {
+            Exponent::new(Constant::new(value))
+                .set_exponent(exponent)
+                .set_input_range(-3.0, 3.0)
+                .get([0.0, 0.0])
+        };
+
+        for i in 0..=24 {
+            let value = i as f64 * 0.25 - 3.0;
+            for &e in &[0.5, 1.0, 2.0, 4.0] {
+                let output = exponent(value, e);
+                assert!(
+                    (-3.0..=3.0).contains(&output),
+                    "{}^{}: {}",
+                    value,
+                    e,
+                    output
+                );
+            }
+
+            // An exponent of 1.0 leaves values within the range unchanged.
+            assert!((exponent(value, 1.0) - value).abs() < 1e-12);
+        }
+
+        // Without the range, the same values grow with the exponent.
+        let unbounded = Exponent::new(Constant::new(3.0)).set_exponent(4.0);
+        assert!(unbounded.get([0.0, 0.0]) > 3.0);
+
+        // Values beyond the range are clamped to it.
+        assert_eq!(3.0, exponent(5.0, 2.0));
+        assert_eq!(-3.0, exponent(-5.0, 2.0));
+    }
+
+    #[test]
+    fn test_empty_input_range_outputs_lower_bound() {
+        let mut exponent = Exponent::new(Constant::new(0.5)).set_exponent(2.0);
+        for &range in &[(1.0, 1.0), (2.0, -2.0)] {
+            exponent.input_range = Some(range);
+            assert_eq!(range.0, exponent.get([0.0, 0.0]));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_input_range_is_rejected() {
+        let _ = Exponent::<f64, _, 2>::new(Constant::new(0.5)).set_input_range(1.0, 1.0);
     }
 }
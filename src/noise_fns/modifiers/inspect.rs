@@ -0,0 +1,114 @@
+use crate::noise_fns::{NoiseFn, Seedable};
+use core::marker::PhantomData;
+
+/// Noise function that calls a closure with each input point and the output
+/// value from the source function, and outputs the value unchanged.
+///
+/// Like [`Iterator::inspect`], this is meant for debugging: logging or
+/// collecting the values that flow through a part of a pipeline without
+/// altering its output.
+#[derive(Clone, Copy)]
+pub struct Inspect<T, Source, F, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+    F: Fn([T; DIM], f64),
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Observes each input point and output value.
+    pub function: F,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, F, const DIM: usize> Inspect<T, Source, F, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+    F: Fn([T; DIM], f64),
+{
+    pub fn new(source: Source, function: F) -> Self {
+        Inspect {
+            source,
+            function,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Source, F, const DIM: usize> Seedable for Inspect<T, Source, F, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+    F: Fn([T; DIM], f64),
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<T, Source, F, const DIM: usize> NoiseFn<T, DIM> for Inspect<T, Source, F, DIM>
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM>,
+    F: Fn([T; DIM], f64),
+{
+    #[inline]
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        let point = point.into();
+        let value = self.source.get(point);
+        (self.function)(point, value);
+        value
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        self.source.value_range()
+    }
+}
+
+impl<T, Source, F, const DIM: usize> core::fmt::Debug for Inspect<T, Source, F, DIM>
+where
+    Source: NoiseFn<T, DIM> + core::fmt::Debug,
+    F: Fn([T; DIM], f64),
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Inspect")
+            .field("source", &self.source)
+            .field("function", &core::format_args!("..."))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NoiseFn, Perlin};
+    use alloc::vec::Vec;
+    use core::cell::RefCell;
+
+    #[test]
+    fn test_inspect_forwards_values() {
+        let perlin = Perlin::new(6);
+        let observed = RefCell::new(Vec::new());
+        let inspected = NoiseFn::<f64, 3>::inspect(perlin, |point, value| {
+            observed.borrow_mut().push((point, value));
+        });
+
+        let mut expected = Vec::new();
+        for i in 0..32 {
+            let t = i as f64 * 0.29;
+            let point = [t - 1.5, 0.7 - t, t * 0.5];
+            let value = perlin.get(point);
+
+            assert_eq!(value, inspected.get(point));
+            expected.push((point, value));
+        }
+
+        assert_eq!(expected, observed.into_inner());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::noise_fns::{NoiseFn, Seedable};
+use core::marker::PhantomData;
+
+/// Noise function that applies a closure to the output value from the source
+/// function.
+///
+/// This covers one-off transformations of the output value that none of the
+/// other modifiers provide, without defining a new noise function.
+#[derive(Clone, Copy)]
+pub struct Map<T, Source, F, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+    F: Fn(f64) -> f64,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Maps the output value from the source function.
+    pub function: F,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, F, const DIM: usize> Map<T, Source, F, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+    F: Fn(f64) -> f64,
+{
+    pub fn new(source: Source, function: F) -> Self {
+        Map {
+            source,
+            function,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Source, F, const DIM: usize> Seedable for Map<T, Source, F, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+    F: Fn(f64) -> f64,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<T, Source, F, const DIM: usize> NoiseFn<T, DIM> for Map<T, Source, F, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+    F: Fn(f64) -> f64,
+{
+    #[inline]
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        (self.function)(self.source.get(point))
+    }
+}
+
+impl<T, Source, F, const DIM: usize> core::fmt::Debug for Map<T, Source, F, DIM>
+where
+    Source: NoiseFn<T, DIM> + core::fmt::Debug,
+    F: Fn(f64) -> f64,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Map")
+            .field("source", &self.source)
+            .field("function", &core::format_args!("..."))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NoiseFn, Perlin};
+
+    #[test]
+    fn test_map_matches_multiply_constant() {
+        let perlin = Perlin::new(6);
+        let mapped = NoiseFn::<f64, 3>::map(perlin, |value| value * 2.0);
+        let multiplied = NoiseFn::<f64, 3>::multiply_constant(perlin, 2.0);
+
+        for i in 0..32 {
+            let t = i as f64 * 0.29;
+            let point = [t - 1.5, 0.7 - t, t * 0.5];
+
+            assert_eq!(multiplied.get(point), mapped.get(point));
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::noise_fns::{NoiseFn, Seedable};
 use core::marker::PhantomData;
 
 /// Noise function that negates the output value from the source function.
@@ -25,10 +25,27 @@ where
     }
 }
 
+impl<T, Source, const DIM: usize> Seedable for Negate<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Negate<T, Source, DIM>
 where
     Source: NoiseFn<T, DIM>,
 {
+    #[inline]
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
         -self.source.get(point)
     }
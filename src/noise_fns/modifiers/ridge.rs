@@ -0,0 +1,103 @@
+use crate::noise_fns::{NoiseFn, Seedable};
+use core::marker::PhantomData;
+
+/// Noise function that folds the output value from the source function into
+/// a ridge, outputting `(offset - |value|)^exponent`.
+///
+/// With the default offset of 1.0 and exponent of 2.0, this is the ridge
+/// function commonly used for ridged noise: source values near zero map to
+/// sharp peaks near 1.0. It is equivalent to chaining `abs`, `negate`,
+/// `add_constant` and `power_constant`.
+///
+/// Note that a source value whose magnitude is larger than the offset with a
+/// fractional exponent outputs NaN.
+#[derive(Clone, Copy, Debug)]
+pub struct Ridge<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Value the magnitude of the source value is subtracted from. Default
+    /// is 1.0.
+    pub offset: f64,
+
+    /// Exponent applied to the ridge. Default is 2.0.
+    pub exponent: f64,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Ridge<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub const DEFAULT_OFFSET: f64 = 1.0;
+    pub const DEFAULT_EXPONENT: f64 = 2.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            offset: Self::DEFAULT_OFFSET,
+            exponent: Self::DEFAULT_EXPONENT,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_offset(self, offset: f64) -> Self {
+        Self { offset, ..self }
+    }
+
+    pub fn set_exponent(self, exponent: f64) -> Self {
+        Self { exponent, ..self }
+    }
+}
+
+impl<T, Source, const DIM: usize> Seedable for Ridge<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Ridge<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        (self.offset - self.source.get(point).abs()).powf(self.exponent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn test_matches_composed_chain() {
+        let perlin = Perlin::new(3);
+        let ridge = perlin.ridge();
+        let chain = perlin.abs().negate().add_constant(1.0).power_constant(2.0);
+
+        let custom = perlin.ridge().set_offset(0.8).set_exponent(3.0);
+        let custom_chain = perlin.abs().negate().add_constant(0.8).power_constant(3.0);
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 5.1, i as f64 * 0.19 + 0.3];
+
+            assert_eq!(chain.get(point), ridge.get(point));
+            assert_eq!(custom_chain.get(point), custom.get(point));
+        }
+    }
+}
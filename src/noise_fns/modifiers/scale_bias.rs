@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::noise_fns::{NoiseFn, Seedable};
 use core::marker::PhantomData;
 
 /// Noise function that applies a scaling factor and a bias to the output value
@@ -44,17 +44,57 @@ where
     }
 }
 
+impl<T, Source, const DIM: usize> Seedable for ScaleBias<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for ScaleBias<T, Source, DIM>
 where
     Source: NoiseFn<T, DIM>,
 {
     #[cfg(not(target_os = "emscripten"))]
+    #[inline]
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
         (self.source.get(point)).mul_add(self.scale, self.bias)
     }
 
     #[cfg(target_os = "emscripten")]
+    #[inline]
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
         (self.source.get(point) * self.scale) + self.bias
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Abs, Clamp, Perlin};
+
+    #[test]
+    fn test_seed_forwarding() {
+        let noise: ScaleBias<f64, Clamp<f64, Abs<f64, Perlin, 2>, 2>, 2> =
+            Perlin::new(1).abs().clamp().scale_bias().set_bias(0.5);
+
+        let reseeded = noise.set_seed(42);
+
+        assert_eq!(42, reseeded.seed());
+        assert_eq!(42, reseeded.source.source.source.seed());
+        assert_eq!(0.5, reseeded.bias);
+        assert_eq!(
+            Perlin::new(42).get([0.3, 0.7]).abs().clamp(-1.0, 1.0) + 0.5,
+            reseeded.get([0.3, 0.7])
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::noise_fns::{NoiseFn, Seedable};
+use core::marker::PhantomData;
+
+/// Noise function that smoothly limits the output value from the source
+/// function to the range -`scale` to `scale`.
+///
+/// The output is `scale * tanh(x / scale)`, which is close to the identity for
+/// values much smaller than `scale` and approaches ±`scale` for large ones.
+/// Unlike [`Clamp`](crate::Clamp), the mapping is smooth everywhere and never
+/// flattens the output into a plateau, which avoids banding when the output
+/// is quantized.
+#[derive(Clone, Copy, Debug)]
+pub struct SoftClamp<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Asymptotic bound of the output. Default is 1.0.
+    pub scale: f64,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> SoftClamp<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub const DEFAULT_SCALE: f64 = 1.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            scale: Self::DEFAULT_SCALE,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_scale(self, scale: f64) -> Self {
+        Self { scale, ..self }
+    }
+}
+
+impl<T, Source, const DIM: usize> Seedable for SoftClamp<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for SoftClamp<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        self.scale * (self.source.get(point) / self.scale).tanh()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn soft_clamp(value: f64, scale: f64) -> f64 {
+        let soft_clamp = SoftClamp::new(Constant::new(value)).set_scale(scale);
+        NoiseFn::<f64, 2>::get(&soft_clamp, [0.0, 0.0])
+    }
+
+    #[test]
+    fn test_small_inputs_pass_through() {
+        assert_eq!(0.0, soft_clamp(0.0, 2.0));
+        for &value in [1e-3, -1e-3, 0.01, -0.02].iter() {
+            assert!((soft_clamp(value, 2.0) - value).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_large_inputs_approach_scale() {
+        assert!((soft_clamp(50.0, 2.0) - 2.0).abs() < 1e-12);
+        assert!((soft_clamp(-50.0, 2.0) + 2.0).abs() < 1e-12);
+        assert!((soft_clamp(1e6, 0.5) - 0.5).abs() < 1e-12);
+
+        // The output is strictly increasing and stays within the bound.
+        let mut previous = soft_clamp(-10.0, 1.5);
+        for i in -99..=100 {
+            let value = soft_clamp(i as f64 * 0.1, 1.5);
+            assert!(value > previous && value.abs() < 1.5);
+            previous = value;
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{math::interpolate, noise_fns::NoiseFn};
+use crate::{
+    math::interpolate,
+    noise_fns::{NoiseFn, Seedable},
+};
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
@@ -37,6 +40,11 @@ where
     /// inverted.
     pub invert_terraces: bool,
 
+    /// Fraction of each segment of the curve, at the top of its riser, over
+    /// which the riser is eased into the next step. Default is 0.0, which
+    /// gives hard terrace edges.
+    pub smoothness: f64,
+
     /// Vec that stores the control points.
     control_points: Vec<f64>,
 
@@ -51,6 +59,7 @@ where
         Terrace {
             source,
             invert_terraces: false,
+            smoothness: 0.0,
             control_points: Vec::with_capacity(2),
             phantom: PhantomData,
         }
@@ -76,7 +85,7 @@ where
                 .control_points
                 .iter()
                 .position(|&x| x >= control_point)
-                .unwrap_or_else(|| self.control_points.len());
+                .unwrap_or(self.control_points.len());
 
             // add the new control point at the correct position.
             self.control_points.insert(insertion_point, control_point);
@@ -94,6 +103,36 @@ where
             ..self
         }
     }
+
+    /// Sets the fraction of each segment of the curve over which its riser
+    /// is eased into the next step, giving rounded terrace edges.
+    ///
+    /// Within that fraction, the curve follows an S-curve that starts with the
+    /// slope of the riser and flattens out at the control point, so the
+    /// output stays smooth across step boundaries. The smoothness is clamped
+    /// to the range 0.0 to 1.0, and a smoothness of 0.0 gives hard edges.
+    pub fn set_smoothness(self, smoothness: f64) -> Self {
+        Terrace {
+            smoothness: smoothness.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+}
+
+impl<T, Source, const DIM: usize> Seedable for Terrace<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM> + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
 }
 
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Terrace<T, Source, DIM>
@@ -113,7 +152,7 @@ where
             .control_points
             .iter()
             .position(|&x| x >= source_value)
-            .unwrap_or_else(|| self.control_points.len());
+            .unwrap_or(self.control_points.len());
 
         // Find the two nearest control points so that we can map their values
         // onto a quadratic curve.
@@ -139,13 +178,80 @@ where
         }
 
         // Squaring the alpha produces the terrace effect.
-        alpha *= alpha;
+        alpha = ease_riser(alpha, self.smoothness);
 
         // Now perform the cubic interpolation and return.
         interpolate::linear(input0, input1, alpha)
     }
 }
 
+/// Squares `alpha`, easing the last `smoothness` of the curve into a slope of
+/// zero at 1.0 with a cubic Hermite curve that matches the slope of the square
+/// where it starts.
+fn ease_riser(alpha: f64, smoothness: f64) -> f64 {
+    let start = 1.0 - smoothness;
+    if alpha <= start {
+        return alpha * alpha;
+    }
+
+    let t = (alpha - start) / smoothness;
+    let (t2, t3) = (t * t, t * t * t);
+    let (value, slope) = (start * start, 2.0 * start * smoothness);
+
+    value * (2.0 * t3 - 3.0 * t2 + 1.0) + slope * (t3 - 2.0 * t2 + t) + (3.0 * t2 - 2.0 * t3)
+}
+
 fn clamp_index(index: isize, min: usize, max: usize) -> usize {
     index.clamp(min as isize, max as isize) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn terrace(value: f64, smoothness: f64) -> f64 {
+        Terrace::<f64, _, 2>::new(Constant::new(value))
+            .add_control_point(-1.0)
+            .add_control_point(0.0)
+            .add_control_point(1.0)
+            .set_smoothness(smoothness)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn test_zero_smoothness_matches_hard_terrace() {
+        for i in 0..=40 {
+            let value = i as f64 * 0.05 - 1.0;
+            let hard = Terrace::<f64, _, 2>::new(Constant::new(value))
+                .add_control_point(-1.0)
+                .add_control_point(0.0)
+                .add_control_point(1.0)
+                .get([0.0, 0.0]);
+
+            assert_eq!(hard, terrace(value, 0.0));
+        }
+    }
+
+    #[test]
+    fn test_smoothness_rounds_step_edges() {
+        let epsilon = 1e-6;
+        for &smoothness in &[0.25, 0.5, 1.0] {
+            // The output is continuous across the step boundary.
+            let (below, above) = (terrace(-epsilon, smoothness), terrace(epsilon, smoothness));
+            assert!((above - below).abs() < 1e-5);
+
+            // And the riser flattens out at the boundary instead of meeting
+            // the next step at an angle.
+            let slope = (terrace(0.0, smoothness) - below) / epsilon;
+            assert!(slope.abs() < 1e-3, "{}", slope);
+
+            // Away from the eased region, the curve is unchanged.
+            let value = -1.0 + (1.0 - smoothness) * 0.5;
+            assert_eq!(terrace(value, 0.0), terrace(value, smoothness));
+        }
+
+        let hard_slope = (terrace(0.0, 0.0) - terrace(-epsilon, 0.0)) / epsilon;
+        assert!(hard_slope > 1.9);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{math::interpolate, noise_fns::NoiseFn};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Noise function that maps the output values from two source functions onto
+/// a two-dimensional lookup table.
+///
+/// This is a transfer function of two variables: the output value from
+/// `source1` selects the column of the table and the output value from
+/// `source2` selects the row, which lets the shape of the remapping of
+/// `source1` vary with `source2`. For example, a table whose rows go from a
+/// straight line to an S-curve sharpens the contrast of `source1` where
+/// `source2` is high.
+///
+/// The table is stored row by row and is interpolated bilinearly. Each source
+/// value is mapped onto the table from its input range, -1.0 to 1.0 by
+/// default, and clamped to the edges of the table outside of it.
+#[derive(Clone, Debug)]
+pub struct Transfer2D<T, Source1, Source2, const DIM: usize>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    /// Outputs the value that selects the column of the table.
+    pub source1: Source1,
+
+    /// Outputs the value that selects the row of the table.
+    pub source2: Source2,
+
+    /// Range of `source1` values mapped onto the columns of the table. An
+    /// empty range maps every value onto the first column.
+    pub source1_range: (f64, f64),
+
+    /// Range of `source2` values mapped onto the rows of the table. An empty
+    /// range maps every value onto the first row.
+    pub source2_range: (f64, f64),
+
+    table: Vec<f64>,
+    width: usize,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source1, Source2, const DIM: usize> Transfer2D<T, Source1, Source2, DIM>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    /// Creates a new `Transfer2D` from a table with `width` columns, stored
+    /// row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table is empty or its length is not a multiple of
+    /// `width`.
+    pub fn new(source1: Source1, source2: Source2, table: Vec<f64>, width: usize) -> Self {
+        assert!(
+            width > 0 && !table.is_empty(),
+            "the table must not be empty"
+        );
+        assert_eq!(
+            0,
+            table.len() % width,
+            "the table length must be a multiple of its width"
+        );
+
+        Self {
+            source1,
+            source2,
+            source1_range: (-1.0, 1.0),
+            source2_range: (-1.0, 1.0),
+            table,
+            width,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets the range of `source1` values mapped onto the columns of the
+    /// table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not less than `max`.
+    pub fn set_source1_range(self, min: f64, max: f64) -> Self {
+        assert!(min < max, "the source1 range must not be empty");
+
+        Self {
+            source1_range: (min, max),
+            ..self
+        }
+    }
+
+    /// Sets the range of `source2` values mapped onto the rows of the
+    /// table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not less than `max`.
+    pub fn set_source2_range(self, min: f64, max: f64) -> Self {
+        assert!(min < max, "the source2 range must not be empty");
+
+        Self {
+            source2_range: (min, max),
+            ..self
+        }
+    }
+
+    /// Returns the number of columns and rows of the table.
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.table.len() / self.width)
+    }
+
+    /// Samples the table at the given pair of source values.
+    pub fn transfer(&self, value1: f64, value2: f64) -> f64 {
+        let (width, height) = self.size();
+
+        // Maps a value onto the table and splits it into the index of the
+        // cell and the position within it. An empty range maps every value
+        // onto the first cell.
+        let locate = |value: f64, (min, max): (f64, f64), len: usize| {
+            let extent = max - min;
+            let normalized = if extent > 0.0 {
+                ((value - min) / extent).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let position = normalized * (len - 1) as f64;
+            let index = (position as usize).min(len.saturating_sub(2));
+            (index, (position - index as f64).min(1.0))
+        };
+
+        let (x, x_alpha) = locate(value1, self.source1_range, width);
+        let (y, y_alpha) = locate(value2, self.source2_range, height);
+        let x1 = (x + 1).min(width - 1);
+        let y1 = (y + 1).min(height - 1);
+
+        let at = |x: usize, y: usize| self.table[x + y * width];
+        let top = interpolate::linear(at(x, y), at(x1, y), x_alpha);
+        let bottom = interpolate::linear(at(x, y1), at(x1, y1), x_alpha);
+
+        interpolate::linear(top, bottom, y_alpha)
+    }
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Transfer2D<T, Source1, Source2, DIM>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        let point = point.into();
+
+        self.transfer(self.source1.get(point), self.source2.get(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn transfer(value1: f64, value2: f64) -> f64 {
+        let transfer = Transfer2D::new(
+            Constant::new(value1),
+            Constant::new(value2),
+            vec![0.0, 1.0, 2.0, 3.0],
+            2,
+        );
+        NoiseFn::<f64, 2>::get(&transfer, [0.0, 0.0])
+    }
+
+    #[test]
+    fn test_known_table() {
+        // The corners of the input ranges map onto the entries of the table.
+        assert_eq!(0.0, transfer(-1.0, -1.0));
+        assert_eq!(1.0, transfer(1.0, -1.0));
+        assert_eq!(2.0, transfer(-1.0, 1.0));
+        assert_eq!(3.0, transfer(1.0, 1.0));
+
+        // Between them, the table is interpolated bilinearly.
+        assert_eq!(1.5, transfer(0.0, 0.0));
+        assert_eq!(0.75, transfer(0.5, -1.0));
+        assert_eq!(2.25, transfer(0.5, 0.5));
+
+        // Outside the input ranges, the edges of the table are used.
+        assert_eq!(3.0, transfer(4.0, 2.0));
+        assert_eq!(0.0, transfer(-4.0, -2.0));
+    }
+
+    #[test]
+    fn test_single_row_table() {
+        let transfer = Transfer2D::<f64, _, _, 2>::new(
+            Constant::new(0.0),
+            Constant::new(0.0),
+            vec![-1.0, 0.0, 3.0],
+            3,
+        );
+
+        assert_eq!((3, 1), transfer.size());
+        assert_eq!(1.5, transfer.transfer(0.5, 0.7));
+    }
+
+    #[test]
+    fn test_empty_range_uses_first_cell() {
+        let mut transfer = Transfer2D::<f64, _, _, 2>::new(
+            Constant::new(0.0),
+            Constant::new(0.0),
+            vec![0.0, 1.0, 2.0, 3.0],
+            2,
+        );
+        transfer.source1_range = (0.5, 0.5);
+        transfer.source2_range = (1.0, -1.0);
+
+        assert_eq!(0.0, transfer.transfer(0.5, 0.0));
+        assert_eq!(0.0, transfer.transfer(2.0, 4.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_range_is_rejected() {
+        let _ = Transfer2D::<f64, _, _, 2>::new(
+            Constant::new(0.0),
+            Constant::new(0.0),
+            vec![0.0, 1.0],
+            2,
+        )
+        .set_source1_range(1.0, 1.0);
+    }
+}
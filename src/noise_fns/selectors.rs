@@ -1,4 +1,12 @@
-pub use self::{blend::*, select::*};
+#[cfg(feature = "std")]
+pub use self::stateful_select::*;
+pub use self::{blend::*, blend3::*, either::*, optional_source::*, select::*, select_by_cell::*};
 
 mod blend;
+mod blend3;
+mod either;
+mod optional_source;
 mod select;
+mod select_by_cell;
+#[cfg(feature = "std")]
+mod stateful_select;
@@ -5,7 +5,15 @@ use core::marker::PhantomData;
 /// source functions given the output value supplied by a control function.
 ///
 /// This noise function uses linear interpolation to perform the blending
-/// operation.
+/// operation. When the control value gives all of the weight to one source,
+/// the other source is not evaluated.
+///
+/// A control value of 0.0 outputs the value from `source1` and 1.0 the value
+/// from `source2`. Control values outside of that range extrapolate beyond
+/// both sources unless [`set_clamp_control`](Self::set_clamp_control) is
+/// enabled, in which case the output always lies between the two source
+/// values, and the range reported by `value_range` is the union of the ranges
+/// of the sources.
 #[derive(Clone, Debug)]
 pub struct Blend<T, Source1, Source2, Control, const DIM: usize>
 where
@@ -19,12 +27,16 @@ where
     /// Outputs one of the values to blend.
     pub source2: Source2,
 
-    /// Determines the weight of the blending operation. Negative values weight
-    /// the blend towards the output value from the `source1` function. Positive
-    /// values weight the blend towards the output value from the `source2`
-    /// function.
+    /// Determines the weight of the blending operation. A value of 0.0 outputs
+    /// the value from `source1`, 1.0 the value from `source2`, and values in
+    /// between blend the two linearly. Values outside of 0.0 to 1.0
+    /// extrapolate unless [`set_clamp_control`](Self::set_clamp_control) is
+    /// enabled.
     pub control: Control,
 
+    /// Whether the control value is clamped to 0.0 to 1.0.
+    clamp_control: bool,
+
     phantom: PhantomData<T>,
 }
 
@@ -39,9 +51,19 @@ where
             source1,
             source2,
             control,
+            clamp_control: false,
             phantom: PhantomData,
         }
     }
+
+    /// Enables or disables clamping the control value to 0.0 to 1.0, so that
+    /// the output never extrapolates beyond the values of the two sources.
+    pub fn set_clamp_control(self, clamp_control: bool) -> Self {
+        Blend {
+            clamp_control,
+            ..self
+        }
+    }
 }
 
 impl<T, Source1, Source2, Control, const DIM: usize> NoiseFn<T, DIM>
@@ -54,10 +76,114 @@ where
 {
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
         let point = point.into();
+        let control = self.control.get(point);
+        let control = if self.clamp_control {
+            control.clamp(0.0, 1.0)
+        } else {
+            control
+        };
+
+        // Skip the source that has no weight in the blend.
+        if control == 0.0 {
+            return self.source1.get(point);
+        } else if control == 1.0 {
+            return self.source2.get(point);
+        }
+
         let lower = self.source1.get(point);
         let upper = self.source2.get(point);
-        let control = self.control.get(point);
 
         interpolate::linear(lower, upper, control)
     }
+
+    /// Returns the union of the ranges of the sources if the output is known
+    /// to lie between their values, i.e. if the control value is clamped or
+    /// its range lies within 0.0 to 1.0. Otherwise the output may extrapolate
+    /// beyond them, and the range is unbounded.
+    fn value_range(&self) -> (f64, f64) {
+        let (control_lower, control_upper) = self.control.value_range();
+        if !self.clamp_control && (control_lower < 0.0 || control_upper > 1.0) {
+            return (f64::NEG_INFINITY, f64::INFINITY);
+        }
+
+        let (lower1, upper1) = self.source1.value_range();
+        let (lower2, upper2) = self.source2.value_range();
+        (lower1.min(lower2), upper1.max(upper2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, NoiseFn, Perlin};
+    use core::cell::Cell;
+
+    #[test]
+    fn test_blend_constant() {
+        let point = [0.5, 0.5];
+        let blend =
+            |t| NoiseFn::<f64, 2>::blend_constant(Constant::new(-0.5), Constant::new(1.5), t);
+
+        assert_eq!(-0.5, blend(-1.0).get(point));
+        assert_eq!(1.5, blend(1.0).get(point));
+        assert_eq!(0.5, blend(0.0).get(point));
+    }
+
+    #[test]
+    fn test_unused_source_is_not_evaluated() {
+        struct Counting<'a>(&'a Cell<usize>);
+
+        impl NoiseFn<f64, 2> for Counting<'_> {
+            fn get(&self, _point: impl Into<[f64; 2]>) -> f64 {
+                self.0.set(self.0.get() + 1);
+                0.0
+            }
+        }
+
+        let (count1, count2) = (Cell::new(0), Cell::new(0));
+        let blend =
+            |control| Blend::new(Counting(&count1), Counting(&count2), Constant::new(control));
+
+        blend(0.0).get([0.0, 0.0]);
+        assert_eq!((1, 0), (count1.get(), count2.get()));
+
+        blend(1.0).get([0.0, 0.0]);
+        assert_eq!((1, 1), (count1.get(), count2.get()));
+
+        blend(0.5).get([0.0, 0.0]);
+        assert_eq!((2, 2), (count1.get(), count2.get()));
+    }
+
+    /// Source with a known range that outputs its lower bound.
+    struct Ranged(f64, f64);
+
+    impl NoiseFn<f64, 2> for Ranged {
+        fn get(&self, _point: impl Into<[f64; 2]>) -> f64 {
+            self.0
+        }
+
+        fn value_range(&self) -> (f64, f64) {
+            (self.0, self.1)
+        }
+    }
+
+    #[test]
+    fn test_value_range() {
+        let blend = |control| Blend::new(Ranged(-1.0, 1.0), Ranged(0.0, 2.0), control);
+
+        // A control value within 0.0 to 1.0 keeps the output between the
+        // sources.
+        assert_eq!((-1.0, 2.0), blend(Constant::new(0.25)).value_range());
+
+        // Otherwise the output can extrapolate, unless the control value is
+        // clamped.
+        let unbounded = Blend::new(Ranged(-1.0, 1.0), Ranged(0.0, 2.0), Perlin::new(1));
+        assert_eq!((f64::NEG_INFINITY, f64::INFINITY), unbounded.value_range());
+        let clamped = unbounded.set_clamp_control(true);
+        assert_eq!((-1.0, 2.0), clamped.value_range());
+
+        assert_eq!(-1.5, blend(Constant::new(-0.5)).get([0.0, 0.0]));
+        let clamped = blend(Constant::new(-0.5)).set_clamp_control(true);
+        assert_eq!(-1.0, clamped.get([0.0, 0.0]));
+    }
 }
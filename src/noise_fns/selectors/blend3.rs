@@ -0,0 +1,140 @@
+use crate::noise_fns::NoiseFn;
+use core::marker::PhantomData;
+
+/// Noise function that outputs a weighted blend of the output values from three
+/// source functions given the output values supplied by two control functions.
+///
+/// The output value of each control function is mapped from the range -1.0 to
+/// 1.0 onto a weight from 0.0 to 1.0, clamping values outside of that range,
+/// and weights the `source1` and `source2` functions respectively. The
+/// `source3` function receives whatever weight remains, so if both control
+/// values are -1.0 or below, the output value from `source3` is returned. The
+/// weights are normalized so that they always sum to one.
+#[derive(Clone, Debug)]
+pub struct Blend3<T, Source1, Source2, Source3, Control1, Control2, const DIM: usize>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+    Source3: NoiseFn<T, DIM>,
+    Control1: NoiseFn<T, DIM>,
+    Control2: NoiseFn<T, DIM>,
+{
+    /// Outputs one of the values to blend.
+    pub source1: Source1,
+
+    /// Outputs one of the values to blend.
+    pub source2: Source2,
+
+    /// Outputs one of the values to blend.
+    pub source3: Source3,
+
+    /// Determines the weight of the `source1` function, from none at -1.0 to
+    /// full at 1.0.
+    pub control1: Control1,
+
+    /// Determines the weight of the `source2` function, from none at -1.0 to
+    /// full at 1.0.
+    pub control2: Control2,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source1, Source2, Source3, Control1, Control2, const DIM: usize>
+    Blend3<T, Source1, Source2, Source3, Control1, Control2, DIM>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+    Source3: NoiseFn<T, DIM>,
+    Control1: NoiseFn<T, DIM>,
+    Control2: NoiseFn<T, DIM>,
+{
+    pub fn new(
+        source1: Source1,
+        source2: Source2,
+        source3: Source3,
+        control1: Control1,
+        control2: Control2,
+    ) -> Self {
+        Blend3 {
+            source1,
+            source2,
+            source3,
+            control1,
+            control2,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Source1, Source2, Source3, Control1, Control2, const DIM: usize> NoiseFn<T, DIM>
+    for Blend3<T, Source1, Source2, Source3, Control1, Control2, DIM>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+    Source3: NoiseFn<T, DIM>,
+    Control1: NoiseFn<T, DIM>,
+    Control2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        let point = point.into();
+
+        let weight1 = control_weight(self.control1.get(point));
+        let weight2 = control_weight(self.control2.get(point));
+        let weight3 = (1.0 - weight1 - weight2).max(0.0);
+        let total = weight1 + weight2 + weight3;
+
+        // Skip the sources that don't contribute to the blend.
+        let mut value = 0.0;
+        if weight1 > 0.0 {
+            value += self.source1.get(point) * weight1;
+        }
+        if weight2 > 0.0 {
+            value += self.source2.get(point) * weight2;
+        }
+        if weight3 > 0.0 {
+            value += self.source3.get(point) * weight3;
+        }
+
+        value / total
+    }
+}
+
+/// Maps a control value from -1.0 to 1.0 onto a weight from 0.0 to 1.0.
+fn control_weight(control: f64) -> f64 {
+    ((control + 1.0) * 0.5).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn blend(control1: f64, control2: f64) -> f64 {
+        let blend = Blend3::new(
+            Constant::new(1.0),
+            Constant::new(2.0),
+            Constant::new(3.0),
+            Constant::new(control1),
+            Constant::new(control2),
+        );
+        NoiseFn::<f64, 2>::get(&blend, [0.5, 0.5])
+    }
+
+    #[test]
+    fn test_extreme_controls_select_pure_sources() {
+        assert_eq!(1.0, blend(1.0, -1.0));
+        assert_eq!(1.0, blend(5.0, -5.0));
+        assert_eq!(2.0, blend(-1.0, 1.0));
+        assert_eq!(2.0, blend(-3.0, 3.0));
+        assert_eq!(3.0, blend(-1.0, -1.0));
+        assert_eq!(3.0, blend(-2.0, -4.0));
+    }
+
+    #[test]
+    fn test_weights_are_normalized() {
+        assert_eq!(1.5, blend(1.0, 1.0));
+        assert_eq!(1.5, blend(0.0, 0.0));
+        assert_eq!(2.25, blend(-0.5, -0.5));
+    }
+}
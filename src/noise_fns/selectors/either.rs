@@ -0,0 +1,78 @@
+use crate::noise_fns::{NoiseFn, Seedable};
+
+/// Noise function that outputs the value from whichever of two source
+/// functions it holds.
+///
+/// This picks one of two pipelines of different types at runtime, for example
+/// from a configuration, without boxing them: the choice is a match rather
+/// than a virtual call, and both variants are stored inline.
+#[derive(Clone, Copy, Debug)]
+pub enum Either<Left, Right> {
+    /// Outputs the value from the left source function.
+    Left(Left),
+
+    /// Outputs the value from the right source function.
+    Right(Right),
+}
+
+impl<Left, Right> Seedable for Either<Left, Right>
+where
+    Left: Seedable,
+    Right: Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        match self {
+            Either::Left(left) => Either::Left(left.set_seed(seed)),
+            Either::Right(right) => Either::Right(right.set_seed(seed)),
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        match self {
+            Either::Left(left) => left.seed(),
+            Either::Right(right) => right.seed(),
+        }
+    }
+}
+
+impl<T, Left, Right, const DIM: usize> NoiseFn<T, DIM> for Either<Left, Right>
+where
+    Left: NoiseFn<T, DIM>,
+    Right: NoiseFn<T, DIM>,
+{
+    #[inline]
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        match self {
+            Either::Left(left) => left.get(point),
+            Either::Right(right) => right.get(point),
+        }
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        match self {
+            Either::Left(left) => left.value_range(),
+            Either::Right(right) => right.value_range(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Perlin, Worley};
+
+    #[test]
+    fn test_dispatches_to_active_variant() {
+        let perlin = Perlin::new(3);
+        let worley = Worley::new(3);
+        let pipelines: [Either<Perlin, Worley>; 2] =
+            [Either::Left(perlin), Either::Right(worley.clone())];
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.37 - 1.1, i as f64 * 0.53 + 0.6];
+
+            assert_eq!(perlin.get(point), pipelines[0].get(point));
+            assert_eq!(worley.get(point), pipelines[1].get(point));
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the value from an optional source function, or
+/// a default value when there is no source.
+///
+/// This lets optional layers, for example ones enabled from a configuration,
+/// be used in a pipeline without matching on them at every call site.
+#[derive(Clone, Copy, Debug)]
+pub struct OptionalSource<Source> {
+    /// Outputs a value, if present.
+    pub source: Option<Source>,
+
+    /// Value output when there is no source function.
+    pub default: f64,
+}
+
+impl<Source> OptionalSource<Source> {
+    pub fn new(source: Option<Source>, default: f64) -> Self {
+        Self { source, default }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for OptionalSource<Source>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    #[inline]
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        match &self.source {
+            Some(source) => source.get(point),
+            None => self.default,
+        }
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        match &self.source {
+            Some(source) => source.value_range(),
+            None => (self.default, self.default),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+    use alloc::boxed::Box;
+
+    #[test]
+    fn test_forwards_or_returns_default() {
+        let perlin = Perlin::new(3);
+        let some = OptionalSource::new(Some(Box::new(perlin)), 0.5);
+        let none = OptionalSource::<Box<Perlin>>::new(None, 0.5);
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.37 - 1.1, i as f64 * 0.53 + 0.6];
+
+            assert_eq!(perlin.get(point), some.get(point));
+            assert_eq!(0.5, none.get(point));
+        }
+        assert_eq!((0.5, 0.5), NoiseFn::<f64, 2>::value_range(&none));
+    }
+}
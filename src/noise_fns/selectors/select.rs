@@ -1,13 +1,46 @@
 use crate::{
-    math::{interpolate, s_curve::cubic::Cubic},
-    noise_fns::NoiseFn,
+    math::{
+        interpolate,
+        s_curve::{cubic::Cubic, quintic::Quintic},
+    },
+    noise_fns::{modifiers::Spline, NoiseFn},
 };
 use core::marker::PhantomData;
 
+/// S-curve used by [`Select`] to blend between its sources within the falloff
+/// region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FalloffCurve {
+    /// Cubic S-curve (smoothstep). The blend has a first derivative of zero at
+    /// the edges of the falloff region.
+    Cubic,
+
+    /// Quintic S-curve (smootherstep). The blend has first and second
+    /// derivatives of zero at the edges of the falloff region, which avoids
+    /// subtle banding along the transition.
+    Quintic,
+}
+
+impl FalloffCurve {
+    fn map(self, alpha: f64) -> f64 {
+        match self {
+            FalloffCurve::Cubic => alpha.map_cubic(),
+            FalloffCurve::Quintic => alpha.map_quintic(),
+        }
+    }
+}
+
 /// Noise function that outputs the value selected from one of two source
 /// functions chosen by the output value from a control function.
+///
+/// Only the sources that contribute to the output are evaluated, so outside
+/// of the falloff region the unselected source is never sampled.
+///
+/// `Transition` is the type of the source set by
+/// [`set_transition_source`](Self::set_transition_source); it is `()` until
+/// one is set.
 #[derive(Clone, Debug)]
-pub struct Select<T, Source1, Source2, Control, const DIM: usize>
+pub struct Select<T, Source1, Source2, Control, Transition, const DIM: usize>
 where
     Source1: NoiseFn<T, DIM>,
     Source2: NoiseFn<T, DIM>,
@@ -31,10 +64,21 @@ where
     /// Edge falloff value. Default is 0.0.
     pub falloff: f64,
 
+    /// Curve used to blend the sources within the falloff region. Default is
+    /// [`FalloffCurve::Cubic`].
+    pub falloff_curve: FalloffCurve,
+
+    /// Perturbs the position within the falloff region. Default is `()`,
+    /// which leaves it unchanged.
+    pub transition: Transition,
+
+    /// Remaps the control value before it is tested against the bounds.
+    control_curve: Option<Spline>,
+
     phantom: PhantomData<T>,
 }
 
-impl<T, Source1, Source2, Control, const DIM: usize> Select<T, Source1, Source2, Control, DIM>
+impl<T, Source1, Source2, Control, const DIM: usize> Select<T, Source1, Source2, Control, (), DIM>
 where
     Source1: NoiseFn<T, DIM>,
     Source2: NoiseFn<T, DIM>,
@@ -47,10 +91,21 @@ where
             control,
             bounds: (0.0, 1.0),
             falloff: 0.0,
+            falloff_curve: FalloffCurve::Cubic,
+            transition: (),
+            control_curve: None,
             phantom: PhantomData,
         }
     }
+}
 
+impl<T, Source1, Source2, Control, Transition, const DIM: usize>
+    Select<T, Source1, Source2, Control, Transition, DIM>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+    Control: NoiseFn<T, DIM>,
+{
     pub fn set_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
         Select {
             bounds: (lower_bound, upper_bound),
@@ -61,19 +116,134 @@ where
     pub fn set_falloff(self, falloff: f64) -> Self {
         Select { falloff, ..self }
     }
+
+    pub fn set_falloff_curve(self, falloff_curve: FalloffCurve) -> Self {
+        Select {
+            falloff_curve,
+            ..self
+        }
+    }
+
+    /// Returns the lower bound of the selection range. The default is 0.0.
+    pub fn lower_bound(&self) -> f64 {
+        self.bounds.0
+    }
+
+    /// Returns the upper bound of the selection range. The default is 1.0.
+    pub fn upper_bound(&self) -> f64 {
+        self.bounds.1
+    }
+
+    /// Returns the edge falloff value. The default is 0.0.
+    pub fn falloff(&self) -> f64 {
+        self.falloff
+    }
+
+    /// Sets a second control function that perturbs the blend within the
+    /// falloff region, which gives feathered or ridged transitions instead of
+    /// smooth ones.
+    ///
+    /// The output of `transition`, expected in the range -1.0 to 1.0, pushes
+    /// the position within the falloff region towards `source2` (positive
+    /// values) or `source1` (negative values) before the falloff curve is
+    /// applied. The perturbation fades out at the edges of the falloff
+    /// region, so the transition stays continuous. It has no effect without
+    /// a falloff.
+    pub fn set_transition_source<Other>(
+        self,
+        transition: Other,
+    ) -> Select<T, Source1, Source2, Control, Other, DIM>
+    where
+        Other: NoiseFn<T, DIM>,
+    {
+        Select {
+            source1: self.source1,
+            source2: self.source2,
+            control: self.control,
+            bounds: self.bounds,
+            falloff: self.falloff,
+            falloff_curve: self.falloff_curve,
+            transition,
+            control_curve: self.control_curve,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Passes the control value through a cubic spline through
+    /// `control_points`, given as `(input, output)` pairs, before it is tested
+    /// against the selection range. This warps where the transitions happen
+    /// without altering the control function. The spline is evaluated like a
+    /// [`Curve`](crate::Curve) with clamped ends, so control values outside
+    /// of the control points map to the output of the nearest end point.
+    ///
+    /// A steep curve around a bound narrows the falloff region there, and a
+    /// flat one widens it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than four control points with distinct
+    /// inputs.
+    pub fn set_control_curve(self, control_points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let mut spline = Spline::new();
+        for (input, output) in control_points {
+            spline.add_control_point(input, output);
+        }
+        assert!(
+            spline.len() >= 4,
+            "the control curve requires at least four control points"
+        );
+
+        Select {
+            control_curve: Some(spline),
+            ..self
+        }
+    }
+
+    /// Returns the blend weight at `alpha`, the position within a falloff
+    /// region. `direction` is 1.0 if the region blends towards `source2` and
+    /// -1.0 if it blends towards `source1`.
+    fn blend_weight(&self, point: [T; DIM], alpha: f64, direction: f64) -> f64
+    where
+        Transition: NoiseFn<T, DIM>,
+    {
+        let offset = direction * self.transition.get(point) * alpha * (1.0 - alpha);
+
+        self.falloff_curve.map((alpha + offset).clamp(0.0, 1.0))
+    }
+
+    /// Blends from `from` towards `to` by `alpha`, only evaluating the
+    /// sources that contribute to the result.
+    fn blend<From, To>(&self, point: [T; DIM], from: &From, to: &To, alpha: f64) -> f64
+    where
+        T: Copy,
+        From: NoiseFn<T, DIM>,
+        To: NoiseFn<T, DIM>,
+    {
+        if alpha <= 0.0 {
+            from.get(point)
+        } else if alpha >= 1.0 {
+            to.get(point)
+        } else {
+            interpolate::linear(from.get(point), to.get(point), alpha)
+        }
+    }
 }
 
-impl<T, Source1, Source2, Control, const DIM: usize> NoiseFn<T, DIM>
-    for Select<T, Source1, Source2, Control, DIM>
+impl<T, Source1, Source2, Control, Transition, const DIM: usize> NoiseFn<T, DIM>
+    for Select<T, Source1, Source2, Control, Transition, DIM>
 where
     T: Copy,
     Source1: NoiseFn<T, DIM>,
     Source2: NoiseFn<T, DIM>,
     Control: NoiseFn<T, DIM>,
+    Transition: NoiseFn<T, DIM>,
 {
     fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
         let point = point.into();
-        let control_value = self.control.get(point);
+        let mut control_value = self.control.get(point);
+        if let Some(curve) = &self.control_curve {
+            control_value = curve.map_value(control_value);
+        }
         let (lower, upper) = self.bounds;
 
         if self.falloff > 0.0 {
@@ -82,19 +252,25 @@ where
                 _ if control_value < (lower + self.falloff) => {
                     let lower_curve = lower - self.falloff;
                     let upper_curve = lower + self.falloff;
-                    let alpha =
-                        ((control_value - lower_curve) / (upper_curve - lower_curve)).map_cubic();
+                    let alpha = self.blend_weight(
+                        point,
+                        (control_value - lower_curve) / (upper_curve - lower_curve),
+                        1.0,
+                    );
 
-                    interpolate::linear(self.source1.get(point), self.source2.get(point), alpha)
+                    self.blend(point, &self.source1, &self.source2, alpha)
                 }
                 _ if control_value < (upper - self.falloff) => self.source2.get(point),
                 _ if control_value < (upper + self.falloff) => {
                     let lower_curve = upper - self.falloff;
                     let upper_curve = upper + self.falloff;
-                    let alpha =
-                        ((control_value - lower_curve) / (upper_curve - lower_curve)).map_cubic();
+                    let alpha = self.blend_weight(
+                        point,
+                        (control_value - lower_curve) / (upper_curve - lower_curve),
+                        -1.0,
+                    );
 
-                    interpolate::linear(self.source2.get(point), self.source1.get(point), alpha)
+                    self.blend(point, &self.source2, &self.source1, alpha)
                 }
                 _ => self.source1.get(point),
             }
@@ -105,3 +281,194 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin};
+    use core::cell::Cell;
+
+    /// Source that counts how many times it has been evaluated.
+    struct Counting<'a>(&'a Cell<usize>);
+
+    impl NoiseFn<f64, 2> for Counting<'_> {
+        fn get(&self, _point: impl Into<[f64; 2]>) -> f64 {
+            self.0.set(self.0.get() + 1);
+            0.0
+        }
+    }
+
+    fn blend_weight(curve: FalloffCurve, control: f64) -> f64 {
+        Select::new(
+            Constant::new(0.0),
+            Constant::new(1.0),
+            Constant::new(control),
+        )
+        .set_bounds(0.0, 1.0)
+        .set_falloff(0.2)
+        .set_falloff_curve(curve)
+        .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn test_getters() {
+        let select = Select::<f64, _, _, _, _, 2>::new(
+            Constant::new(0.0),
+            Constant::new(1.0),
+            Constant::new(0.0),
+        );
+        assert_eq!(
+            (0.0, 1.0, 0.0),
+            (select.lower_bound(), select.upper_bound(), select.falloff())
+        );
+
+        let select = select.set_bounds(-0.25, 0.75).set_falloff(0.125);
+        assert_eq!(-0.25, select.lower_bound());
+        assert_eq!(0.75, select.upper_bound());
+        assert_eq!(0.125, select.falloff());
+    }
+
+    #[test]
+    fn test_falloff_curve_profiles() {
+        // Both curves cross the midpoint of the falloff region at one half.
+        assert_eq!(0.5, blend_weight(FalloffCurve::Cubic, 0.0));
+        assert_eq!(0.5, blend_weight(FalloffCurve::Quintic, 0.0));
+
+        // Away from the midpoint the quintic curve stays flatter near the edge.
+        let cubic = blend_weight(FalloffCurve::Cubic, -0.1);
+        let quintic = blend_weight(FalloffCurve::Quintic, -0.1);
+        assert!((cubic - 0.156_25).abs() < 1e-12);
+        assert!((quintic - 0.103_515_625).abs() < 1e-12);
+        assert!(quintic < cubic);
+    }
+
+    #[test]
+    fn test_transition_source() {
+        let select = || {
+            Select::new(Constant::new(0.0), Constant::new(1.0), Constant::new(0.05))
+                .set_bounds(0.0, 1.0)
+                .set_falloff(0.2)
+        };
+        let points = (0..64)
+            .map(|i| [i as f64 * 0.37 + 0.1, i as f64 * 0.23 - 1.7])
+            .collect::<alloc::vec::Vec<_>>();
+
+        // Without a transition source, the blend only depends on the control.
+        let plain = select();
+        assert!(points
+            .iter()
+            .all(|&point| plain.get(point) == blend_weight(FalloffCurve::Cubic, 0.05)));
+
+        // With one, the blend weight varies within the falloff region but
+        // stays within the range of the sources.
+        let feathered = select().set_transition_source(Perlin::new(1));
+        let weights = points
+            .iter()
+            .map(|&point| feathered.get(point))
+            .collect::<alloc::vec::Vec<_>>();
+        assert!(weights.iter().all(|&weight| (0.0..=1.0).contains(&weight)));
+        assert!(weights
+            .iter()
+            .any(|&weight| weight < plain.get([0.0, 0.0]) - 0.01));
+        assert!(weights
+            .iter()
+            .any(|&weight| weight > plain.get([0.0, 0.0]) + 0.01));
+    }
+
+    #[test]
+    fn test_transition_source_is_borrowed() {
+        let count = Cell::new(0);
+        let select = Select::new(Constant::new(0.0), Constant::new(1.0), Constant::new(0.05))
+            .set_bounds(0.0, 1.0)
+            .set_falloff(0.2)
+            .set_transition_source(Counting(&count));
+
+        // The transition source is only sampled within the falloff region.
+        select.get([0.0, 0.0]);
+        assert_eq!(count.get(), 1);
+        select.set_bounds(0.5, 1.0).get([0.0, 0.0]);
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn test_unused_source_is_not_evaluated() {
+        let (count1, count2) = (Cell::new(0), Cell::new(0));
+        let select = |control| {
+            Select::new(Counting(&count1), Counting(&count2), Constant::new(control))
+                .set_bounds(0.0, 1.0)
+                .set_falloff(0.2)
+        };
+
+        // Well outside the falloff region, only the selected source is used.
+        select(-0.5).get([0.0, 0.0]);
+        select(2.0).get([0.0, 0.0]);
+        assert_eq!((2, 0), (count1.get(), count2.get()));
+
+        select(0.5).get([0.0, 0.0]);
+        assert_eq!((2, 1), (count1.get(), count2.get()));
+
+        // Within it, both sources are blended.
+        select(0.1).get([0.0, 0.0]);
+        assert_eq!((3, 2), (count1.get(), count2.get()));
+    }
+
+    #[test]
+    fn test_control_curve() {
+        let select = |control| {
+            Select::new(
+                Constant::new(0.0),
+                Constant::new(1.0),
+                Constant::new(control),
+            )
+            .set_bounds(0.0, 1.0)
+            .set_falloff(0.2)
+        };
+
+        // An identity curve leaves the selection unchanged.
+        let identity = (-6..=6)
+            .map(|i| (i as f64 * 0.25, i as f64 * 0.25))
+            .collect::<alloc::vec::Vec<_>>();
+        for i in -4..=4 {
+            let control = i as f64 * 0.25;
+            assert_eq!(
+                select(control).get([0.0, 0.0]),
+                select(control)
+                    .set_control_curve(identity.iter().copied())
+                    .get([0.0, 0.0])
+            );
+        }
+
+        // A steep curve around the lower bound narrows the transition there.
+        let steep = (-60..=60)
+            .map(|i| (i as f64 * 0.01, (i as f64 * 0.1).clamp(-0.5, 0.5)))
+            .collect::<alloc::vec::Vec<_>>();
+        let blended = |curve: Option<&[(f64, f64)]>| {
+            (-50..=50)
+                .map(|i| i as f64 * 0.01)
+                .filter(|&control| {
+                    let select = match curve {
+                        Some(points) => select(control).set_control_curve(points.iter().copied()),
+                        None => select(control),
+                    };
+                    let value = select.get([0.0, 0.0]);
+                    value > 0.0 && value < 1.0
+                })
+                .count()
+        };
+
+        let (plain, steep) = (blended(None), blended(Some(&steep)));
+        assert!(steep > 0);
+        assert!(steep * 4 < plain, "{} vs {}", steep, plain);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_control_curve_requires_four_points() {
+        let _ = Select::<f64, _, _, _, _, 2>::new(
+            Constant::new(0.0),
+            Constant::new(1.0),
+            Constant::new(0.5),
+        )
+        .set_control_curve([(0.0, 0.0), (0.5, 0.5), (1.0, 1.0), (1.0, 0.0)]);
+    }
+}
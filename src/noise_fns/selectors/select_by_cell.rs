@@ -0,0 +1,148 @@
+use crate::noise_fns::NoiseFn;
+use alloc::{collections::BTreeMap, sync::Arc};
+use core::marker::PhantomData;
+
+/// Source function stored by [`SelectByCell`].
+type CellSource<T, const DIM: usize> = dyn Fn([T; DIM]) -> f64 + Send + Sync;
+
+/// Noise function that outputs the value from a source function chosen by
+/// the integer cell id output by a control function.
+///
+/// The control value is rounded to the nearest integer to give the cell id.
+/// If a source function has been assigned to that id with
+/// [`set_source`](Self::set_source), its value is output; otherwise the value
+/// of the fallback source function is. Since every point in a cell shares its
+/// id, whole cells are assigned to a source, with hard edges between cells.
+///
+/// This is meant for assigning biomes to Voronoi cells. [`Worley`] with
+/// [`ReturnType::Value`] outputs one of 256 evenly spaced values per cell, so
+/// passing it through [`ScaleBias`] with a scale and bias of 127.5 gives cell
+/// ids from 0 to 255.
+///
+/// The source functions may be of different types, so they are boxed; only
+/// the sources that are selected are evaluated.
+///
+/// [`Worley`]: crate::Worley
+/// [`ReturnType::Value`]: crate::core::worley::ReturnType::Value
+/// [`ScaleBias`]: crate::ScaleBias
+#[derive(Clone)]
+pub struct SelectByCell<T, Control, const DIM: usize>
+where
+    Control: NoiseFn<T, DIM>,
+{
+    /// Outputs the cell id of each point.
+    pub control: Control,
+
+    sources: BTreeMap<i64, Arc<CellSource<T, DIM>>>,
+
+    fallback: Arc<CellSource<T, DIM>>,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Control, const DIM: usize> SelectByCell<T, Control, DIM>
+where
+    T: 'static,
+    Control: NoiseFn<T, DIM>,
+{
+    /// Creates the noise function, outputting the value from `fallback` in
+    /// every cell until sources are assigned to cell ids.
+    pub fn new<Fallback>(control: Control, fallback: Fallback) -> Self
+    where
+        Fallback: NoiseFn<T, DIM> + Send + Sync + 'static,
+    {
+        Self {
+            control,
+            sources: BTreeMap::new(),
+            fallback: Arc::new(move |point| fallback.get(point)),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Assigns `source` to the cells with the given id, replacing the source
+    /// previously assigned to them.
+    pub fn set_source<Source>(mut self, id: i64, source: Source) -> Self
+    where
+        Source: NoiseFn<T, DIM> + Send + Sync + 'static,
+    {
+        self.sources
+            .insert(id, Arc::new(move |point| source.get(point)));
+        self
+    }
+
+    /// Returns the ids of the cells that have been assigned a source, in
+    /// ascending order.
+    pub fn mapped_ids(&self) -> impl Iterator<Item = i64> + '_ {
+        self.sources.keys().copied()
+    }
+}
+
+impl<T, Control, const DIM: usize> NoiseFn<T, DIM> for SelectByCell<T, Control, DIM>
+where
+    T: Copy,
+    Control: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        let point = point.into();
+        let id = self.control.get(point).round() as i64;
+
+        match self.sources.get(&id) {
+            Some(source) => source(point),
+            None => (self.fallback)(point),
+        }
+    }
+}
+
+impl<T, Control, const DIM: usize> core::fmt::Debug for SelectByCell<T, Control, DIM>
+where
+    Control: NoiseFn<T, DIM> + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SelectByCell")
+            .field("control", &self.control)
+            .field("sources", &self.sources.keys())
+            .field("fallback", &core::format_args!("..."))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::worley::ReturnType, Constant, Perlin, ScaleBias, Worley};
+
+    #[test]
+    fn test_mapped_cells_use_their_source() {
+        let cells = ScaleBias::new(Worley::new(3).set_return_type(ReturnType::Value))
+            .set_scale(127.5)
+            .set_bias(127.5);
+        let points = (0..64)
+            .map(|i| [i as f64 * 0.37 - 4.1, i as f64 * 0.23 + 0.6])
+            .collect::<alloc::vec::Vec<_>>();
+
+        // Assign a source to the cell of the first point only.
+        let mapped_id = cells.get(points[0]).round() as i64;
+        let select = SelectByCell::new(cells.clone(), Constant::new(-1.0))
+            .set_source(mapped_id, Constant::new(1.0))
+            .set_source(mapped_id + 1000, Perlin::new(1));
+        assert_eq!(
+            vec![mapped_id, mapped_id + 1000],
+            select.mapped_ids().collect::<alloc::vec::Vec<_>>()
+        );
+
+        let mut unmapped = 0;
+        for &point in &points {
+            let id = cells.get(point).round() as i64;
+            assert!((0..=255).contains(&id));
+            assert!((cells.get(point) - id as f64).abs() < 1e-9);
+
+            if id == mapped_id {
+                assert_eq!(1.0, select.get(point));
+            } else {
+                assert_eq!(-1.0, select.get(point));
+                unmapped += 1;
+            }
+        }
+        assert!(unmapped > 0);
+    }
+}
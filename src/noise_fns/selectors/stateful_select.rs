@@ -0,0 +1,264 @@
+use crate::noise_fns::NoiseFn;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+};
+
+/// Noise function that outputs the value selected from one of two source
+/// functions chosen by the output value from a control function, with a
+/// hysteresis band around the bounds of the selection range.
+///
+/// Like [`Select`](crate::Select), this outputs the value from `source2` if the
+/// output value from the control function is within the selection range, and
+/// the value from `source1` otherwise. However, the source selected at each
+/// point is remembered: once `source2` is selected, it stays selected until
+/// the control value leaves the selection range widened by `hysteresis` on
+/// both sides, and once `source1` is selected, it stays selected until the
+/// control value enters the selection range narrowed by `hysteresis`.
+///
+/// This stops points whose control value hovers near a bound from flickering
+/// between sources when the control function is animated. The first sample
+/// at a point uses the plain selection range.
+///
+/// Evaluation is stateful: points are remembered by their exact coordinates,
+/// so the memory used grows with the number of distinct points sampled, up to
+/// `capacity` points. Past that, the points sampled first are forgotten first.
+/// Call [`reset`](Self::reset) to forget every point.
+#[derive(Debug)]
+pub struct StatefulSelect<Source1, Source2, Control, const DIM: usize>
+where
+    Source1: NoiseFn<f64, DIM>,
+    Source2: NoiseFn<f64, DIM>,
+    Control: NoiseFn<f64, DIM>,
+{
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+
+    /// Determines the value to select.
+    pub control: Control,
+
+    /// Bounds of the selection range. Default is 0.0 to 1.0.
+    pub bounds: (f64, f64),
+
+    /// Width of the hysteresis band on either side of each bound. Default is
+    /// 0.0.
+    pub hysteresis: f64,
+
+    /// Maximum number of points to remember the selected source at. Default
+    /// is [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY).
+    pub capacity: usize,
+
+    /// Source selected at each sampled point.
+    selected: Mutex<Selection<DIM>>,
+}
+
+/// Whether `source2` is currently selected at each sampled point, along with
+/// the order in which the points were first sampled.
+#[derive(Clone, Debug, Default)]
+struct Selection<const DIM: usize> {
+    points: HashMap<[u64; DIM], bool>,
+    order: VecDeque<[u64; DIM]>,
+}
+
+impl<Source1, Source2, Control, const DIM: usize> StatefulSelect<Source1, Source2, Control, DIM>
+where
+    Source1: NoiseFn<f64, DIM>,
+    Source2: NoiseFn<f64, DIM>,
+    Control: NoiseFn<f64, DIM>,
+{
+    /// Enough points for a 1024 by 1024 image.
+    pub const DEFAULT_CAPACITY: usize = 1 << 20;
+
+    pub fn new(source1: Source1, source2: Source2, control: Control) -> Self {
+        StatefulSelect {
+            source1,
+            source2,
+            control,
+            bounds: (0.0, 1.0),
+            hysteresis: 0.0,
+            capacity: Self::DEFAULT_CAPACITY,
+            selected: Mutex::new(Selection::default()),
+        }
+    }
+
+    pub fn set_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
+        StatefulSelect {
+            bounds: (lower_bound, upper_bound),
+            ..self
+        }
+    }
+
+    pub fn set_hysteresis(self, hysteresis: f64) -> Self {
+        StatefulSelect { hysteresis, ..self }
+    }
+
+    /// Sets the maximum number of points to remember the selected source at.
+    /// Once it is reached, sampling a new point forgets the point that was
+    /// sampled first.
+    pub fn set_capacity(self, capacity: usize) -> Self {
+        StatefulSelect { capacity, ..self }
+    }
+
+    /// Forgets the source selected at every point.
+    pub fn reset(&self) {
+        let mut selected = self.selected.lock().unwrap();
+        selected.points.clear();
+        selected.order.clear();
+    }
+}
+
+impl<Source1, Source2, Control, const DIM: usize> Clone
+    for StatefulSelect<Source1, Source2, Control, DIM>
+where
+    Source1: NoiseFn<f64, DIM> + Clone,
+    Source2: NoiseFn<f64, DIM> + Clone,
+    Control: NoiseFn<f64, DIM> + Clone,
+{
+    /// Clones the noise function along with the source selected at every
+    /// sampled point.
+    fn clone(&self) -> Self {
+        StatefulSelect {
+            source1: self.source1.clone(),
+            source2: self.source2.clone(),
+            control: self.control.clone(),
+            bounds: self.bounds,
+            hysteresis: self.hysteresis,
+            capacity: self.capacity,
+            selected: Mutex::new(self.selected.lock().unwrap().clone()),
+        }
+    }
+}
+
+impl<Source1, Source2, Control, const DIM: usize> NoiseFn<f64, DIM>
+    for StatefulSelect<Source1, Source2, Control, DIM>
+where
+    Source1: NoiseFn<f64, DIM>,
+    Source2: NoiseFn<f64, DIM>,
+    Control: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: impl Into<[f64; DIM]>) -> f64 {
+        let point = point.into();
+        let control_value = self.control.get(point);
+        let (lower, upper) = self.bounds;
+
+        let mut key = [0; DIM];
+        for (key, coordinate) in key.iter_mut().zip(point.iter()) {
+            *key = coordinate.to_bits();
+        }
+
+        let use_source2 = {
+            let mut selected = self.selected.lock().unwrap();
+            let band = match selected.points.get(&key) {
+                Some(true) => -self.hysteresis,
+                Some(false) => self.hysteresis,
+                None => 0.0,
+            };
+            let use_source2 = control_value >= lower + band && control_value <= upper - band;
+
+            if selected.points.insert(key, use_source2).is_none() {
+                selected.order.push_back(key);
+            }
+            while selected.order.len() > self.capacity {
+                if let Some(oldest) = selected.order.pop_front() {
+                    selected.points.remove(&oldest);
+                }
+            }
+            use_source2
+        };
+
+        if use_source2 {
+            self.source2.get(point)
+        } else {
+            self.source1.get(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+    use std::cell::Cell;
+
+    /// Control function that outputs a value that can be changed between
+    /// samples, like an animated control.
+    #[derive(Clone)]
+    struct Animated<'a>(&'a Cell<f64>);
+
+    impl NoiseFn<f64, 2> for Animated<'_> {
+        fn get(&self, _point: impl Into<[f64; 2]>) -> f64 {
+            self.0.get()
+        }
+    }
+
+    #[test]
+    fn test_hysteresis_band_prevents_switching() {
+        let control = Cell::new(0.05);
+        let select =
+            StatefulSelect::new(Constant::new(-1.0), Constant::new(1.0), Animated(&control))
+                .set_bounds(0.0, 1.0)
+                .set_hysteresis(0.1);
+
+        // Inside the selection range, source2 is selected and stays selected
+        // while the control oscillates around the lower bound.
+        assert_eq!(1.0, select.get([0.5, 0.5]));
+        for &value in [-0.05, 0.05, -0.09, 0.02, -0.02].iter() {
+            control.set(value);
+            assert_eq!(1.0, select.get([0.5, 0.5]));
+        }
+
+        // Leaving the widened range switches to source1, which then stays
+        // selected until the control is well inside the selection range.
+        control.set(-0.15);
+        assert_eq!(-1.0, select.get([0.5, 0.5]));
+        for &value in [0.05, -0.05, 0.09, 0.0].iter() {
+            control.set(value);
+            assert_eq!(-1.0, select.get([0.5, 0.5]));
+        }
+        control.set(0.15);
+        assert_eq!(1.0, select.get([0.5, 0.5]));
+
+        // Clones remember the selected sources.
+        control.set(0.05);
+        assert_eq!(1.0, select.clone().get([0.5, 0.5]));
+
+        // Other points and reset points start from the plain selection range.
+        control.set(0.05);
+        assert_eq!(1.0, select.get([1.5, 0.5]));
+        control.set(-0.05);
+        select.reset();
+        assert_eq!(-1.0, select.get([0.5, 0.5]));
+    }
+
+    #[test]
+    fn test_capacity_forgets_oldest_points() {
+        let control = Cell::new(0.5);
+        let select =
+            StatefulSelect::new(Constant::new(-1.0), Constant::new(1.0), Animated(&control))
+                .set_hysteresis(0.1)
+                .set_capacity(2);
+        let default = StatefulSelect::<_, _, _, 2>::new(
+            Constant::new(0.0),
+            Constant::new(0.0),
+            Constant::new(0.0),
+        );
+        assert_eq!(1 << 20, default.capacity);
+
+        assert_eq!(1.0, select.get([0.0, 0.0]));
+        assert_eq!(1.0, select.get([1.0, 0.0]));
+
+        // Resampling a point doesn't change the order it is forgotten in.
+        control.set(-0.05);
+        assert_eq!(1.0, select.get([0.0, 0.0]));
+
+        // A third point forgets the first, which then starts from the plain
+        // selection range, but the second is still remembered.
+        assert_eq!(-1.0, select.get([2.0, 0.0]));
+        assert_eq!(1.0, select.get([1.0, 0.0]));
+        assert_eq!(-1.0, select.get([0.0, 0.0]));
+        assert_eq!(2, select.selected.lock().unwrap().points.len());
+    }
+}
@@ -1,8 +1,17 @@
-pub use self::{displace::*, rotate_point::*, scale_point::*, translate_point::*, turbulence::*};
+pub use self::{
+    displace::*, lift::*, looping_time::*, map_point::*, repeat::*, rotate_point::*,
+    scale_point::*, slice::*, translate_by_noise::*, translate_point::*, turbulence::*,
+};
 
 mod displace;
+mod lift;
+mod looping_time;
+mod map_point;
+mod repeat;
 mod rotate_point;
 mod scale_point;
+mod slice;
+mod translate_by_noise;
 mod translate_point;
 mod turbulence;
 
@@ -1,7 +1,20 @@
-use crate::noise_fns::NoiseFn;
+use super::turbulence::displacement_seed;
+use crate::noise_fns::{NoiseFn, Seedable};
 
 /// Noise function that uses multiple source functions to displace each coordinate
 /// of the input value before returning the output value from the `source` function.
+///
+/// Passing the same displacement function for every axis moves each point
+/// by the same amount along every axis, so the warp is always diagonal. Use
+/// [`new_decorrelated`](Self::new_decorrelated) to derive an independent
+/// displacement for each axis from a single function instead.
+///
+/// By default every displacement function is sampled at the input value. In
+/// feedback mode, set with [`set_feedback`](Self::set_feedback), the axes are
+/// displaced one after another, and each displacement function is sampled at
+/// the input value as already displaced along the previous axes. Feedback
+/// folds the earlier displacements into the later ones, which gives stronger,
+/// more swirling warps, but it makes the warp depend on the order of the axes.
 #[derive(Clone, Debug)]
 pub struct Displace<Source, XDisplace, YDisplace, ZDisplace, UDisplace> {
     /// Source function that outputs a value
@@ -22,6 +35,11 @@ pub struct Displace<Source, XDisplace, YDisplace, ZDisplace, UDisplace> {
     /// Displacement function that displaces the _u_ coordinate of the input
     /// value. Only needed for 4d or higher noise.
     pub u_displace: UDisplace,
+
+    /// Whether each displacement function is sampled at the input value as
+    /// displaced along the previous axes, rather than at the original input
+    /// value. The default is false.
+    pub feedback: bool,
 }
 
 impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace>
@@ -40,8 +58,45 @@ impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace>
             y_displace,
             z_displace,
             u_displace,
+            feedback: false,
         }
     }
+
+    /// Sets whether each displacement function is sampled at the input value
+    /// as displaced along the previous axes, rather than at the original
+    /// input value.
+    pub fn set_feedback(self, feedback: bool) -> Self {
+        Self { feedback, ..self }
+    }
+
+    /// Returns the point at which to sample the next displacement function.
+    #[inline]
+    fn sample_point<const DIM: usize>(
+        &self,
+        point: [f64; DIM],
+        displaced: [f64; DIM],
+    ) -> [f64; DIM] {
+        if self.feedback {
+            displaced
+        } else {
+            point
+        }
+    }
+}
+
+impl<Source, Displacement> Displace<Source, Displacement, Displacement, Displacement, Displacement>
+where
+    Displacement: Clone + Seedable,
+{
+    /// Creates a displacement that uses a copy of `displace` for every axis,
+    /// each reseeded with a seed derived from the seed of `displace` and the
+    /// axis, so that the displacements along different axes are independent.
+    pub fn new_decorrelated(source: Source, displace: Displacement) -> Self {
+        let seed = displace.seed();
+        let axis = |axis| displace.clone().set_seed(displacement_seed(seed, axis));
+
+        Self::new(source, axis(0), axis(1), axis(2), axis(3))
+    }
 }
 
 impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace> NoiseFn<f64, 2>
@@ -56,12 +111,13 @@ where
         // Get the output values from the displacement functions and add them to
         // the corresponding coordinate in the input value. Since this is a 2d
         // function, we only need the x_displace and y_displace functions.
-        let x = point[0] + self.x_displace.get(point);
-        let y = point[1] + self.y_displace.get(point);
+        let mut displaced = point;
+        displaced[0] += self.x_displace.get(self.sample_point(point, displaced));
+        displaced[1] += self.y_displace.get(self.sample_point(point, displaced));
 
         // get the output value using the offset input value instead of the
         // original input value.
-        self.source.get([x, y])
+        self.source.get(displaced)
     }
 }
 
@@ -79,13 +135,14 @@ where
         // the corresponding coordinate in the input value. Since this is a 3d
         // function, we only need the x_displace, y_displace, and z_displace
         // functions. Also, panic if there is no z_displace function defined.
-        let x = point[0] + self.x_displace.get(point);
-        let y = point[1] + self.y_displace.get(point);
-        let z = point[2] + self.z_displace.get(point);
+        let mut displaced = point;
+        displaced[0] += self.x_displace.get(self.sample_point(point, displaced));
+        displaced[1] += self.y_displace.get(self.sample_point(point, displaced));
+        displaced[2] += self.z_displace.get(self.sample_point(point, displaced));
 
         // get the output value using the offset input value instead of the
         // original input value.
-        self.source.get([x, y, z])
+        self.source.get(displaced)
     }
 }
 
@@ -104,13 +161,87 @@ where
         // the corresponding coordinate in the input value. Since this is a 4d
         // function, we need all of the displace functions. Panic if there is no z-
         // or u-displace function defined.
-        let x = point[0] + self.x_displace.get(point);
-        let y = point[1] + self.y_displace.get(point);
-        let z = point[2] + self.z_displace.get(point);
-        let u = point[3] + self.u_displace.get(point);
+        let mut displaced = point;
+        displaced[0] += self.x_displace.get(self.sample_point(point, displaced));
+        displaced[1] += self.y_displace.get(self.sample_point(point, displaced));
+        displaced[2] += self.z_displace.get(self.sample_point(point, displaced));
+        displaced[3] += self.u_displace.get(self.sample_point(point, displaced));
 
         // get the output value using the offset input value instead of the
         // original input value.
-        self.source.get([x, y, z, u])
+        self.source.get(displaced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{noise_fns::from_fn, Constant, Cylinders, Perlin};
+
+    #[test]
+    fn test_displace_uniform() {
+        let (source, displace) = (Perlin::new(3), Perlin::new(8));
+        let xyzu = NoiseFn::<f64, 4>::displace_xyzu(source, displace, displace, displace, displace);
+        let uniform = NoiseFn::<f64, 4>::displace_uniform(source, displace, [1.0; 4]);
+        let identity = NoiseFn::<f64, 4>::displace_uniform(source, displace, [0.0; 4]);
+
+        for i in 0..32 {
+            let x = i as f64 * 0.37 - 2.1;
+            let point = [x, x * 0.5 + 0.3, 1.7 - x, x * 0.25];
+
+            assert_eq!(xyzu.get(point), uniform.get(point));
+            assert_eq!(source.get(point), identity.get(point));
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_displacement() {
+        let displace = Displace::new_decorrelated(Cylinders::new(), Perlin::new(8));
+        let axes = [
+            displace.x_displace,
+            displace.y_displace,
+            displace.z_displace,
+            displace.u_displace,
+        ];
+        for (i, a) in axes.iter().enumerate() {
+            assert!(axes[i + 1..].iter().all(|b| a.seed() != b.seed()));
+        }
+
+        // The offsets along the x and y axes differ, so the warp isn't
+        // diagonal.
+        let mut non_diagonal = 0;
+        for i in 0..32 {
+            let point = [i as f64 * 0.37 - 2.1, i as f64 * 0.23 + 0.4];
+            let (x, y) = (
+                displace.x_displace.get(point),
+                displace.y_displace.get(point),
+            );
+            if (x - y).abs() > 1e-3 {
+                non_diagonal += 1;
+            }
+        }
+        assert!(non_diagonal > 16);
+    }
+
+    #[test]
+    fn test_feedback_samples_displaced_point() {
+        // The y displacement is the x coordinate at which it's sampled, and
+        // the source outputs the displaced y coordinate.
+        let displace = Displace::new(
+            from_fn(|point: [f64; 2]| point[1]),
+            Constant::new(0.5),
+            from_fn(|point: [f64; 2]| point[0]),
+            Constant::new(0.0),
+            Constant::new(0.0),
+        );
+        let point = [1.0, 0.25];
+
+        // Without feedback, y is displaced by the original x.
+        assert!(!displace.feedback);
+        assert_eq!(1.25, displace.get(point));
+
+        // With feedback, y is displaced by the already displaced x.
+        let feedback = displace.set_feedback(true);
+        assert_eq!(1.75, feedback.get(point));
     }
 }
@@ -0,0 +1,78 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that lifts its source function into a higher dimension by
+/// ignoring the extra coordinates of the input value.
+///
+/// Only the first `SOURCE_DIM` coordinates of the input value are passed to
+/// the source function, so a 2-dimensional source can be used in a 3- or
+/// 4-dimensional pipeline, where it is constant along the _z_ and _u_ axes,
+/// and a 3-dimensional source can be used in a 4-dimensional pipeline.
+#[derive(Clone, Debug)]
+pub struct Lift<Source, const SOURCE_DIM: usize> {
+    /// Source function that outputs a value.
+    pub source: Source,
+}
+
+impl<Source, const SOURCE_DIM: usize> Lift<Source, SOURCE_DIM> {
+    pub fn new(source: Source) -> Self {
+        Self { source }
+    }
+}
+
+impl<T, Source> NoiseFn<T, 3> for Lift<Source, 2>
+where
+    Source: NoiseFn<T, 2>,
+{
+    fn get(&self, point: impl Into<[T; 3]>) -> f64 {
+        let [x, y, _] = point.into();
+
+        self.source.get([x, y])
+    }
+}
+
+impl<T, Source> NoiseFn<T, 4> for Lift<Source, 2>
+where
+    Source: NoiseFn<T, 2>,
+{
+    fn get(&self, point: impl Into<[T; 4]>) -> f64 {
+        let [x, y, _, _] = point.into();
+
+        self.source.get([x, y])
+    }
+}
+
+impl<T, Source> NoiseFn<T, 4> for Lift<Source, 3>
+where
+    Source: NoiseFn<T, 3>,
+{
+    fn get(&self, point: impl Into<[T; 4]>) -> f64 {
+        let [x, y, z, _] = point.into();
+
+        self.source.get([x, y, z])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn test_lift_ignores_extra_coordinates() {
+        let perlin = Perlin::new(5);
+        let lifted = NoiseFn::<f64, 2>::lift(perlin);
+
+        for i in 0..32 {
+            let (x, y) = (i as f64 * 0.37 - 2.1, i as f64 * 0.23 + 0.4);
+            let value = perlin.get([x, y]);
+
+            assert_eq!(value, lifted.get([x, y, i as f64 * 0.71]));
+            assert_eq!(value, lifted.get([x, y, -1.5, i as f64]));
+
+            let z = i as f64 * 0.19 - 0.8;
+            let value = perlin.get([x, y, z]);
+            let lifted = NoiseFn::<f64, 3>::lift(perlin);
+            assert_eq!(value, lifted.get([x, y, z, i as f64 * 0.71]));
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::noise_fns::NoiseFn;
+use core::f64::consts::TAU;
+
+/// Noise function that animates a 4-dimensional source function along a
+/// seamlessly looping time axis.
+///
+/// The input value is `(x, y, t)`. Rather than moving along a line, the time
+/// coordinate traverses a circle in the _z_ and _u_ axes of the source
+/// function, so the source is sampled at
+/// `(x, y, r·cos(2πt / period), r·sin(2πt / period))`. The time is wrapped
+/// into `[0, period)` before the angle is computed, so `t` and `t + period`
+/// produce identical output.
+///
+/// The default radius is `period / 2π`, so the circle has a circumference of
+/// `period` and features move at the same speed as they would along a
+/// straight time axis.
+#[derive(Clone, Debug)]
+pub struct LoopingTime<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Length of one loop along the time axis.
+    pub period: f64,
+
+    /// Radius of the circle that the time coordinate traverses.
+    pub radius: f64,
+}
+
+impl<Source> LoopingTime<Source> {
+    pub fn new(source: Source, period: f64) -> Self {
+        Self {
+            source,
+            period,
+            radius: period / TAU,
+        }
+    }
+
+    /// Sets the length of one loop along the time axis. The radius is reset
+    /// to `period / 2π`.
+    pub fn set_period(self, period: f64) -> Self {
+        Self {
+            period,
+            radius: period / TAU,
+            ..self
+        }
+    }
+
+    /// Sets the radius of the circle that the time coordinate traverses.
+    /// Larger radii pass through more of the source function per loop, so the
+    /// animation changes faster.
+    pub fn set_radius(self, radius: f64) -> Self {
+        Self { radius, ..self }
+    }
+}
+
+impl<Source> NoiseFn<f64, 3> for LoopingTime<Source>
+where
+    Source: NoiseFn<f64, 4>,
+{
+    fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
+        let [x, y, t] = point.into();
+        let angle = (t / self.period).rem_euclid(1.0) * TAU;
+
+        self.source
+            .get([x, y, self.radius * angle.cos(), self.radius * angle.sin()])
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        self.source.value_range()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn test_loop_is_seamless() {
+        let looping = LoopingTime::new(Perlin::new(9), 1.0);
+
+        let mut changed = false;
+        for i in 0..32 {
+            let (x, y) = (i as f64 * 0.37 - 2.1, i as f64 * 0.23 + 0.4);
+            let start = looping.get([x, y, 0.0]);
+
+            assert_eq!(start, looping.get([x, y, 1.0]));
+            assert_eq!(start, looping.get([x, y, -3.0]));
+            changed |= start != looping.get([x, y, 0.5]);
+        }
+        assert!(changed);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that applies a closure to the input value before returning
+/// the output value from the source function.
+///
+/// This covers custom coordinate transforms that none of the other
+/// transformers provide, without defining a new noise function.
+#[derive(Clone, Copy)]
+pub struct MapPoint<Source, F> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Maps the input value before it is passed to the source function.
+    pub function: F,
+}
+
+impl<Source, F> MapPoint<Source, F> {
+    pub fn new(source: Source, function: F) -> Self {
+        Self { source, function }
+    }
+}
+
+impl<T, Source, F, const DIM: usize> NoiseFn<T, DIM> for MapPoint<Source, F>
+where
+    Source: NoiseFn<T, DIM>,
+    F: Fn([T; DIM]) -> [T; DIM],
+{
+    #[inline]
+    fn get(&self, point: impl Into<[T; DIM]>) -> f64 {
+        self.source.get((self.function)(point.into()))
+    }
+}
+
+impl<Source, F> core::fmt::Debug for MapPoint<Source, F>
+where
+    Source: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MapPoint")
+            .field("source", &self.source)
+            .field("function", &core::format_args!("..."))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_fn, Perlin};
+
+    #[test]
+    fn test_swapped_axes_match_hand_built() {
+        let perlin = Perlin::new(6);
+        let swapped = NoiseFn::<f64, 2>::map_point(perlin, |[x, y]: [f64; 2]| [y, x]);
+        let hand_built = from_fn(|[x, y]: [f64; 2]| perlin.get([y, x]));
+
+        for i in 0..32 {
+            let point = [i as f64 * 0.29 - 1.5, 0.7 - i as f64 * 0.17];
+
+            assert_eq!(hand_built.get(point), swapped.get(point));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that wraps the input value into a repeating domain before
+/// returning the output value from the source function.
+///
+/// Each coordinate of the input value is wrapped modulo the period of its
+/// axis into the range `[0, period)`, so the output repeats with that period
+/// along every axis. A period of 0.0 leaves its axis unwrapped.
+///
+/// Wrapping the input value doesn't make the source function itself periodic:
+/// unless it already repeats with the same period, its output jumps at every
+/// multiple of the period, leaving a visible seam. For seamless tiling, use a
+/// periodic generator, such as [`Perlin`](crate::Perlin) with
+/// `set_period`, and a matching period here.
+#[derive(Clone, Copy, Debug)]
+pub struct Repeat<Source, const DIM: usize> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Period of each axis of the input value.
+    pub period: [f64; DIM],
+}
+
+impl<Source, const DIM: usize> Repeat<Source, DIM> {
+    pub fn new(source: Source, period: [f64; DIM]) -> Self {
+        Self { source, period }
+    }
+
+    pub fn set_period(self, period: [f64; DIM]) -> Self {
+        Self { period, ..self }
+    }
+}
+
+impl<Source, const DIM: usize> NoiseFn<f64, DIM> for Repeat<Source, DIM>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: impl Into<[f64; DIM]>) -> f64 {
+        let mut point = point.into();
+        for (coordinate, &period) in point.iter_mut().zip(self.period.iter()) {
+            if period != 0.0 {
+                *coordinate = coordinate.rem_euclid(period);
+            }
+        }
+
+        self.source.get(point)
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        self.source.value_range()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn test_output_repeats() {
+        let perlin = Perlin::new(4);
+        let repeated = NoiseFn::<f64, 3>::repeat(perlin, [4.0, 2.5, 0.0]);
+
+        for i in 0..32 {
+            let point = [i as f64 * 0.375 - 6.0, i as f64 * 0.125 - 1.0, 0.625];
+            let value = repeated.get(point);
+
+            assert_eq!(value, repeated.get([point[0] + 4.0, point[1], point[2]]));
+            assert_eq!(value, repeated.get([point[0], point[1] + 2.5, point[2]]));
+            assert_eq!(
+                value,
+                repeated.get([point[0] - 8.0, point[1] + 5.0, point[2]])
+            );
+        }
+
+        // Inside the first period, and along unwrapped axes, the source is
+        // sampled as is.
+        let point = [1.5, 0.75, -3.25];
+        assert_eq!(perlin.get(point), repeated.get(point));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::{math::coordinate::Coordinate, noise_fns::NoiseFn};
 
 /// Noise function that rotates the input value around the origin before
 /// returning the output value from the source function.
@@ -28,6 +28,45 @@ pub struct RotatePoint<Source> {
     /// _u_ rotation angle applied to the input value, in degrees. The
     /// default angle is set to 0.0 degrees.
     pub u_angle: f64,
+
+    plane_angles: [f64; 6],
+}
+
+/// One of the six coordinate planes of four-dimensional space, for rotating
+/// 4D input values with [`RotatePoint::set_plane_rotation`]. The _w_ axis is
+/// the fourth axis, which the per-axis angles call _u_.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RotationPlane {
+    XY,
+    XZ,
+    XW,
+    YZ,
+    YW,
+    ZW,
+}
+
+impl RotationPlane {
+    /// Every plane, in the order the rotations are applied.
+    pub const ALL: [RotationPlane; 6] = [
+        RotationPlane::XY,
+        RotationPlane::XZ,
+        RotationPlane::XW,
+        RotationPlane::YZ,
+        RotationPlane::YW,
+        RotationPlane::ZW,
+    ];
+
+    /// Returns the indices of the two axes spanning the plane.
+    fn axes(self) -> (usize, usize) {
+        match self {
+            RotationPlane::XY => (0, 1),
+            RotationPlane::XZ => (0, 2),
+            RotationPlane::XW => (0, 3),
+            RotationPlane::YZ => (1, 2),
+            RotationPlane::YW => (1, 3),
+            RotationPlane::ZW => (2, 3),
+        }
+    }
 }
 
 impl<Source> RotatePoint<Source> {
@@ -38,6 +77,7 @@ impl<Source> RotatePoint<Source> {
             y_angle: 0.0,
             z_angle: 0.0,
             u_angle: 0.0,
+            plane_angles: [0.0; 6],
         }
     }
 
@@ -76,22 +116,46 @@ impl<Source> RotatePoint<Source> {
             ..self
         }
     }
+
+    /// Sets the rotation angle within one of the coordinate planes to apply
+    /// to 4D input values, in radians. The rotation turns the first axis of
+    /// the plane towards the second.
+    ///
+    /// The per-axis angles can't express every rotation in four dimensions,
+    /// so 4D input values are rotated by the plane angles instead, one plane
+    /// at a time in the order of [`RotationPlane::ALL`]. The plane angles
+    /// don't affect 2D or 3D input values.
+    pub fn set_plane_rotation(self, plane: RotationPlane, angle: f64) -> Self {
+        let mut plane_angles = self.plane_angles;
+        plane_angles[plane as usize] = angle;
+        Self {
+            plane_angles,
+            ..self
+        }
+    }
+
+    /// Returns the rotation angle within a coordinate plane, in radians.
+    pub fn plane_rotation(&self, plane: RotationPlane) -> f64 {
+        self.plane_angles[plane as usize]
+    }
 }
 
-impl<Source> NoiseFn<f64, 2> for RotatePoint<Source>
+impl<T, Source> NoiseFn<T, 2> for RotatePoint<Source>
 where
-    Source: NoiseFn<f64, 2>,
+    T: Coordinate,
+    Source: NoiseFn<T, 2>,
 {
-    fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
+    fn get(&self, point: impl Into<[T; 2]>) -> f64 {
         let point = point.into();
         // In two dimensions, the plane is _xy_, and we rotate around the
         // z-axis.
         let x = point[0];
         let y = point[1];
         let theta = self.z_angle.to_radians();
+        let (sin, cos) = (T::from_f64(theta.sin()), T::from_f64(theta.cos()));
 
-        let x2 = x * theta.cos() - y * theta.sin();
-        let y2 = x * theta.sin() + y * theta.cos();
+        let x2 = x * cos - y * sin;
+        let y2 = x * sin + y * cos;
 
         // get the output value using the offset input value instead of the
         // original input value.
@@ -99,11 +163,12 @@ where
     }
 }
 
-impl<Source> NoiseFn<f64, 3> for RotatePoint<Source>
+impl<T, Source> NoiseFn<T, 3> for RotatePoint<Source>
 where
-    Source: NoiseFn<f64, 3>,
+    T: Coordinate,
+    Source: NoiseFn<T, 3>,
 {
-    fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
+    fn get(&self, point: impl Into<[T; 3]>) -> f64 {
         let point = point.into();
         // In three dimensions, we could rotate around any of the x, y, or z
         // axes. Need a more complicated function to handle this case.
@@ -124,6 +189,8 @@ where
         let y3 = x_sin;
         let z3 = y_cos * x_cos;
 
+        let [x1, y1, z1, x2, y2, z2, x3, y3, z3] =
+            [x1, y1, z1, x2, y2, z2, x3, y3, z3].map(T::from_f64);
         let x = (x1 * point[0]) + (y1 * point[1]) + (z1 * point[2]);
         let y = (x2 * point[0]) + (y2 * point[1]) + (z2 * point[2]);
         let z = (x3 * point[0]) + (y3 * point[1]) + (z3 * point[2]);
@@ -134,12 +201,84 @@ where
     }
 }
 
-impl<Source> NoiseFn<f64, 4> for RotatePoint<Source>
+impl<T, Source> NoiseFn<T, 4> for RotatePoint<Source>
 where
-    Source: NoiseFn<f64, 4>,
+    T: Coordinate,
+    Source: NoiseFn<T, 4>,
 {
-    fn get(&self, _point: impl Into<[f64; 4]>) -> f64 {
-        // 4d rotations are hard.
-        unimplemented!();
+    fn get(&self, point: impl Into<[T; 4]>) -> f64 {
+        let mut point = point.into();
+        for &plane in RotationPlane::ALL.iter() {
+            let angle = self.plane_angles[plane as usize];
+            if angle == 0.0 {
+                continue;
+            }
+
+            let (i, j) = plane.axes();
+            let (sin, cos) = (T::from_f64(angle.sin()), T::from_f64(angle.cos()));
+            let (a, b) = (point[i], point[j]);
+            point[i] = a * cos - b * sin;
+            point[j] = a * sin + b * cos;
+        }
+
+        // get the output value using the offset input value instead of the
+        // original input value.
+        self.source.get(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{noise_fns::from_fn, Perlin};
+
+    #[test]
+    fn test_xw_plane_rotation() {
+        let angle = 0.3;
+        let rotated = |axis: usize| {
+            RotatePoint::new(from_fn(move |point: [f64; 4]| point[axis]))
+                .set_plane_rotation(RotationPlane::XW, angle)
+        };
+        let point = [1.0, 2.0, 3.0, 4.0];
+
+        let expected = [
+            1.0 * angle.cos() - 4.0 * angle.sin(),
+            2.0,
+            3.0,
+            1.0 * angle.sin() + 4.0 * angle.cos(),
+        ];
+        for (axis, &expected) in expected.iter().enumerate() {
+            let actual = rotated(axis).get(point);
+            assert!(
+                (actual - expected).abs() < 1e-12,
+                "{} != {}",
+                actual,
+                expected
+            );
+        }
+
+        // Without plane rotations, 4D input values pass through unchanged.
+        let identity = RotatePoint::new(from_fn(|point: [f64; 4]| point[3]));
+        assert_eq!(0.0, identity.plane_rotation(RotationPlane::XW));
+        assert_eq!(4.0, identity.get(point));
+    }
+
+    #[test]
+    fn test_coordinate_types() {
+        let source = Perlin::new(2);
+        let rotated = RotatePoint::new(source).set_z_angle(35.0);
+        let (sin, cos) = (35.0_f64.to_radians().sin(), 35.0_f64.to_radians().cos());
+        for i in 0..32 {
+            let (x, y, z) = (i as f64 * 0.37 - 2.1, i as f64 * 0.53 + 0.6, 0.7);
+
+            let expected = source.get([x * cos - y * sin, x * sin + y * cos]);
+            assert_eq!(expected, rotated.get([x, y]));
+
+            let expected = source.get([x * cos + y * sin, y * cos - x * sin, z]);
+            assert_eq!(expected, rotated.get([x, y, z]));
+        }
+
+        let x = RotatePoint::new(from_fn(|point: [f32; 2]| point[0] as f64)).set_z_angle(90.0);
+        assert!((-2.0 - x.get([1.0_f32, 2.0])).abs() < 1e-6);
     }
 }
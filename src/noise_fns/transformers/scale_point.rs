@@ -1,10 +1,12 @@
-use crate::noise_fns::NoiseFn;
+use crate::{math::coordinate::Coordinate, noise_fns::NoiseFn};
 
 /// Noise function that scales the coordinates of the input value before
 /// returning the output value from the source function.
 ///
 /// The get() method multiplies the coordinates of the input value with a
 /// scaling factor before returning the output value from the source function.
+/// The coordinates are scaled about the center set by `set_center`, which is
+/// the origin by default.
 #[derive(Clone, Debug)]
 pub struct ScalePoint<Source> {
     /// Source function that outputs a value
@@ -25,6 +27,10 @@ pub struct ScalePoint<Source> {
     /// Scaling factor applied to the _u_ coordinate of the input value. The
     /// default scaling factor is set to 1.0.
     pub u_scale: f64,
+
+    /// Point that the input value is scaled about, which is passed to the
+    /// source function unchanged. The default center is the origin.
+    pub center: [f64; 4],
 }
 
 impl<Source> ScalePoint<Source> {
@@ -35,6 +41,7 @@ impl<Source> ScalePoint<Source> {
             y_scale: 1.0,
             z_scale: 1.0,
             u_scale: 1.0,
+            center: [0.0; 4],
         }
     }
 
@@ -73,6 +80,28 @@ impl<Source> ScalePoint<Source> {
         }
     }
 
+    /// Sets the point to scale the input value about, so that it stays fixed
+    /// as the scaling factors change. Each coordinate becomes
+    /// `(coordinate - center) * scale + center`. Axes without a center
+    /// coordinate are scaled about 0.0.
+    pub fn set_center<const DIM: usize>(self, center: [f64; DIM]) -> Self {
+        assert!(DIM <= 4, "at most four center coordinates are supported");
+
+        let mut axes = [0.0; 4];
+        axes[..DIM].copy_from_slice(&center);
+        Self {
+            center: axes,
+            ..self
+        }
+    }
+
+    /// Scales one coordinate of the input value about the center.
+    #[inline]
+    fn scale<T: Coordinate>(&self, coordinate: T, axis: usize, scale: f64) -> T {
+        let center = T::from_f64(self.center[axis]);
+        (coordinate - center) * T::from_f64(scale) + center
+    }
+
     /// Sets the individual scaling factors to apply to each coordinate of the
     /// input value.
     pub fn set_all_scales(self, x_scale: f64, y_scale: f64, z_scale: f64, u_scale: f64) -> Self {
@@ -86,49 +115,57 @@ impl<Source> ScalePoint<Source> {
     }
 }
 
-impl<Source> NoiseFn<f64, 2> for ScalePoint<Source>
+impl<T, Source> NoiseFn<T, 2> for ScalePoint<Source>
 where
-    Source: NoiseFn<f64, 2>,
+    T: Coordinate,
+    Source: NoiseFn<T, 2>,
 {
-    fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
+    fn get(&self, point: impl Into<[T; 2]>) -> f64 {
         let point = point.into();
-        self.source
-            .get([point[0] * self.x_scale, point[1] * self.y_scale])
+        self.source.get([
+            self.scale(point[0], 0, self.x_scale),
+            self.scale(point[1], 1, self.y_scale),
+        ])
     }
 }
 
-impl<Source> NoiseFn<f64, 3> for ScalePoint<Source>
+impl<T, Source> NoiseFn<T, 3> for ScalePoint<Source>
 where
-    Source: NoiseFn<f64, 3>,
+    T: Coordinate,
+    Source: NoiseFn<T, 3>,
 {
-    fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
+    fn get(&self, point: impl Into<[T; 3]>) -> f64 {
         let point = point.into();
         self.source.get([
-            point[0] * self.x_scale,
-            point[1] * self.y_scale,
-            point[2] * self.z_scale,
+            self.scale(point[0], 0, self.x_scale),
+            self.scale(point[1], 1, self.y_scale),
+            self.scale(point[2], 2, self.z_scale),
         ])
     }
 }
 
-impl<Source> NoiseFn<f64, 4> for ScalePoint<Source>
+impl<T, Source> NoiseFn<T, 4> for ScalePoint<Source>
 where
-    Source: NoiseFn<f64, 4>,
+    T: Coordinate,
+    Source: NoiseFn<T, 4>,
 {
-    fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
+    fn get(&self, point: impl Into<[T; 4]>) -> f64 {
         let point = point.into();
         self.source.get([
-            point[0] * self.x_scale,
-            point[1] * self.y_scale,
-            point[2] * self.z_scale,
-            point[3] * self.u_scale,
+            self.scale(point[0], 0, self.x_scale),
+            self.scale(point[1], 1, self.y_scale),
+            self.scale(point[2], 2, self.z_scale),
+            self.scale(point[3], 3, self.u_scale),
         ])
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{super::super::Perlin, *};
+    use super::{
+        super::super::{from_fn, Perlin},
+        *,
+    };
 
     #[test]
     fn test_pass_by_ref() {
@@ -165,4 +202,40 @@ mod tests {
 
         assert!(zero_count < 10 * 10 * 10 * 10);
     }
+
+    #[test]
+    fn test_coordinate_types() {
+        let source = Perlin::new(2);
+        let scaled = ScalePoint::new(source).set_all_scales(0.8, 0.1, 0.4, 0.2);
+        for i in 0..32 {
+            let point = [
+                i as f64 * 0.37 - 2.1,
+                i as f64 * 0.53 + 0.6,
+                1.3 - i as f64 * 0.11,
+            ];
+            let expected = source.get([point[0] * 0.8, point[1] * 0.1, point[2] * 0.4]);
+            assert_eq!(expected, scaled.get(point));
+        }
+
+        let sum = ScalePoint::new(from_fn(|point: [f32; 2]| (point[0] + point[1]) as f64))
+            .set_x_scale(2.0)
+            .set_y_scale(0.5);
+        assert_eq!(3.125, sum.get([1.5_f32, 0.25]));
+    }
+
+    #[test]
+    fn test_center_is_fixed() {
+        let source = Perlin::new(5);
+        let center = [1.25, -0.75, 2.5];
+
+        for &scale in [0.25, 0.5, 2.0, 7.0].iter() {
+            let scaled = ScalePoint::new(source).set_scale(scale).set_center(center);
+            assert_eq!(source.get(center), scaled.get(center));
+
+            // Other points move towards or away from the center.
+            let point = [center[0] + 0.5, center[1], center[2] - 0.25];
+            let expected = [center[0] + 0.5 * scale, center[1], center[2] - 0.25 * scale];
+            assert_eq!(source.get(expected), scaled.get(point));
+        }
+    }
 }
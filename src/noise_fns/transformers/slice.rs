@@ -0,0 +1,69 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that fixes the last coordinate of its source function, so
+/// that a slice of the source can be sampled with one less dimension.
+///
+/// The fixed coordinate is appended to the input value before it is passed
+/// to the source function, so a 3-dimensional source is sampled as a
+/// 2-dimensional function at a fixed _z_ coordinate, and a 4-dimensional one
+/// as a 3-dimensional function at a fixed _u_ coordinate.
+#[derive(Clone, Debug)]
+pub struct Slice<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Value of the coordinate that is appended to the input value.
+    pub coordinate: f64,
+}
+
+impl<Source> Slice<Source> {
+    pub fn new(source: Source, coordinate: f64) -> Self {
+        Self { source, coordinate }
+    }
+
+    pub fn set_coordinate(self, coordinate: f64) -> Self {
+        Self { coordinate, ..self }
+    }
+}
+
+impl<Source> NoiseFn<f64, 2> for Slice<Source>
+where
+    Source: NoiseFn<f64, 3>,
+{
+    fn get(&self, point: impl Into<[f64; 2]>) -> f64 {
+        let [x, y] = point.into();
+
+        self.source.get([x, y, self.coordinate])
+    }
+}
+
+impl<Source> NoiseFn<f64, 3> for Slice<Source>
+where
+    Source: NoiseFn<f64, 4>,
+{
+    fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
+        let [x, y, z] = point.into();
+
+        self.source.get([x, y, z, self.coordinate])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Perlin, Simplex};
+
+    #[test]
+    fn test_slice_matches_source() {
+        let (perlin, simplex) = (Perlin::new(5), Simplex::new(5));
+        let slice_2d = NoiseFn::<f64, 3>::slice_z(perlin, 0.0);
+        let slice_3d = Slice::new(simplex, 1.25);
+
+        for i in 0..32 {
+            let (x, y) = (i as f64 * 0.37 - 2.1, i as f64 * 0.23 + 0.4);
+
+            assert_eq!(perlin.get([x, y, 0.0]), slice_2d.get([x, y]));
+            assert_eq!(simplex.get([x, y, 0.5, 1.25]), slice_3d.get([x, y, 0.5]));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::noise_fns::{NoiseFn, TransformerArgs};
+
+/// Noise function that moves the coordinates of the input value by the output
+/// value of a noise function before returning the output value from the
+/// source function.
+///
+/// Each coordinate is moved by the output value of `offset` at the input
+/// value, multiplied by the weight of its axis. This is a cheaper, coarser
+/// alternative to [`Displace`](crate::Displace): a single function is sampled
+/// once for all axes, so the input value moves along a fixed direction.
+#[derive(Clone, Debug)]
+pub struct TranslateByNoise<Source, Offset> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Outputs the translation amount, before weighting.
+    pub offset: Offset,
+
+    /// Weight of the translation along the _x_, _y_, _z_ and _u_ axes. The
+    /// default weights are 1.0.
+    pub weights: [f64; 4],
+}
+
+impl<Source, Offset> TranslateByNoise<Source, Offset> {
+    pub fn new(source: Source, offset: Offset) -> Self {
+        Self {
+            source,
+            offset,
+            weights: [1.0; 4],
+        }
+    }
+
+    /// Sets the weight of the translation along each axis. Axes without a
+    /// weight are not translated.
+    pub fn set_weights(self, weights: impl TransformerArgs) -> Self {
+        Self {
+            weights: weights.expand(0.0),
+            ..self
+        }
+    }
+}
+
+impl<Source, Offset, const DIM: usize> NoiseFn<f64, DIM> for TranslateByNoise<Source, Offset>
+where
+    Source: NoiseFn<f64, DIM>,
+    Offset: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: impl Into<[f64; DIM]>) -> f64 {
+        let mut point = point.into();
+        let offset = self.offset.get(point);
+
+        for (coordinate, weight) in point.iter_mut().zip(&self.weights) {
+            *coordinate += offset * weight;
+        }
+
+        self.source.get(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin};
+
+    #[test]
+    fn test_weights() {
+        let perlin = Perlin::new(2);
+        let identity = NoiseFn::<f64, 2>::translate_by_noise(perlin, Perlin::new(5), 0.0);
+        let shifted = NoiseFn::<f64, 2>::translate_by_noise(perlin, Constant::new(0.5), [0.0, 2.0]);
+
+        for i in 0..32 {
+            let point = [i as f64 * 0.29 - 1.3, i as f64 * 0.41 + 0.2];
+
+            assert_eq!(perlin.get(point), identity.get(point));
+            assert_eq!(perlin.get([point[0], point[1] + 1.0]), shifted.get(point));
+        }
+    }
+}
@@ -5,6 +5,9 @@ use crate::noise_fns::NoiseFn;
 ///
 /// The get() method moves the coordinates of the input value by a translation
 /// amount before returning the output value from the source function.
+///
+/// Translating along a time axis never repeats; for a seamlessly looping
+/// animation, use [`LoopingTime`](crate::LoopingTime) instead.
 #[derive(Clone, Debug)]
 pub struct TranslatePoint<Source> {
     /// Source function that outputs a value
@@ -143,3 +146,69 @@ where
         ])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OpenSimplex, Perlin, PerlinSurflet, ScalePoint, Simplex, SuperSimplex, Value};
+
+    /// Samples densely across `x = 0`, through translated and scaled points,
+    /// and asserts that the output never jumps between neighboring samples.
+    fn assert_continuous_across_origin<Source>(source: Source)
+    where
+        Source: NoiseFn<f64, 2> + NoiseFn<f64, 3>,
+    {
+        let translated = TranslatePoint::new(&source).set_x_translation(-0.25);
+        let scaled = ScalePoint::new(&source).set_x_scale(-3.0);
+        let step = 1e-5;
+        // Generous bound on the slope of the noise functions, which is far
+        // below the jump a seam would cause over a single step.
+        let max_change = 50.0 * step;
+
+        for row in 0..10 {
+            let y = row as f64 * 0.137 - 0.6;
+            let sample = |x: f64| {
+                [
+                    NoiseFn::<f64, 2>::get(&translated, [x + 0.25, y]),
+                    NoiseFn::<f64, 3>::get(&translated, [x + 0.25, y, 0.3]),
+                    NoiseFn::<f64, 2>::get(&scaled, [x / -3.0, y]),
+                    NoiseFn::<f64, 3>::get(&source, [x, y, -0.3]),
+                ]
+            };
+
+            let mut previous = sample(-0.001);
+            for i in 1..=200 {
+                let current = sample(-0.001 + i as f64 * step);
+                for (a, b) in previous.iter().zip(current.iter()) {
+                    assert!((a - b).abs() < max_change, "jump from {} to {}", a, b);
+                }
+                previous = current;
+            }
+        }
+    }
+
+    #[test]
+    fn test_lattice_generators_continuous_across_origin() {
+        assert_continuous_across_origin(Perlin::new(1));
+        assert_continuous_across_origin(PerlinSurflet::new(1));
+        assert_continuous_across_origin(Value::new(1));
+        assert_continuous_across_origin(Simplex::new(1));
+        assert_continuous_across_origin(OpenSimplex::new(1));
+        assert_continuous_across_origin(SuperSimplex::new(1));
+    }
+
+    #[test]
+    fn test_value_continuous_at_negative_cell_boundaries() {
+        // Value noise used to apply the interpolation curve to the cell
+        // coordinates rather than the position within the cell, which broke
+        // (and panicked) away from the unit square.
+        let value = Value::new(1);
+        let step = 1e-5;
+
+        for &boundary in [-3.0, -2.0, -1.0, 0.0, 2.0].iter() {
+            let before: f64 = value.get([boundary - step, -1.5]);
+            let after: f64 = value.get([boundary + step, -1.5]);
+            assert!((before - after).abs() < 1e-6);
+        }
+    }
+}
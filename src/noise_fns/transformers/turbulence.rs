@@ -1,4 +1,7 @@
-use crate::noise_fns::{Fbm, MultiFractal, NoiseFn, Seedable};
+use crate::{
+    math::expand_axes,
+    noise_fns::{Fbm, MultiFractal, NoiseFn, Seedable},
+};
 
 /// Noise function that randomly displaces the input value before returning the
 /// output value from the source function.
@@ -8,6 +11,15 @@ use crate::noise_fns::{Fbm, MultiFractal, NoiseFn, Seedable};
 /// retrieving the output value from the source function. To control the
 /// turbulence, an application can modify its frequency, its power, and its
 /// roughness.
+///
+/// 4D turbulence is not an extension of 3D turbulence. The displacement
+/// functions are sampled at offset _u_ coordinates and the _u_ coordinate is
+/// displaced too, so a point with a _u_ coordinate of 0.0 is generally
+/// displaced differently than the same point in 3D. The two only agree when
+/// the source and the displacement generator `F` ignore _u_, outputting the
+/// same values in 4D as in 3D; the noise generators in this crate don't. The
+/// displacement is continuous along _u_ whenever `F` is, so adding a time axis
+/// doesn't cause jumps.
 #[derive(Clone, Debug)]
 pub struct Turbulence<Source, F>
 where
@@ -26,7 +38,11 @@ where
     /// Affects the roughness of the turbulence. Higher values are rougher.
     pub roughness: usize,
 
+    /// Bounds the output value is clamped to, if any. Default is `None`.
+    pub clamp: Option<(f64, f64)>,
+
     seed: u32,
+    power_axes: [f64; 4],
     x_distort_function: Fbm<F>,
     y_distort_function: Fbm<F>,
     z_distort_function: Fbm<F>,
@@ -43,31 +59,34 @@ where
     pub const DEFAULT_ROUGHNESS: usize = 3;
 
     pub fn new(source: Source) -> Self {
+        let distort_function = |axis| {
+            Fbm::default()
+                .set_seed(displacement_seed(Self::DEFAULT_SEED, axis))
+                .set_octaves(Self::DEFAULT_ROUGHNESS)
+                .set_frequency(Self::DEFAULT_FREQUENCY)
+        };
+
         Self {
             source,
             seed: Self::DEFAULT_SEED,
             frequency: Self::DEFAULT_FREQUENCY,
             power: Self::DEFAULT_POWER,
             roughness: Self::DEFAULT_ROUGHNESS,
-            x_distort_function: Fbm::default()
-                .set_seed(Self::DEFAULT_SEED)
-                .set_octaves(Self::DEFAULT_ROUGHNESS)
-                .set_frequency(Self::DEFAULT_FREQUENCY),
-            y_distort_function: Fbm::default()
-                .set_seed(Self::DEFAULT_SEED + 1)
-                .set_octaves(Self::DEFAULT_ROUGHNESS)
-                .set_frequency(Self::DEFAULT_FREQUENCY),
-            z_distort_function: Fbm::default()
-                .set_seed(Self::DEFAULT_SEED + 2)
-                .set_octaves(Self::DEFAULT_ROUGHNESS)
-                .set_frequency(Self::DEFAULT_FREQUENCY),
-            u_distort_function: Fbm::default()
-                .set_seed(Self::DEFAULT_SEED + 3)
-                .set_octaves(Self::DEFAULT_ROUGHNESS)
-                .set_frequency(Self::DEFAULT_FREQUENCY),
+            clamp: None,
+            power_axes: [1.0; 4],
+            x_distort_function: distort_function(0),
+            y_distort_function: distort_function(1),
+            z_distort_function: distort_function(2),
+            u_distort_function: distort_function(3),
         }
     }
 
+    /// Returns the seed of the displacement along the _x_ axis, which is
+    /// derived from the seed of the turbulence.
+    pub fn displacement_seed(&self) -> u32 {
+        self.x_distort_function.seed()
+    }
+
     pub fn set_frequency(self, frequency: f64) -> Self {
         Self {
             frequency,
@@ -83,6 +102,51 @@ where
         Self { power, ..self }
     }
 
+    /// Sets a multiplier of the power for each axis, in order, so that points
+    /// are displaced more along some axes than others. Axes without a
+    /// multiplier use 1.0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than four multipliers are given.
+    pub fn set_power_axes<const DIM: usize>(self, power_axes: [f64; DIM]) -> Self {
+        Self {
+            power_axes: expand_axes(power_axes, 1.0),
+            ..self
+        }
+    }
+
+    /// Sets the lower and upper bounds to clamp the output value to, or
+    /// `None` to output the value from the source function unchanged.
+    ///
+    /// Strong turbulence samples the source function far from the input
+    /// value, so a source function that is unbounded, or only bounded in
+    /// practice near the origin, can output spikes; clamping bounds them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lower bound is greater than the upper bound, or if
+    /// either is NaN.
+    pub fn set_clamp(self, clamp: Option<(f64, f64)>) -> Self {
+        if let Some((lower, upper)) = clamp {
+            assert!(
+                lower <= upper,
+                "the lower bound must not exceed the upper bound"
+            );
+        }
+
+        Self { clamp, ..self }
+    }
+
+    /// Clamps the output value to the bounds set by `set_clamp`, if any.
+    #[inline]
+    fn clamp_output(&self, value: f64) -> f64 {
+        match self.clamp {
+            Some((lower, upper)) => value.clamp(lower, upper),
+            None => value,
+        }
+    }
+
     pub fn set_roughness(self, roughness: usize) -> Self {
         Self {
             roughness,
@@ -95,6 +159,22 @@ where
     }
 }
 
+/// Derives the seed of the displacement along one axis from the seed of the
+/// turbulence.
+///
+/// The seeds are scrambled so that the displacement is decorrelated from a
+/// source seeded with the same value, and so that the octaves of the
+/// displacement functions for different axes don't share seeds.
+pub(super) fn displacement_seed(seed: u32, axis: u32) -> u32 {
+    // Finalizer of MurmurHash3, applied to the seed offset by the axis.
+    let mut hash = seed.wrapping_add((axis + 1).wrapping_mul(0x9e37_79b9));
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^ (hash >> 16)
+}
+
 impl<Source, F> Seedable for Turbulence<Source, F>
 where
     F: Default + Seedable,
@@ -102,10 +182,10 @@ where
     fn set_seed(self, seed: u32) -> Self {
         Self {
             seed,
-            x_distort_function: self.x_distort_function.set_seed(seed),
-            y_distort_function: self.y_distort_function.set_seed(seed + 1),
-            z_distort_function: self.z_distort_function.set_seed(seed + 2),
-            u_distort_function: self.u_distort_function.set_seed(seed + 3),
+            x_distort_function: self.x_distort_function.set_seed(displacement_seed(seed, 0)),
+            y_distort_function: self.y_distort_function.set_seed(displacement_seed(seed, 1)),
+            z_distort_function: self.z_distort_function.set_seed(displacement_seed(seed, 2)),
+            u_distort_function: self.u_distort_function.set_seed(displacement_seed(seed, 3)),
             ..self
         }
     }
@@ -131,10 +211,12 @@ where
         let x1 = point[0] + 26519.0 / 65536.0;
         let y1 = point[1] + 18128.0 / 65536.0;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1]) * self.power);
+        let x_distort =
+            point[0] + (self.x_distort_function.get([x0, y0]) * self.power * self.power_axes[0]);
+        let y_distort =
+            point[1] + (self.y_distort_function.get([x1, y1]) * self.power * self.power_axes[1]);
 
-        self.source.get([x_distort, y_distort])
+        self.clamp_output(self.source.get([x_distort, y_distort]))
     }
 }
 
@@ -160,11 +242,14 @@ where
         let y2 = point[1] + 11213.0 / 65536.0;
         let z2 = point[2] + 44845.0 / 65536.0;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1]) * self.power);
-        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2]) * self.power);
+        let x_distort = point[0]
+            + (self.x_distort_function.get([x0, y0, z0]) * self.power * self.power_axes[0]);
+        let y_distort = point[1]
+            + (self.y_distort_function.get([x1, y1, z1]) * self.power * self.power_axes[1]);
+        let z_distort = point[2]
+            + (self.z_distort_function.get([x2, y2, z2]) * self.power * self.power_axes[2]);
 
-        self.source.get([x_distort, y_distort, z_distort])
+        self.clamp_output(self.source.get([x_distort, y_distort, z_distort]))
     }
 }
 
@@ -198,12 +283,212 @@ where
         let z3 = point[2] + 12414.0 / 65536.0;
         let u3 = point[3] + 60943.0 / 65536.0;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0, u0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1, u1]) * self.power);
-        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2, u2]) * self.power);
-        let u_distort = point[3] + (self.u_distort_function.get([x3, y3, z3, u3]) * self.power);
+        let x_distort = point[0]
+            + (self.x_distort_function.get([x0, y0, z0, u0]) * self.power * self.power_axes[0]);
+        let y_distort = point[1]
+            + (self.y_distort_function.get([x1, y1, z1, u1]) * self.power * self.power_axes[1]);
+        let z_distort = point[2]
+            + (self.z_distort_function.get([x2, y2, z2, u2]) * self.power * self.power_axes[2]);
+        let u_distort = point[3]
+            + (self.u_distort_function.get([x3, y3, z3, u3]) * self.power * self.power_axes[3]);
+
+        self.clamp_output(
+            self.source
+                .get([x_distort, y_distort, z_distort, u_distort]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn test_seeds() {
+        let seed = 7;
+        let turbulence = Turbulence::<_, Perlin>::new(Perlin::new(seed)).set_seed(seed);
+        let next = Turbulence::<_, Perlin>::new(Perlin::new(seed)).set_seed(seed + 1);
+
+        assert_eq!(seed, turbulence.seed());
+        assert_ne!(seed, turbulence.displacement_seed());
+        assert_ne!(turbulence.displacement_seed(), next.displacement_seed());
+
+        // The displacement functions use the derived seeds, so none of their
+        // octaves is seeded like the source or like each other.
+        let octave_seeds = [
+            &turbulence.x_distort_function,
+            &turbulence.y_distort_function,
+            &turbulence.z_distort_function,
+            &turbulence.u_distort_function,
+        ]
+        .iter()
+        .flat_map(|fbm| {
+            (0..Turbulence::<Perlin, Perlin>::DEFAULT_ROUGHNESS as u32)
+                .map(move |x| fbm.seed().wrapping_add(x))
+        })
+        .collect::<alloc::vec::Vec<_>>();
+        for (i, octave_seed) in octave_seeds.iter().enumerate() {
+            assert_ne!(seed, *octave_seed);
+            assert!(!octave_seeds[i + 1..].contains(octave_seed));
+        }
+
+        // Sampled at the same points, the displacement is uncorrelated with
+        // the source.
+        let samples = (0..400)
+            .map(|i| {
+                let point = [(i % 20) as f64 * 0.37 + 0.1, (i / 20) as f64 * 0.37 + 0.1];
+                (
+                    turbulence.source.get(point),
+                    turbulence.x_distort_function.get(point),
+                )
+            })
+            .collect::<alloc::vec::Vec<_>>();
+        let dot = |f: fn(&(f64, f64)) -> f64, g: fn(&(f64, f64)) -> f64| {
+            samples.iter().map(|s| f(s) * g(s)).sum::<f64>()
+        };
+        let correlation =
+            dot(|s| s.0, |s| s.1) / (dot(|s| s.0, |s| s.0) * dot(|s| s.1, |s| s.1)).sqrt();
+        assert!(correlation.abs() < 0.2, "correlation was {}", correlation);
+
+        let mut differences = 0;
+        for i in 0..100 {
+            let point = [i as f64 * 0.173, i as f64 * 0.291];
+            if turbulence.get(point) != next.get(point) {
+                differences += 1;
+            }
+        }
+        assert!(differences > 90);
+    }
+
+    /// Outputs one coordinate of the input point.
+    struct Axis(usize);
+
+    impl NoiseFn<f64, 3> for Axis {
+        fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
+            point.into()[self.0]
+        }
+    }
+
+    impl NoiseFn<f64, 4> for Axis {
+        fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
+            point.into()[self.0]
+        }
+    }
+
+    /// Seeded generator whose 4D output ignores the _u_ coordinate, so it
+    /// extends its 3D output.
+    #[derive(Clone, Copy, Debug, Default)]
+    struct Waves(u32);
+
+    impl Seedable for Waves {
+        fn set_seed(self, seed: u32) -> Self {
+            Waves(seed)
+        }
+
+        fn seed(&self) -> u32 {
+            self.0
+        }
+    }
+
+    impl NoiseFn<f64, 3> for Waves {
+        fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
+            let [x, y, z] = point.into();
+            (x * 0.7 + y * 1.3 - z * 0.4 + (self.0 % 64) as f64).sin()
+        }
+    }
+
+    impl NoiseFn<f64, 4> for Waves {
+        fn get(&self, point: impl Into<[f64; 4]>) -> f64 {
+            let [x, y, z, _] = point.into();
+            self.get([x, y, z])
+        }
+    }
+
+    #[test]
+    fn test_4d_matches_3d_when_u_is_ignored() {
+        for axis in 0..3 {
+            let turbulence = Turbulence::<_, Waves>::new(Axis(axis))
+                .set_seed(5)
+                .set_power(0.5);
+
+            for i in 0..32 {
+                let point = [
+                    i as f64 * 0.31 + 0.2,
+                    i as f64 * 0.17 - 1.4,
+                    i as f64 * 0.23,
+                ];
+                let [x, y, z] = point;
+
+                assert_eq!(
+                    NoiseFn::<f64, 3>::get(&turbulence, point),
+                    NoiseFn::<f64, 4>::get(&turbulence, [x, y, z, 0.0])
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at most four axes are supported")]
+    fn test_power_axes_are_limited_to_four() {
+        let _ = Turbulence::<_, Perlin>::new(Axis(0)).set_power_axes([1.0; 5]);
+    }
+
+    #[test]
+    fn test_power_axes() {
+        let turbulence = |axis| {
+            Turbulence::<_, Perlin>::new(Axis(axis))
+                .set_power(0.5)
+                .set_power_axes([0.0, 2.0, 0.0])
+        };
+
+        let mut displaced = false;
+        for i in 0..32 {
+            let point = [
+                i as f64 * 0.31 + 0.2,
+                i as f64 * 0.17 - 1.4,
+                i as f64 * 0.23,
+            ];
+
+            assert_eq!(point[0], turbulence(0).get(point));
+            assert_eq!(point[2], turbulence(2).get(point));
+            displaced |= turbulence(1).get(point) != point[1];
+        }
+        assert!(displaced);
+    }
+
+    #[test]
+    fn test_clamp() {
+        let turbulence = |clamp| {
+            Turbulence::<_, Perlin>::new(Axis(0))
+                .set_power(4.0)
+                .set_clamp(clamp)
+        };
+        let (unclamped, clamped) = (turbulence(None), turbulence(Some((-1.0, 1.0))));
+        let default = Turbulence::<_, Perlin>::new(Axis(0)).set_power(4.0);
+        assert_eq!(None, default.clamp);
+
+        let mut out_of_range = 0;
+        for i in 0..32 {
+            let point = [
+                i as f64 * 0.31 - 5.0,
+                i as f64 * 0.17 - 1.4,
+                i as f64 * 0.23,
+            ];
+            let value = unclamped.get(point);
+            if value.abs() > 1.0 {
+                out_of_range += 1;
+            }
+
+            assert_eq!(value, default.get(point));
+            assert_eq!(value.clamp(-1.0, 1.0), clamped.get(point));
+        }
+        assert!(out_of_range > 16);
+    }
 
-        self.source
-            .get([x_distort, y_distort, z_distort, u_distort])
+    #[test]
+    #[should_panic(expected = "the lower bound must not exceed the upper bound")]
+    fn test_clamp_bounds_must_be_ordered() {
+        let _ = Turbulence::<_, Perlin>::new(Axis(0)).set_clamp(Some((1.0, -1.0)));
     }
 }
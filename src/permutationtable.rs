@@ -9,10 +9,26 @@ use rand_xorshift::XorShiftRng;
 
 const TABLE_SIZE: usize = 256;
 
+/// Trait for the hashing scheme used by noise functions to turn lattice
+/// coordinates into pseudo-random values.
+///
+/// The output of every generator is determined by its hasher, so a custom
+/// implementation can be used to pin the hashing scheme of long-lived worlds
+/// independently of the version of this crate.
 pub trait NoiseHasher: Send + Sync {
     fn hash(&self, to_hash: &[isize]) -> usize;
 }
 
+/// Trait for hashers that can be deterministically constructed from a seed.
+///
+/// This is required for generators using the hasher to implement `Seedable`.
+pub trait SeedableHasher: NoiseHasher {
+    fn from_seed(seed: u32) -> Self;
+}
+
+/// Hasher used by the generators unless another one is specified.
+pub type DefaultHasher = PermutationTable;
+
 /// A seed table, required by all noise functions.
 ///
 /// Table creation is expensive, so in most circumstances you'll only want to
@@ -72,6 +88,46 @@ impl NoiseHasher for PermutationTable {
     }
 }
 
+impl SeedableHasher for PermutationTable {
+    fn from_seed(seed: u32) -> Self {
+        Self::new(seed)
+    }
+}
+
+/// Hasher that wraps the lattice coordinates modulo a period along each axis
+/// before hashing them, which makes lattice noise tile exactly. Axes with a
+/// period of 0 are not wrapped.
+pub(crate) struct PeriodicHasher<'a, H: ?Sized> {
+    hasher: &'a H,
+    period: &'a [u32; 4],
+}
+
+impl<'a, H: ?Sized> PeriodicHasher<'a, H> {
+    pub(crate) fn new(hasher: &'a H, period: &'a [u32; 4]) -> Self {
+        Self { hasher, period }
+    }
+}
+
+impl<H> NoiseHasher for PeriodicHasher<'_, H>
+where
+    H: NoiseHasher + ?Sized,
+{
+    fn hash(&self, to_hash: &[isize]) -> usize {
+        // Keys may carry an extra coordinate beyond the four axes, such as the
+        // index of a feature point within its cell, which is never wrapped.
+        let mut wrapped = [0; 5];
+        let wrapped = &mut wrapped[..to_hash.len()];
+        wrapped.copy_from_slice(to_hash);
+        for (coordinate, &period) in wrapped.iter_mut().zip(self.period) {
+            if period != 0 {
+                *coordinate = coordinate.rem_euclid(period as isize);
+            }
+        }
+
+        self.hasher.hash(wrapped)
+    }
+}
+
 impl fmt::Debug for PermutationTable {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "PermutationTable {{ .. }}")
@@ -80,9 +136,26 @@ impl fmt::Debug for PermutationTable {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::{NoiseFn, Perlin, Seedable};
     use rand::random;
 
+    /// Hasher that maps every lattice point to the same gradient.
+    #[derive(Clone, Copy, Debug)]
+    struct ConstantHasher;
+
+    impl NoiseHasher for ConstantHasher {
+        fn hash(&self, _to_hash: &[isize]) -> usize {
+            0
+        }
+    }
+
+    impl SeedableHasher for ConstantHasher {
+        fn from_seed(_seed: u32) -> Self {
+            ConstantHasher
+        }
+    }
+
     #[test]
     fn test_random_seed() {
         let perlin = Perlin::default().set_seed(random());
@@ -94,4 +167,38 @@ mod tests {
         let perlin = Perlin::default();
         let _ = perlin.get([-1.0, 2.0, 3.0]);
     }
+
+    #[test]
+    fn test_default_hasher_golden_values() {
+        // These values pin the output of the default hashing scheme. If they
+        // change, every seeded world generated with this crate changes too.
+        let table = DefaultHasher::from_seed(0);
+        assert_eq!(162, table.hash(&[0, 0]));
+        assert_eq!(64, table.hash(&[1, 2]));
+        assert_eq!(250, table.hash(&[-3, 7]));
+        assert_eq!(181, table.hash(&[255, 256]));
+
+        let perlin = Perlin::new(0);
+        let golden = [
+            ([0.5, 0.25], 0.3535533905932738),
+            ([1.7, -3.2], 0.48449168537901405),
+            ([-10.1, 4.4], -0.061585688892596246),
+        ];
+        for &(point, expected) in golden.iter() {
+            assert!((perlin.get(point) - expected).abs() < 1e-12);
+        }
+        assert!((perlin.get([0.5, 0.25, 0.75]) - -0.47696742905496936).abs() < 1e-12);
+        assert!((perlin.get([1.7, -3.2, 2.9]) - 0.03625280071352117).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_custom_hasher() {
+        let default = Perlin::with_hasher(3, PermutationTable::new(3));
+        assert_eq!(Perlin::new(3).get([1.3, 2.7]), default.get([1.3, 2.7]));
+
+        let perlin = Perlin::with_hasher(0, ConstantHasher).set_seed(7);
+        assert_eq!(7, perlin.seed());
+        // With every lattice point sharing a gradient, the noise is periodic.
+        assert!((perlin.get([0.25, 0.5]) - perlin.get([5.25, -2.5])).abs() < 1e-12);
+    }
 }
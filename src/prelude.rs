@@ -0,0 +1,32 @@
+//! Re-exports of the traits and the most commonly used noise functions.
+//!
+//! Glob importing the prelude covers typical usage without importing each
+//! type individually:
+//!
+//! ```rust
+//! use noise::prelude::*;
+//!
+//! let mountains = Fbm::<Perlin>::new(0).set_octaves(4).set_frequency(0.5);
+//! let plains = ScaleBias::new(Billow::<Perlin>::new(1))
+//!     .set_scale(0.125)
+//!     .set_bias(-0.75);
+//! let terrain = Select::new(plains, mountains, Perlin::new(2))
+//!     .set_bounds(0.0, 1000.0)
+//!     .set_falloff(0.125);
+//! let terrain = Clamp::new(Add::new(terrain, Constant::new(0.1)));
+//!
+//! let map = PlaneMapBuilder::new(&terrain).set_size(16, 16).build();
+//! assert_eq!((16, 16), map.size());
+//! assert!(terrain.get([0.5, 0.25, 0.0]).abs() <= 1.0);
+//! ```
+
+pub use crate::{
+    noise_fns::{
+        Abs, Add, BasicMulti, Billow, Blend, Cache, Checkerboard, Clamp, Constant, Curve,
+        Cylinders, Displace, Exponent, Fbm, Frequency, HybridMulti, Max, Min, MultiFractal,
+        Multiply, Negate, NoiseFn, OpenSimplex, Perlin, Power, RidgedMulti, RotatePoint, ScaleBias,
+        ScalePoint, Seedable, Select, Simplex, SuperSimplex, Terrace, TranslatePoint, Turbulence,
+        Value, Worley,
+    },
+    utils::{NoiseMap, NoiseMapBuilder, PlaneMapBuilder},
+};
@@ -1,10 +1,16 @@
 #[cfg(feature = "image")]
 pub use self::image_renderer::*;
-pub use self::{color_gradient::*, noise_image::*, noise_map::*, noise_map_builder::*};
+pub use self::{
+    color_gradient::*, finite_diff::*, noise_image::*, noise_map::*, noise_map_builder::*,
+    stats::*, to_color::*,
+};
 
 mod color_gradient;
+mod finite_diff;
 #[cfg(feature = "image")]
 mod image_renderer;
 mod noise_image;
 mod noise_map;
 mod noise_map_builder;
+mod stats;
+mod to_color;
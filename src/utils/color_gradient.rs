@@ -80,7 +80,7 @@ impl ColorGradient {
         self.gradient_points
             .iter()
             .position(|x| x.pos >= pos)
-            .unwrap_or_else(|| self.gradient_points.len())
+            .unwrap_or(self.gradient_points.len())
     }
 
     pub fn clear_gradient(mut self) -> Self {
@@ -124,30 +124,46 @@ impl ColorGradient {
             .add_gradient_point( 1.0, [255,   0,   0, 255])
     }
 
+    /// Returns the color at `pos`, linearly interpolated between the nearest
+    /// gradient points on either side. Positions beyond the first or last
+    /// gradient point take its color, and an empty gradient is black.
     pub fn get_color(&self, pos: f64) -> Color {
-        let mut color = Color::default();
-
-        // If there are no colors in the gradient, return black
-        if !self.gradient_points.is_empty() {
-            match () {
-                _ if pos < self.domain.min => color = self.gradient_points.first().unwrap().color,
-                _ if pos > self.domain.max => color = self.gradient_points.last().unwrap().color,
-                _ => {
-                    for points in self.gradient_points.windows(2) {
-                        if (points[0].pos <= pos) && (points[1].pos > pos) {
-                            // Compute the alpha value used for linear interpolation
-                            let alpha = (pos - points[0].pos) / (points[1].pos - points[0].pos);
-
-                            // Now perform the interpolation and return.
-                            color = interpolate_color(points[0].color, points[1].color, alpha)
-                        }
-                    }
-                }
-            };
+        let (first, last) = match (self.gradient_points.first(), self.gradient_points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            // If there are no colors in the gradient, return black
+            _ => return Color::default(),
         };
 
+        if pos <= first.pos {
+            return first.color;
+        }
+        if pos >= last.pos {
+            return last.color;
+        }
+
+        let mut color = Color::default();
+        for points in self.gradient_points.windows(2) {
+            if (points[0].pos <= pos) && (points[1].pos > pos) {
+                // Compute the alpha value used for linear interpolation
+                let alpha = (pos - points[0].pos) / (points[1].pos - points[0].pos);
+
+                // Now perform the interpolation and return.
+                color = interpolate_color(points[0].color, points[1].color, alpha)
+            }
+        }
+
         color
     }
+
+    /// Shorthand for [`add_gradient_point`](Self::add_gradient_point).
+    pub fn add_stop(self, pos: f64, color: Color) -> Self {
+        self.add_gradient_point(pos, color)
+    }
+
+    /// Shorthand for [`get_color`](Self::get_color).
+    pub fn get(&self, pos: f64) -> Color {
+        self.get_color(pos)
+    }
 }
 
 fn interpolate_color(color0: Color, color1: Color, alpha: f64) -> Color {
@@ -190,4 +206,40 @@ mod tests {
 
         assert_eq!([127, 127, 127, 127], gradient.get_color(0.5));
     }
+
+    #[test]
+    fn test_interpolates_between_stops() {
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_stop(-0.5, [0, 0, 0, 255])
+            .add_stop(0.5, [200, 100, 0, 255]);
+
+        assert_eq!([0, 0, 0, 255], gradient.get(-0.5));
+        assert_eq!([50, 25, 0, 255], gradient.get(-0.25));
+        assert_eq!([100, 50, 0, 255], gradient.get(0.0));
+        assert_eq!([200, 100, 0, 255], gradient.get(0.5));
+    }
+
+    #[test]
+    fn test_clamps_beyond_stops() {
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_stop(0.5, [200, 100, 0, 255])
+            .add_stop(-0.5, [0, 0, 0, 255]);
+
+        assert_eq!([0, 0, 0, 255], gradient.get(-0.75));
+        assert_eq!([0, 0, 0, 255], gradient.get(-10.0));
+        assert_eq!([200, 100, 0, 255], gradient.get(0.75));
+        assert_eq!([200, 100, 0, 255], gradient.get(10.0));
+
+        let single = ColorGradient::new()
+            .clear_gradient()
+            .add_stop(0.0, [1, 2, 3, 4]);
+        assert_eq!([1, 2, 3, 4], single.get(-1.0));
+        assert_eq!([1, 2, 3, 4], single.get(0.0));
+        assert_eq!([1, 2, 3, 4], single.get(1.0));
+
+        let empty = ColorGradient::new().clear_gradient();
+        assert_eq!([0, 0, 0, 0], empty.get(0.0));
+    }
 }
@@ -0,0 +1,124 @@
+use crate::noise_fns::NoiseFn;
+
+/// Estimates the gradient of a source function by central finite differences.
+///
+/// The partial derivative along each axis is computed from two samples of the
+/// source, one `epsilon` on either side of the point. The ideal step depends
+/// on the frequency of the source: too small and the difference of the two
+/// nearly equal samples is lost to floating point cancellation, too large and
+/// features smaller than the step are smoothed away. A step of about 1/1000
+/// of the feature size is a reasonable starting point. Steps smaller than
+/// [`MIN_EPSILON`](Self::MIN_EPSILON) are rejected.
+#[derive(Clone, Copy, Debug)]
+pub struct FiniteDiff<Source, const DIM: usize>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    epsilons: [f64; DIM],
+}
+
+impl<Source, const DIM: usize> FiniteDiff<Source, DIM>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    pub const DEFAULT_EPSILON: f64 = 1e-4;
+
+    /// Smallest step accepted, below which the differences of the samples of
+    /// typical noise functions are dominated by rounding errors.
+    pub const MIN_EPSILON: f64 = 1e-9;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            epsilons: [Self::DEFAULT_EPSILON; DIM],
+        }
+    }
+
+    /// Sets the step along every axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epsilon` is smaller than [`MIN_EPSILON`](Self::MIN_EPSILON).
+    pub fn set_epsilon(self, epsilon: f64) -> Self {
+        self.set_epsilons([epsilon; DIM])
+    }
+
+    /// Sets the step along each axis, for sources whose frequency differs
+    /// between axes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any step is smaller than [`MIN_EPSILON`](Self::MIN_EPSILON).
+    pub fn set_epsilons(self, epsilons: [f64; DIM]) -> Self {
+        for &epsilon in epsilons.iter() {
+            assert!(
+                epsilon >= Self::MIN_EPSILON,
+                "epsilon {} is smaller than the minimum of {}",
+                epsilon,
+                Self::MIN_EPSILON
+            );
+        }
+
+        Self { epsilons, ..self }
+    }
+
+    pub fn epsilons(&self) -> [f64; DIM] {
+        self.epsilons
+    }
+
+    /// Returns the estimated gradient of the source at the point.
+    pub fn gradient(&self, point: impl Into<[f64; DIM]>) -> [f64; DIM] {
+        let point = point.into();
+
+        let mut gradient = [0.0; DIM];
+        for (axis, derivative) in gradient.iter_mut().enumerate() {
+            let epsilon = self.epsilons[axis];
+            let (mut low, mut high) = (point, point);
+            low[axis] -= epsilon;
+            high[axis] += epsilon;
+
+            *derivative = (self.source.get(high) - self.source.get(low)) / (2.0 * epsilon);
+        }
+        gradient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Ramp;
+
+    impl NoiseFn<f64, 3> for Ramp {
+        fn get(&self, point: impl Into<[f64; 3]>) -> f64 {
+            let [x, y, z] = point.into();
+            2.0 * x - 3.0 * y + 0.5 * z
+        }
+    }
+
+    #[test]
+    fn test_ramp_slope_is_independent_of_epsilon() {
+        let point = [1.3, -0.7, 4.1];
+
+        for &epsilon in [1e-6, 1e-4, 1e-2, 0.5].iter() {
+            let gradient = FiniteDiff::new(Ramp).set_epsilon(epsilon).gradient(point);
+            for (derivative, expected) in gradient.iter().zip([2.0, -3.0, 0.5].iter()) {
+                assert!((derivative - expected).abs() < 1e-6);
+            }
+        }
+
+        let gradient = FiniteDiff::new(Ramp)
+            .set_epsilons([1e-3, 1e-2, 1e-1])
+            .gradient(point);
+        assert!((gradient[1] - -3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_too_small_epsilon_is_rejected() {
+        let _ = FiniteDiff::new(Ramp).set_epsilon(1e-12);
+    }
+}
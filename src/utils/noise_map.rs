@@ -1,12 +1,28 @@
+#[cfg(feature = "image")]
+use super::color_gradient::ColorGradient;
+use crate::math::{
+    interpolate,
+    vectors::{Vector, Vector3},
+};
 use alloc::{
     slice::{Iter, IterMut},
     vec::{IntoIter, Vec},
 };
-use core::ops::{Index, IndexMut};
+use core::{
+    iter::Enumerate,
+    ops::{Index, IndexMut},
+};
 
 const RASTER_MAX_WIDTH: u16 = 32_767;
 const RASTER_MAX_HEIGHT: u16 = 32_767;
 
+#[cfg(feature = "std")]
+const FILE_MAGIC: [u8; 4] = *b"NMAP";
+#[cfg(feature = "std")]
+const FILE_VERSION: u32 = 1;
+#[cfg(feature = "std")]
+const FILE_HEADER_LEN: usize = 16;
+
 #[derive(Clone, Debug)]
 pub struct NoiseMap {
     size: (usize, usize),
@@ -27,6 +43,13 @@ impl NoiseMap {
         self.map.iter_mut()
     }
 
+    /// Returns the values stored in the map, laid out row by row.
+    pub fn values(&self) -> &[f64] {
+        let (width, height) = self.size;
+
+        &self.map[..width * height]
+    }
+
     pub fn set_size(self, width: usize, height: usize) -> Self {
         // Check for invalid width or height.
         assert!(width < RASTER_MAX_WIDTH as usize);
@@ -92,6 +115,220 @@ impl NoiseMap {
         }
     }
 
+    /// Returns the smallest and largest values stored in the map.
+    ///
+    /// An empty map returns `(f64::INFINITY, f64::NEG_INFINITY)`.
+    pub fn min_max(&self) -> (f64, f64) {
+        self.values()
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            })
+    }
+
+    /// Rescales the values stored in the map, in place, so that they span the
+    /// range 0.0 to 1.0 based on the actual minimum and maximum of the map.
+    ///
+    /// If every value in the map is the same, all values are set to 0.0.
+    pub fn normalize(&mut self) {
+        let (width, height) = self.size;
+        let (min, max) = self.min_max();
+        let range = max - min;
+
+        for value in &mut self.map[..width * height] {
+            *value = if range > 0.0 {
+                (*value - min) / range
+            } else {
+                0.0
+            };
+        }
+    }
+
+    /// Returns the surface normal at each value of the map, laid out row by
+    /// row, treating the values as heights.
+    ///
+    /// The slope at each value is estimated from the difference between its
+    /// neighbors, or between the value and its only neighbor at the edges of
+    /// the map. `strength` scales the slopes, so larger values exaggerate the
+    /// relief. The normals are normalized and a flat map yields `[0.0, 0.0,
+    /// 1.0]` everywhere.
+    pub fn to_normal_map(&self, strength: f64) -> Vec<[f32; 3]> {
+        let (width, height) = self.size;
+
+        // Estimates the slope along one axis from the values before and after
+        // the current one, falling back to one-sided differences at the edges.
+        let slope = |index: usize, len: usize, value: &dyn Fn(usize) -> f64| {
+            let before = index.saturating_sub(1);
+            let after = (index + 1).min(len - 1);
+
+            if before == after {
+                0.0
+            } else {
+                (value(after) - value(before)) / (after - before) as f64
+            }
+        };
+
+        let mut normals = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let dx = slope(x, width, &|x| self.get_value(x, y));
+                let dy = slope(y, height, &|y| self.get_value(x, y));
+
+                let normal = Vector3::new(-dx * strength, -dy * strength, 1.0);
+                let normal = normal / normal.magnitude();
+
+                normals.push([normal.x as f32, normal.y as f32, normal.z as f32]);
+            }
+        }
+
+        normals
+    }
+
+    /// Samples the map at normalized coordinates, interpolating bilinearly
+    /// between the four nearest values.
+    ///
+    /// `u` and `v` range from 0.0 to 1.0 across the width and height of the
+    /// map, with the value at `(x, y)` stored at the center of its texel, at
+    /// `((x + 0.5) / width, (y + 0.5) / height)`. Coordinates beyond the
+    /// outermost texel centers are clamped to the edges of the map. An empty
+    /// map returns the border value.
+    pub fn sample_bilinear(&self, u: f64, v: f64) -> f64 {
+        let (width, height) = self.size;
+        if width == 0 || height == 0 {
+            return self.border_value;
+        }
+
+        // Finds the texels on either side of the coordinate along one axis,
+        // and the weight of the second one.
+        let neighbors = |coordinate: f64, len: usize| {
+            let position = (coordinate * len as f64 - 0.5)
+                .max(0.0)
+                .min((len - 1) as f64);
+            let low = position.floor() as usize;
+
+            (low, (low + 1).min(len - 1), position - low as f64)
+        };
+
+        let (x0, x1, tx) = neighbors(u, width);
+        let (y0, y1, ty) = neighbors(v, height);
+
+        let top = interpolate::linear(self.get_value(x0, y0), self.get_value(x1, y0), tx);
+        let bottom = interpolate::linear(self.get_value(x0, y1), self.get_value(x1, y1), tx);
+
+        interpolate::linear(top, bottom, ty)
+    }
+
+    /// Returns a copy of the `width` by `height` region of the map whose
+    /// top-left value is at `(x, y)`.
+    ///
+    /// Parts of the region that lie outside the map are filled with the
+    /// border value, which the cropped map keeps.
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> NoiseMap {
+        let mut cropped = NoiseMap::new(width, height).set_border_value(self.border_value);
+        for j in 0..height {
+            for i in 0..width {
+                cropped[(i, j)] = self.get_value(x + i, y + j);
+            }
+        }
+
+        cropped
+    }
+
+    /// Returns a copy of the map resampled to `width` by `height` values with
+    /// [`sample_bilinear`](Self::sample_bilinear), without sampling the
+    /// source function again.
+    ///
+    /// The texel centers of the two maps are aligned, so resizing to the same
+    /// size returns the stored values unchanged.
+    pub fn resize_bilinear(&self, width: usize, height: usize) -> NoiseMap {
+        let mut resized = NoiseMap::new(width, height).set_border_value(self.border_value);
+        for j in 0..height {
+            for i in 0..width {
+                let (u, v) = (
+                    (i as f64 + 0.5) / width as f64,
+                    (j as f64 + 0.5) / height as f64,
+                );
+                resized[(i, j)] = self.sample_bilinear(u, v);
+            }
+        }
+
+        resized
+    }
+
+    /// Saves the map to a file in a compact binary format that can be read
+    /// back with [`load`](Self::load).
+    ///
+    /// The file starts with a header of the magic bytes `NMAP`, the format
+    /// version, the width and the height, each of the last three as a
+    /// little-endian `u32`. The values follow row by row as little-endian
+    /// `f64`s. The border value is not saved.
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        let (width, height) = self.size;
+        let mut bytes = Vec::with_capacity(FILE_HEADER_LEN + width * height * 8);
+
+        bytes.extend_from_slice(&FILE_MAGIC);
+        bytes.extend_from_slice(&FILE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(width as u32).to_le_bytes());
+        bytes.extend_from_slice(&(height as u32).to_le_bytes());
+        for value in self.values() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        std::fs::write(path, bytes)
+    }
+
+    /// Loads a map saved with [`save`](Self::save).
+    ///
+    /// Returns an error of kind `InvalidData` if the file is not a noise map
+    /// of a supported version, and of kind `UnexpectedEof` if it is
+    /// truncated.
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+
+        let bytes = std::fs::read(path)?;
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidData, message);
+        let truncated = |message: &str| Error::new(ErrorKind::UnexpectedEof, message);
+
+        if bytes.len() < FILE_HEADER_LEN {
+            return Err(truncated("noise map header is truncated"));
+        }
+        if bytes[..4] != FILE_MAGIC {
+            return Err(invalid("not a noise map file"));
+        }
+
+        let read_u32 = |offset: usize| {
+            let mut word = [0; 4];
+            word.copy_from_slice(&bytes[offset..offset + 4]);
+            u32::from_le_bytes(word)
+        };
+
+        if read_u32(4) != FILE_VERSION {
+            return Err(invalid("unsupported noise map version"));
+        }
+
+        let width = read_u32(8) as usize;
+        let height = read_u32(12) as usize;
+        if width >= RASTER_MAX_WIDTH as usize || height >= RASTER_MAX_HEIGHT as usize {
+            return Err(invalid("noise map size is too large"));
+        }
+
+        let values = &bytes[FILE_HEADER_LEN..];
+        if values.len() < width * height * 8 {
+            return Err(truncated("noise map values are truncated"));
+        }
+
+        let mut map = Self::new(width, height);
+        for (value, chunk) in map.map.iter_mut().zip(values.chunks_exact(8)) {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            *value = f64::from_le_bytes(word);
+        }
+
+        Ok(map)
+    }
+
     #[cfg(feature = "images")]
     pub fn write_to_file(&self, filename: &str) {
         use std::{fs, path::Path};
@@ -111,8 +348,8 @@ impl NoiseMap {
         let (width, height) = self.size;
         let mut pixels: Vec<u8> = Vec::with_capacity(width * height);
 
-        for i in &self.map {
-            pixels.push(((i * 0.5 + 0.5).clamp(0.0, 1.0) * 255.0) as u8);
+        for &value in &self.map {
+            pixels.push(to_luma(value));
         }
 
         let _ = image::save_buffer(
@@ -126,6 +363,17 @@ impl NoiseMap {
         println!("\nFinished generating {}", filename);
     }
 
+    /// Renders the map as an RGB image, coloring each value with `gradient`.
+    #[cfg(feature = "image")]
+    pub fn to_rgb_image(&self, gradient: &ColorGradient) -> image::RgbImage {
+        let (width, height) = self.size;
+
+        image::RgbImage::from_fn(width as u32, height as u32, |x, y| {
+            let [r, g, b, _] = gradient.get_color(self[(x as usize, y as usize)]);
+            image::Rgb([r, g, b])
+        })
+    }
+
     fn initialize() -> Self {
         Self {
             size: (0, 0),
@@ -135,6 +383,25 @@ impl NoiseMap {
     }
 }
 
+/// Renders the map as a grayscale image, mapping values from -1.0 to 1.0 onto
+/// black to white. Values outside of that range are clamped.
+#[cfg(feature = "image")]
+impl From<&NoiseMap> for image::GrayImage {
+    fn from(map: &NoiseMap) -> Self {
+        let (width, height) = map.size;
+        let pixels = map.map.iter().map(|&value| to_luma(value)).collect();
+
+        image::GrayImage::from_raw(width as u32, height as u32, pixels)
+            .expect("the map has a value for every pixel")
+    }
+}
+
+/// Maps a value from -1.0 to 1.0 onto a grayscale intensity.
+#[cfg(feature = "image")]
+fn to_luma(value: f64) -> u8 {
+    ((value * 0.5 + 0.5).clamp(0.0, 1.0) * 255.0) as u8
+}
+
 impl Default for NoiseMap {
     fn default() -> Self {
         Self::initialize()
@@ -179,12 +446,15 @@ impl IntoIterator for NoiseMap {
 }
 
 impl<'a> IntoIterator for &'a NoiseMap {
-    type Item = &'a f64;
+    type Item = (usize, usize, f64);
 
-    type IntoIter = Iter<'a, f64>;
+    type IntoIter = NoiseMapIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+        NoiseMapIter {
+            width: self.size.0,
+            values: self.values().iter().enumerate(),
+        }
     }
 }
 
@@ -197,3 +467,273 @@ impl<'a> IntoIterator for &'a mut NoiseMap {
         self.iter_mut()
     }
 }
+
+/// Iterator over the coordinates and values of a [`NoiseMap`], yielding
+/// `(x, y, value)` in row-major order.
+///
+/// Created by iterating over a `&NoiseMap`.
+#[derive(Clone, Debug)]
+pub struct NoiseMapIter<'a> {
+    width: usize,
+    values: Enumerate<Iter<'a, f64>>,
+}
+
+impl<'a> Iterator for NoiseMapIter<'a> {
+    type Item = (usize, usize, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values
+            .next()
+            .map(|(index, &value)| (index % self.width, index / self.width, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for NoiseMapIter<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_max() {
+        let mut map = NoiseMap::new(3, 2);
+        map[(0, 0)] = -0.5;
+        map[(2, 1)] = 0.75;
+
+        assert_eq!((-0.5, 0.75), map.min_max());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut map = NoiseMap::new(4, 4);
+        for (i, value) in map.iter_mut().enumerate() {
+            *value = (i as f64 * 0.37).sin() * 3.0;
+        }
+
+        map.normalize();
+
+        assert_eq!((0.0, 1.0), map.min_max());
+    }
+
+    #[test]
+    fn test_normalize_flat() {
+        let mut map = NoiseMap::new(2, 2);
+        for value in map.iter_mut() {
+            *value = 0.25;
+        }
+
+        map.normalize();
+
+        assert_eq!((0.0, 0.0), map.min_max());
+    }
+
+    #[test]
+    fn test_iter_row_major() {
+        let mut map = NoiseMap::new(3, 2);
+        for (i, value) in map.iter_mut().enumerate() {
+            *value = i as f64;
+        }
+
+        let items: Vec<_> = (&map).into_iter().collect();
+
+        assert_eq!(3 * 2, items.len());
+        assert_eq!(
+            vec![
+                (0, 0, 0.0),
+                (1, 0, 1.0),
+                (2, 0, 2.0),
+                (0, 1, 3.0),
+                (1, 1, 4.0),
+                (2, 1, 5.0),
+            ],
+            items
+        );
+        assert_eq!(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0], map.values());
+    }
+
+    #[test]
+    fn test_sample_bilinear() {
+        let mut map = NoiseMap::new(4, 2);
+        for y in 0..2 {
+            for x in 0..4 {
+                map[(x, y)] = x as f64 + y as f64 * 10.0;
+            }
+        }
+
+        // Texel centers return the stored values.
+        for y in 0..2 {
+            for x in 0..4 {
+                let (u, v) = ((x as f64 + 0.5) / 4.0, (y as f64 + 0.5) / 2.0);
+                assert_eq!(map[(x, y)], map.sample_bilinear(u, v));
+            }
+        }
+
+        // Midpoints between texel centers interpolate.
+        assert_eq!(0.5, map.sample_bilinear(0.25, 0.25));
+        assert_eq!(5.0, map.sample_bilinear(0.125, 0.5));
+        assert_eq!(7.5, map.sample_bilinear(0.75, 0.5));
+
+        // Coordinates beyond the texel centers are clamped.
+        assert_eq!(0.0, map.sample_bilinear(0.0, 0.0));
+        assert_eq!(13.0, map.sample_bilinear(1.5, 2.0));
+        assert_eq!(0.0, NoiseMap::new(0, 0).sample_bilinear(0.5, 0.5));
+    }
+
+    #[test]
+    fn test_crop() {
+        let mut map = NoiseMap::new(4, 3).set_border_value(-1.0);
+        for y in 0..3 {
+            for x in 0..4 {
+                map[(x, y)] = x as f64 + y as f64 * 10.0;
+            }
+        }
+
+        let cropped = map.crop(1, 1, 2, 2);
+        assert_eq!((2, 2), cropped.size());
+        assert_eq!(&[11.0, 12.0, 21.0, 22.0], cropped.values());
+
+        // The part of the region beyond the map is filled with the border
+        // value.
+        let cropped = map.crop(3, 2, 2, 2);
+        assert_eq!(&[23.0, -1.0, -1.0, -1.0], cropped.values());
+        assert_eq!(-1.0, cropped.border_value());
+    }
+
+    #[test]
+    fn test_resize_bilinear() {
+        let mut map = NoiseMap::new(4, 2);
+        for (i, value) in map.iter_mut().enumerate() {
+            *value = (i as f64 * 0.37).sin();
+        }
+
+        assert_eq!(map.values(), map.resize_bilinear(4, 2).values());
+
+        // Halving the size averages pairs of values along the halved axis.
+        let halved = map.resize_bilinear(2, 2);
+        assert_eq!((2, 2), halved.size());
+        for y in 0..2 {
+            for x in 0..2 {
+                let expected = (map[(x * 2, y)] + map[(x * 2 + 1, y)]) / 2.0;
+                assert!((halved[(x, y)] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_normal_map_flat() {
+        let mut map = NoiseMap::new(4, 3);
+        for value in map.iter_mut() {
+            *value = 0.3;
+        }
+
+        let normals = map.to_normal_map(2.0);
+
+        assert_eq!(4 * 3, normals.len());
+        assert!(normals.iter().all(|&normal| normal == [0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_normal_map_ramp() {
+        let mut map = NoiseMap::new(4, 3);
+        for y in 0..3 {
+            for x in 0..4 {
+                map[(x, y)] = x as f64 * 0.25;
+            }
+        }
+
+        // The slope of a ramp is the same with one-sided differences at the
+        // edges, so every normal leans away from the uphill direction equally.
+        let normals = map.to_normal_map(2.0);
+        let expected = [-0.5 / 1.25_f64.sqrt(), 0.0, 1.0 / 1.25_f64.sqrt()];
+
+        for normal in normals {
+            assert!((f64::from(normal[0]) - expected[0]).abs() < 1e-6);
+            assert_eq!(0.0, normal[1]);
+            assert!((f64::from(normal[2]) - expected[2]).abs() < 1e-6);
+        }
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_image_conversion() {
+        let mut map = NoiseMap::new(3, 2);
+        map[(0, 0)] = -1.0;
+        map[(1, 0)] = 1.0;
+        map[(2, 1)] = 2.0;
+
+        let gray = image::GrayImage::from(&map);
+        assert_eq!((3, 2), gray.dimensions());
+        assert_eq!(image::Luma([0]), *gray.get_pixel(0, 0));
+        assert_eq!(image::Luma([255]), *gray.get_pixel(1, 0));
+        assert_eq!(image::Luma([127]), *gray.get_pixel(0, 1));
+        assert_eq!(image::Luma([255]), *gray.get_pixel(2, 1));
+
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_gradient_point(-1.0, [255, 0, 0, 255])
+            .add_gradient_point(1.0, [0, 0, 255, 255]);
+        let rgb = map.to_rgb_image(&gradient);
+        assert_eq!((3, 2), rgb.dimensions());
+        assert_eq!(image::Rgb([255, 0, 0]), *rgb.get_pixel(0, 0));
+        assert_eq!(image::Rgb([127, 0, 127]), *rgb.get_pixel(0, 1));
+        assert_eq!(image::Rgb([0, 0, 255]), *rgb.get_pixel(2, 1));
+    }
+
+    #[cfg(feature = "std")]
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("noise_map_{}_{}.nmap", name, std::process::id()))
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_save_load_round_trip() {
+        let mut map = NoiseMap::new(5, 3);
+        for (i, value) in map.iter_mut().enumerate() {
+            *value = (i as f64 * 0.61).sin();
+        }
+
+        let path = temp_path("round_trip");
+        map.save(&path).unwrap();
+        let loaded = NoiseMap::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(map.size(), loaded.size());
+        assert_eq!(map.values(), loaded.values());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_load_rejects_corrupted_files() {
+        use std::io::ErrorKind;
+
+        let path = temp_path("corrupted");
+        NoiseMap::new(4, 4).save(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        std::fs::write(&path, bad_magic).unwrap();
+        let error = NoiseMap::load(&path).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 99;
+        std::fs::write(&path, bad_version).unwrap();
+        let error = NoiseMap::load(&path).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+
+        std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        let error = NoiseMap::load(&path).unwrap_err();
+        assert_eq!(ErrorKind::UnexpectedEof, error.kind());
+
+        std::fs::write(&path, &bytes[..10]).unwrap();
+        let error = NoiseMap::load(&path).unwrap_err();
+        assert_eq!(ErrorKind::UnexpectedEof, error.kind());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
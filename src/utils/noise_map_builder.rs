@@ -1,4 +1,5 @@
 use crate::{math::interpolate, noise_fns::NoiseFn, utils::noise_map::NoiseMap};
+use alloc::vec::Vec;
 
 pub trait NoiseMapBuilder<SourceModule> {
     fn set_size(self, width: usize, height: usize) -> Self;
@@ -160,6 +161,12 @@ where
         }
     }
 
+    /// Enables or disables seamless rendering, so that the baked map tiles
+    /// along both axes whatever the source module.
+    ///
+    /// Each value is blended with the values one extent away along each axis,
+    /// so the values wrap around from one edge of the map to the other, as if
+    /// the map were rendered on a torus.
     pub fn set_is_seamless(self, is_seamless: bool) -> Self {
         PlaneMapBuilder {
             is_seamless,
@@ -181,6 +188,10 @@ where
         }
     }
 
+    pub fn is_seamless(&self) -> bool {
+        self.is_seamless
+    }
+
     pub fn x_bounds(&self) -> (f64, f64) {
         self.x_bounds
     }
@@ -188,6 +199,74 @@ where
     pub fn y_bounds(&self) -> (f64, f64) {
         self.y_bounds
     }
+
+    /// Renders the map in horizontal strips of up to `chunk_rows` rows,
+    /// handing each strip to `f` along with the index of its first row.
+    ///
+    /// Only a single strip is held in memory at a time, so this can be used to
+    /// stream very large maps to an encoder or to disk. The values in each
+    /// strip are laid out row by row, exactly as they would be in the map
+    /// returned by `build`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_rows` is zero.
+    pub fn render_chunks<F>(&self, chunk_rows: usize, mut f: F)
+    where
+        F: FnMut(usize, &[f64]),
+    {
+        assert!(chunk_rows > 0, "chunk_rows must be greater than zero");
+
+        let (width, height) = self.size;
+        let mut chunk = Vec::with_capacity(width * chunk_rows.min(height));
+
+        for y0 in (0..height).step_by(chunk_rows) {
+            chunk.clear();
+
+            for y in y0..(y0 + chunk_rows).min(height) {
+                for x in 0..width {
+                    chunk.push(self.sample(x, y));
+                }
+            }
+
+            f(y0, &chunk);
+        }
+    }
+
+    fn sample(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
+
+        let x_extent = self.x_bounds.1 - self.x_bounds.0;
+        let y_extent = self.y_bounds.1 - self.y_bounds.0;
+
+        let x_step = x_extent / width as f64;
+        let y_step = y_extent / height as f64;
+
+        let current_x = self.x_bounds.0 + x_step * x as f64;
+        let current_y = self.y_bounds.0 + y_step * y as f64;
+
+        if self.is_seamless {
+            let sw_value = self.source_module.get([current_x, current_y, 0.0]);
+            let se_value = self
+                .source_module
+                .get([current_x + x_extent, current_y, 0.0]);
+            let nw_value = self
+                .source_module
+                .get([current_x, current_y + y_extent, 0.0]);
+            let ne_value =
+                self.source_module
+                    .get([current_x + x_extent, current_y + y_extent, 0.0]);
+
+            let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
+            let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
+
+            let y0 = interpolate::linear(sw_value, se_value, x_blend);
+            let y1 = interpolate::linear(nw_value, ne_value, x_blend);
+
+            interpolate::linear(y0, y1, y_blend)
+        } else {
+            self.source_module.get([current_x, current_y, 0.0])
+        }
+    }
 }
 
 impl<SourceModule> NoiseMapBuilder<SourceModule> for PlaneMapBuilder<SourceModule>
@@ -217,42 +296,9 @@ where
 
         let mut result_map = NoiseMap::new(width, height);
 
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
-
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
-
         for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
             for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0]);
-                    let se_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y, 0.0]);
-                    let nw_value = self
-                        .source_module
-                        .get([current_x, current_y + y_extent, 0.0]);
-                    let ne_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y + y_extent, 0.0]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y, 0.0])
-                };
-
-                result_map[(x, y)] = final_value;
+                result_map[(x, y)] = self.sample(x, y);
             }
         }
 
@@ -378,3 +424,54 @@ fn lat_lon_to_xyz(lat: f64, lon: f64) -> [f64; 3] {
 
     [x, y, z]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn test_render_chunks_matches_build() {
+        let builder = PlaneMapBuilder::new(Perlin::default())
+            .set_size(17, 11)
+            .set_x_bounds(-2.0, 2.0)
+            .set_y_bounds(-1.5, 1.5);
+
+        let full = builder.build();
+
+        let mut chunked = Vec::new();
+        let mut next_row = 0;
+        builder.render_chunks(4, |y0, rows| {
+            assert_eq!(next_row, y0);
+            next_row += rows.len() / 17;
+            chunked.extend_from_slice(rows);
+        });
+
+        assert_eq!(11, next_row);
+        assert_eq!(
+            full.iter().take(17 * 11).copied().collect::<Vec<_>>(),
+            chunked
+        );
+    }
+
+    #[test]
+    fn test_seamless_edges_wrap() {
+        let (width, height) = (32, 24);
+        let builder = PlaneMapBuilder::new(Perlin::default())
+            .set_size(width, height)
+            .set_x_bounds(-2.0, 2.0)
+            .set_y_bounds(-1.5, 1.5)
+            .set_is_seamless(true);
+        assert!(builder.is_seamless());
+
+        // The values just past the far edges of the map match the values on
+        // the opposite edges, so the map tiles.
+        let map = builder.build();
+        for y in 0..height {
+            assert!((map[(0, y)] - builder.sample(width, y)).abs() < 1e-9);
+        }
+        for x in 0..width {
+            assert!((map[(x, 0)] - builder.sample(x, height)).abs() < 1e-9);
+        }
+    }
+}
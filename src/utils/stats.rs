@@ -0,0 +1,131 @@
+use crate::noise_fns::NoiseFn;
+use alloc::vec::Vec;
+
+/// Statistics of the values of a noise function over a sampled region,
+/// computed by [`sample_stats`].
+///
+/// These help to choose the settings of functions that remap the output, such
+/// as [`ScaleBias`](crate::ScaleBias) or [`Curve`](crate::Curve).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    /// Smallest sampled value.
+    pub min: f64,
+
+    /// Largest sampled value.
+    pub max: f64,
+
+    /// Mean of the sampled values.
+    pub mean: f64,
+
+    /// Variance of the sampled values.
+    pub variance: f64,
+
+    /// Number of sampled values in each of [`HISTOGRAM_BINS`] bins of equal
+    /// width, spanning from `min` to `max`. Values equal to `max` are counted
+    /// in the last bin, and if all values are equal they are counted in the
+    /// first.
+    ///
+    /// [`HISTOGRAM_BINS`]: Self::HISTOGRAM_BINS
+    pub histogram: [usize; Stats::HISTOGRAM_BINS],
+}
+
+impl Stats {
+    pub const HISTOGRAM_BINS: usize = 16;
+
+    /// Returns the range of values counted in a bin of the histogram.
+    pub fn bin_range(&self, bin: usize) -> (f64, f64) {
+        let width = (self.max - self.min) / Self::HISTOGRAM_BINS as f64;
+        (
+            self.min + width * bin as f64,
+            self.min + width * (bin + 1) as f64,
+        )
+    }
+}
+
+/// Samples `source` on a grid of `size` points spanning `bounds`, and returns
+/// the statistics of the sampled values.
+///
+/// `bounds` holds the lower and upper bound of the region along the _x_ and
+/// _y_ axes. Like [`PlaneMapBuilder`](crate::utils::PlaneMapBuilder), the
+/// points are spaced evenly from the lower bound, up to but excluding the
+/// upper bound.
+///
+/// # Panics
+/// Panics if `size` has no points.
+pub fn sample_stats<S>(source: &S, bounds: [(f64, f64); 2], size: (usize, usize)) -> Stats
+where
+    S: NoiseFn<f64, 2>,
+{
+    let (width, height) = size;
+    assert!(width > 0 && height > 0, "size must have at least one point");
+
+    let [(x_lower, x_upper), (y_lower, y_upper)] = bounds;
+    let x_step = (x_upper - x_lower) / width as f64;
+    let y_step = (y_upper - y_lower) / height as f64;
+
+    let mut values = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let point = [x_lower + x_step * x as f64, y_lower + y_step * y as f64];
+            values.push(source.get(point));
+        }
+    }
+
+    let count = values.len() as f64;
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mean = values.iter().sum::<f64>() / count;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean) * (value - mean))
+        .sum::<f64>()
+        / count;
+
+    let mut histogram = [0; Stats::HISTOGRAM_BINS];
+    let extent = max - min;
+    for value in values {
+        let bin = if extent > 0.0 {
+            ((value - min) / extent * Stats::HISTOGRAM_BINS as f64) as usize
+        } else {
+            0
+        };
+        histogram[bin.min(Stats::HISTOGRAM_BINS - 1)] += 1;
+    }
+
+    Stats {
+        min,
+        max,
+        mean,
+        variance,
+        histogram,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_fn, Constant};
+
+    #[test]
+    fn test_constant_stats() {
+        let stats = sample_stats(&Constant::new(0.25), [(-1.0, 1.0); 2], (8, 4));
+
+        assert_eq!((0.25, 0.25, 0.25), (stats.min, stats.max, stats.mean));
+        assert_eq!(0.0, stats.variance);
+        assert_eq!(32, stats.histogram[0]);
+        assert_eq!(32, stats.histogram.iter().sum::<usize>());
+    }
+
+    #[test]
+    fn test_ramp_stats() {
+        // Samples x = 0, 1, ..., 15 on each of four rows.
+        let ramp = from_fn(|[x, _y]: [f64; 2]| x);
+        let stats = sample_stats(&ramp, [(0.0, 16.0), (-1.0, 1.0)], (16, 4));
+
+        assert_eq!((0.0, 15.0, 7.5), (stats.min, stats.max, stats.mean));
+        assert!((stats.variance - 21.25).abs() < 1e-12);
+        assert_eq!([4; Stats::HISTOGRAM_BINS], stats.histogram);
+        assert_eq!((0.0, 0.9375), stats.bin_range(0));
+        assert_eq!((14.0625, 15.0), stats.bin_range(15));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::noise_fns::NoiseFn;
+use core::marker::PhantomData;
+
+/// Maps the output value from a source function to an RGB color.
+///
+/// Each channel applies its own scaling factor and bias to the output value,
+/// like three [`ScaleBias`](crate::ScaleBias) functions sharing a source. This
+/// turns a noise function into colors directly, without a color gradient.
+///
+/// By default every channel maps the range -1.0 to 1.0 onto 0.0 to 1.0, which
+/// gives a grayscale image.
+#[derive(Clone, Copy, Debug)]
+pub struct ToColor<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Scaling factors of the red, green and blue channels. The default values
+    /// are 0.5.
+    pub scales: [f64; 3],
+
+    /// Biases of the red, green and blue channels. The default values are 0.5.
+    pub biases: [f64; 3],
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> ToColor<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            scales: [0.5; 3],
+            biases: [0.5; 3],
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_red(self, scale: f64, bias: f64) -> Self {
+        self.set_channel(0, scale, bias)
+    }
+
+    pub fn set_green(self, scale: f64, bias: f64) -> Self {
+        self.set_channel(1, scale, bias)
+    }
+
+    pub fn set_blue(self, scale: f64, bias: f64) -> Self {
+        self.set_channel(2, scale, bias)
+    }
+
+    fn set_channel(mut self, channel: usize, scale: f64, bias: f64) -> Self {
+        self.scales[channel] = scale;
+        self.biases[channel] = bias;
+        self
+    }
+
+    /// Returns the color at the point, with each channel scaled and biased
+    /// but not clamped.
+    pub fn get(&self, point: impl Into<[T; DIM]>) -> [f64; 3] {
+        let value = self.source.get(point);
+
+        let mut color = [0.0; 3];
+        for (channel, color) in color.iter_mut().enumerate() {
+            *color = value * self.scales[channel] + self.biases[channel];
+        }
+        color
+    }
+
+    /// Returns the color at the point as bytes, mapping each channel from the
+    /// range 0.0 to 1.0 onto 0 to 255 and clamping it to that range.
+    pub fn get_u8(&self, point: impl Into<[T; DIM]>) -> [u8; 3] {
+        let color = self.get(point);
+
+        let mut bytes = [0; 3];
+        for (byte, channel) in bytes.iter_mut().zip(color.iter()) {
+            *byte = (channel.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn test_known_colors() {
+        let color = |value| {
+            ToColor::<f64, _, 2>::new(Constant::new(value))
+                .set_red(1.0, 0.0)
+                .set_green(-0.5, 0.5)
+                .set_blue(0.0, 0.25)
+        };
+
+        assert_eq!([0.5, 0.25, 0.25], color(0.5).get([0.0, 0.0]));
+        assert_eq!([128, 64, 64], color(0.5).get_u8([0.0, 0.0]));
+
+        // Channels outside the range 0.0 to 1.0 are clamped as bytes.
+        assert_eq!([-1.0, 1.0, 0.25], color(-1.0).get([0.0, 0.0]));
+        assert_eq!([0, 255, 64], color(-1.0).get_u8([0.0, 0.0]));
+
+        // By default, the colors are grayscale.
+        let gray = ToColor::<f64, _, 2>::new(Constant::new(0.0));
+        assert_eq!([0.5; 3], gray.get([1.0, 2.0]));
+    }
+}